@@ -1,149 +1,1467 @@
+param(
+	# Komanda: scrape (download i parsiranje), parse (parsiranje sacuvanih HTML fajlova, uz -Offline svih foldera kompanija), export (izvoz postojecih rezultata),
+	# lookup (pretraga PIB-a po dijelu naziva kompanije), rank (rang liste kompanija za godinu), report (Markdown ili HTML izvjestaj za objavljivanje),
+	# diff (poredjenje dva skupa rezultata), watch (ponavljanje inkrementalnog prikupljanja u zadatom intervalu),
+	# discover (pretraga kompanija po kljucnim rijecima u nazivu, za pregled i dopunu liste kompanija), cache (odrzavanje kesa, vidjeti -CacheAction),
+	# retry (ponovno prikupljanje samo kompanija i godina iz -From, uz spajanje sa postojecim izlazom),
+	# schema (ispis sheme izlaza za -Format kao JSON Schema, sa verzijom sheme),
+	# history (izmjene vrijednosti kompanije -Name kroz pokretanja prikupljanja, iz istorije pokretanja u SQLite bazi),
+	# config (config init upisuje komentarisan sablon konfiguracionog fajla na putanju -Config),
+	# auth (auth set cuva, a auth clear brise tajni podatak -Secret u skladistu kljuceva operativnog sistema)
+	[Parameter(Position = 0)]
+	[ValidateSet("scrape", "parse", "export", "lookup", "rank", "report", "diff", "watch", "discover", "cache", "retry", "schema", "history", "config", "auth")]
+	[string]$Command = "scrape",
 
-# Deklarisanje niza kompanija
-$companies = New-Object "System.Collections.Generic.Dictionary[[String], [String]]"
-$companies.Add("03014215", "Coinis")
-$companies.Add("02686473", "Domen")
-$companies.Add("02775018", "CoreIT")
-$companies.Add("02632284", "Logate")
-$companies.Add("02783061", "Bild Studio")
-$companies.Add("02907259", "Amplitudo")
-$companies.Add("03073572", "Datum Solutions")
-$companies.Add("02713098", "Poslovna Inteligencija")
-$companies.Add("03037258", "International Bridge")
-$companies.Add("02731517", "Fleka")
-$companies.Add("02679744", "Datalab")
-$companies.Add("03167453", "Omnitech")
-$companies.Add("03131343", "SynergySuite")
-$companies.Add("03122123", "Alicorn")
-$companies.Add("03066258", "Codingo")
-$companies.Add("03274357", "Uhura Solutions")
-$companies.Add("02246244", "Winsoft")
-$companies.Add("02177579", "Cikom")
-$companies.Add("02961717", "Media Monkeys")
-$companies.Add("03091627", "Codeus")
-$companies.Add("03084434", "Digital Control")
-$companies.Add("03165663", "Ridgemax")
-$companies.Add("03360962", "Infinum")
-$companies.Add("03191451", "Kodio")
-$companies.Add("03381447", "EPAM")
-$companies.Add("03413772", "First Line Software")
-$companies.Add("03374700", "Vega IT Omega")
-$companies.Add("03373398", "Quantox Technology")
-$companies.Add("03216446", "Ooblee")
-$companies.Add("03209296", "BIXBIT")
-$companies.Add("03367053", "GoldBear Technologies")
-$companies.Add("03421198", "G5 Entertainment")
-$companies.Add("03428184", "Tungsten Montenegro")
-$companies.Add("03110222", "BGS Consulting")
-$companies.Add("03413381", "Artec 3D Adriatica")
-$companies.Add("03413616", "Customertimes Montenegro")
-
-# Formiranje CSV fajla za smjestanje rezultata
-Set-Content -Path "./Results.csv" -Value '"name","Year","totalIncome","profit","employeeCount","netPayCosts","averagePay"'
-
-# Definisanje header-a zbog provizornog ID-a sesije
-$headers = New-Object "System.Collections.Generic.Dictionary[[String],[String]]"
-$headers.Add("Cookie", "taxisSession=ir3pdvm0e20di2u4p2dfh4d4")
-
-foreach ($company in $companies.GetEnumerator()) {
-	Write-Host "`nPrikupljanje podataka za: $($company.Value) ($($company.Key))"
-
-	# Pretraga pravnog lica po PIB-u na portalu ePrijava
-	$pib = $company.Key
-	$response = Invoke-RestMethod "https://eprijava.tax.gov.me/TaxisPortal/FinancialStatement/Grid?pib=$($pib)&naziv=&orderBy=naziv&skip=0&take=1" -Method 'POST' -Headers $headers
-	$taxpayers = $response.TaxPayerRows
-
-	# Pronadjena sljedeca pravna lica
-	foreach ($taxpayer in $taxpayers) {
-		Write-Host "Pronadjen: $($taxpayer.PIB) - $($taxpayer.Naziv)"
-	}
-
-	# Kreiranje pod-foldera za pravno lice
-	New-Item -ItemType Directory -Force -Path "./$($company.Value)\" | Out-Null
-
-	# Detalji pravnog lica
-	Write-Host "`nDownload detalja pravnog lica"
-	$response = Invoke-RestMethod "https://eprijava.tax.gov.me/TaxisPortal/TaxPayerCompanies/Details?PIB=$($pib)" -Method 'POST' -Headers $headers
-	Out-File -FilePath "./$($company.Value)\$($pib).htm" -InputObject $response -Encoding UTF8
-
-	# Pretraga liste finansijskih izvjestaja
-	Write-Host "`nPretraga liste finansijskih izvjestaja"
-	$response = Invoke-RestMethod "https://eprijava.tax.gov.me/TaxisPortal/FinancialStatement/TaxPayerStatementsList?PIB=$($pib)&take=20&skip=0&page=1&pageSize=20" -Method 'POST' -Headers $headers
-	$finStatements = $response.data
-
-	# Pronadjeni sljedeci finansijski izvjestaji
-	Write-Host "Pronadjeno $($finStatements.length) finansijskih izvjestaja"
-	# $finStatements
-
-	# Download svakog pronadjenog finansijskog izvjestaja
-	Write-Host "`nDownload finansijskih izvjestaja..."
-	foreach ($finStatement in $finStatements) {
-		$no = $finStatement.FinStatementNumber
-		$year = $finStatement.Year
-		Write-Host "Download izvjestaja br. $($no) za godinu $($year)"
-		$response = Invoke-RestMethod "https://eprijava.tax.gov.me/TaxisPortal/FinancialStatement/Details?rbr=$($no)" -Method 'POST' -Headers $headers
-
-		# Izvjestaji ce biti sacuvani u formatu: <PIB>-<GODINA>.htm
-		Out-File -FilePath "./$($company.Value)\$($pib)-$($year).html" -InputObject $response -Encoding UTF8
-
-		Write-Host "`nIme firme u obradi u sledecem redu"
-		Write-Host $company.Value
-		Write-Host "./$company.Value\$pib-$year.html"
-
-		$imeFirme = $company.Value
-
-		$content = [IO.File]::ReadAllText("./$imeFirme/$pib-$year.html")
-
-		# Pretraga podatka: totalIncome
-		$totalIncome = 0
-		$pattern = '<td style="text-align: center;">201<\/td>\s*<td><\/td>\s*<td style="text-align: right; padding-right: 8px">(?<totalIncome>\d+)<\/td>'
-		$result = [regex]::Matches($content, $pattern)
-
-		if ($result -ne $null) {
-			$totalIncome = $result[0].Groups['totalIncome'].Value -as [int]
-		}
-
-		# Pretraga podatka: profit
-		$profit = 0
-#		$pattern = '(?:(Neto sveobuhvatni|NETO REZULTAT).+\r\n?|\n.+(260|232).+\r\n?|\n.+\r\n?|\n[^>]+>)(?<profit>\d+)(?:</td>)'
-		$pattern = '<td style="text-align: left">IX. Neto sveobuhvatni rezultat \(248\+259\)<\/td>\s*<td style="text-align: center;">260<\/td>\s*<td><\/td>\s*<td style="text-align: right; padding-right: 8px">(?<profit>\d+)<\/td>'
-
-		$result = [regex]::Matches($content, $pattern)
-		if ($result -ne $null) {
-			$profit = $result[0].Groups['profit'].Value -as [int]
+	# Akcija komande cache: stats (velicina, broj fajlova i udio izvjestaja iz kesa u posljednjem prikupljanju),
+	# prune (brisanje kompanija kojih nema u listi -Companies, uz -DryRun samo ispis) ili compact (komprimovanje svih sacuvanih HTML fajlova);
+	# akcija komande config: init (podrazumijevana), akcije komande auth: set ili clear
+	[Parameter(Position = 1)]
+	[ValidateSet("stats", "prune", "compact", "init", "set", "clear")]
+	[string]$CacheAction = "stats",
+
+	# Redosljed redova izlaza: name (po nazivu kompanije), pib ili none (redosljed iz liste kompanija), uvijek uz godinu i verziju
+	[ValidateSet("name", "pib", "none")]
+	[string]$Sort = "none",
+
+	# Format izlaznog fajla: csv, json (niz kompanija sa godisnjim izvjestajima), jsonl (jedna kompanija po redu), parquet ili xlsx
+	[ValidateSet("csv", "json", "jsonl", "parquet", "xlsx")]
+	[string]$Format = "csv",
+
+	# CSV fajl sa listom kompanija cije izvjestaje treba preuzeti, ili - za listu sa standardnog ulaza
+	[string]$Companies = "$PSScriptRoot/Companies.csv",
+
+	# CSV fajl u koji se upisuju rezultati, ili SQLite baza u formatu sqlite://<putanja>
+	[string]$Output = "./Results.csv",
+
+	# Izbor i redosljed kolona CSV fajla sa rezultatima (npr. name,Year,profit,netMargin), prazno = sve kolone;
+	# ukoliko nije zadat, uzima se kljuc Columns iz konfiguracionog fajla
+	[string[]]$Columns = @(),
+
+	# Komentar sa verzijom sheme izlaza ("#schemaVersion: 1") kao prvi red CSV fajlova, ispred zaglavlja
+	[switch]$SchemaComment,
+
+	# SQLite baza sa istorijom pokretanja (vrijednosti iz svakog prikupljanja, uz vrijeme pokretanja) za komandu history;
+	# uz -Output sqlite://<putanja> istorija se cuva u istoj bazi, a ukoliko nije zadata uzima se kljuc HistoryDatabase iz konfiguracionog fajla
+	[string]$HistoryDatabase = "",
+
+	# Trenutak za koji komanda history prikazuje vrijednosti kakve su tada bile (posljednje pokretanje do tog trenutka), umjesto izmjena
+	[datetime]$AsOf,
+
+	# Kes preuzetih HTML fajlova: <PIB>/<GODINA>.html za izvjestaje, <PIB>/details.html za detalje i manifest.json
+	[Alias("OutputDir")]
+	[string]$CacheDir = "./cache",
+
+	# Najveca starost sacuvanog HTML-a prije ponovnog preuzimanja (npr. 30d, 12h, 0 = bez ogranicenja) i preuzimanje bez koriscenja kesa
+	[string]$MaxCacheAge = "30d",
+	[switch]$Refresh,
+
+	# Cuvanje preuzetih HTML fajlova komprimovanih (gzip, <GODINA>.html.gz); ukoliko nije zadato, uzima se kljuc CompressCache iz konfiguracionog fajla
+	[switch]$CompressCache,
+
+	# Postojeci CSV fajl sa rezultatima (za komande export, rank, report i diff)
+	[string]$Source = "./Results.csv",
+
+	# Siroki format izvoza (za komandu export): jedan red po kompaniji, sa kolonama <POKAZATELJ>_<GODINA> (npr. revenue_2021)
+	# za pokazatelje rang liste i realne iznose (-CpiTable), odnosno za brojcane kolone izabrane sa -Columns
+	# Izvozi se samo u CSV fajl: uz -Format razlicit od csv ili sqlite:// izlaz pokretanje se odbija prije citanja rezultata
+	[switch]$Pivot,
+
+	# Pseudonimizacija izvoza za objavljivanje (za komandu export): naziv i PIB kompanije se zamjenjuju pseudonimom iz HMAC-a sa tajnom
+	# vrijednoscu -AnonymizeSalt (ista vrijednost daje iste pseudonime), a tabela pseudonima se upisuje u privatni fajl -PseudonymMap
+	[switch]$Anonymize,
+	[string]$AnonymizeSalt = "",
+	[string]$PseudonymMap = "./pseudonyms.csv",
+
+	# HTML (ili PDF) fajlovi izvjestaja (za komandu parse)
+	[string[]]$Path,
+
+	# Ponovno formiranje izlaza iz svih izvjestaja sacuvanih u kesu (-CacheDir), bez zahtjeva prema portalu (za komandu parse)
+	# i broj izvjestaja koji se parsiraju istovremeno (0 = broj procesora, 1 = jedan po jedan; paralelno samo u PowerShell 7)
+	[switch]$Offline,
+	[int]$ThrottleLimit = 0,
+
+	# Dio naziva kompanije i najveci broj pronadjenih kompanija (za komandu lookup), odnosno prikazanih mjesta rang liste (za komandu rank);
+	# za komandu history PIB ili dio naziva kompanije, a za scrape uz -Pib naziv te kompanije
+	[string]$Name,
+	[int]$Take = 20,
+
+	# PIB jedne kompanije za scrape, umjesto liste -Companies (bez -Name se naziv uzima iz liste kompanija ili sa portala)
+	[string]$Pib = "",
+
+	# Kljucne rijeci u nazivu kompanija i CSV fajl za upis pronadjenih kompanija kojih nema u listi -Companies (za komandu discover)
+	[string[]]$Keywords = @("softver", "software", "informati", "tech", "digital", "data", "web", "racunar", "kompjuter"),
+	[string]$DiscoverOutput = "./Companies.candidates.csv",
+
+	# Godina rang liste, 0 = posljednja godina u skupu podataka, i CSV fajl za upis svih rang lista (za komandu rank)
+	[int]$Year = 0,
+	[string]$RankOutput = "",
+
+	# Fajl izvjestaja (za komandu report): .md za Markdown, .html za staticku HTML stranicu, i naslov izvjestaja (prazno = naslov na jeziku izlaza)
+	[string]$ReportOutput = "./Report.md",
+	[string]$ReportTitle = "",
+
+	# Jezik izlaza: me (zaglavlja CSV fajlova na crnogorskom) ili en (natpisi izvjestaja i grafikona na engleskom);
+	# ukoliko nije zadat, uzima se kljuc Locale iz konfiguracionog fajla, a bez njega su zaglavlja na engleskom i natpisi na crnogorskom
+	[ValidateSet("me", "en")]
+	[string]$Locale,
+
+	# Prethodni rezultati sa kojima se poredi -Source (za komandu diff) i CSV fajl za upis razlika (za komandu diff i nakon scrape)
+	[string]$Previous = "",
+	[string]$DiffOutput = "",
+
+	# Opseg godina, 0 znaci bez ogranicenja
+	[int]$FromYear = 0,
+	[int]$ToYear = 0,
+
+	# Preuzimanje samo najnovijeg izvjestaja svake kompanije
+	[switch]$Latest,
+
+	# Izbor kompanija: prvo se preuzima samo posljednji izvjestaj svih kompanija iz liste, a puno prikupljanje (sve godine)
+	# se radi samo za N kompanija sa najvecim prihodima, 0 = sve kompanije
+	[int]$Top = 0,
+
+	# Samo kompanije sa bar jednom od zadatih oznaka (kljuc Tags konfiguracionog fajla), za scrape, retry, export, rank i report
+	[string[]]$Tag = @(),
+
+	# Opseg godina u formatu 2019..2023 (ili 2019.., ..2021, 2022), zamjenjuje -FromYear i -ToYear
+	[string[]]$Years,
+
+	# Najveci broj zahtjeva prema portalu u sekundi (0 = bez ogranicenja) i nasumicna dodatna pauza u sekundama
+	# Ukoliko nisu zadati, uzimaju se kljucevi RequestsPerSecond i Jitter iz konfiguracionog fajla
+	[double]$RequestsPerSecond = 2,
+	[double]$Jitter = 0.5,
+
+	# Najveci broj zahtjeva prema portalu i preuzetih bajtova (npr. 500MB, 2GB) jednog pokretanja, 0 i prazno = bez ogranicenja
+	# Po dostizanju ogranicenja prikupljanje se zaustavlja sa izlaznim kodom 4 (nastavak sa -Resume); kljucevi MaxRequests i MaxBytes u konfiguracionom fajlu
+	[int]$MaxRequests = 0,
+	[string]$MaxBytes = "",
+
+	# Ponavljanje zahtjeva nakon privremenih gresaka: broj pokusaja, pocetna pauza u sekundama (udvostrucava se) i HTTP statusi
+	[int]$RetryAttempts = 3,
+	[double]$RetryDelay = 2,
+	[int[]]$RetryOnStatus = @(408, 429, 500, 502, 503, 504),
+
+	# Broj izvjestaja po stranici liste finansijskih izvjestaja (lista se preuzima stranicu po stranicu)
+	[int]$PageSize = 20,
+
+	# Model zarade: broj isplata godisnje (12, ili 13 uz trinaestu platu) za prosjecnu neto zaradu i stope doprinosa zaposlenog,
+	# poreza na dohodak i prireza na porez (udio, npr. 0.24) za procjenu bruto zarade; ukoliko nisu zadati, uzimaju se istoimeni kljucevi iz konfiguracionog fajla
+	[int]$SalaryDivisor = 12,
+	[decimal]$ContributionRate = 0.24,
+	[decimal]$TaxRate = 0.09,
+	[decimal]$SurtaxRate = 0.13,
+
+	# Snimanje svih pozicija bilansa uspjeha u JSON fajl za svaku kompaniju i godinu
+	[switch]$DumpStatements,
+
+	# Preuzimanje i PDF verzije svakog izvjestaja u kes (<PIB>/<GODINA>.pdf) i putanja PDF-a na portalu sa {number} (redni broj izvjestaja),
+	# ukoliko putanja nije zadata uzima se kljuc PdfPath iz konfiguracionog fajla, odnosno podrazumijevana putanja
+	[switch]$Pdf,
+	[string]$PdfPath = "",
+
+	# Dodatne kolone iz statistickog aneksa (broj vlasnika, zaposleni po kvartalima, struktura vlasnistva)
+	[switch]$StatAnnex,
+
+	# Dodatne kolone iz registra privrednih subjekata (datum osnivanja, djelatnost, adresa, osnivaci) i sablon adrese stranice
+	# kompanije u registru sa {pib}; ukoliko adresa nije zadata, uzima se kljuc RegistryUrl iz konfiguracionog fajla
+	[switch]$Registry,
+	[string]$RegistryUrl = "",
+
+	# Lista obveznika PDV-a (CSV fajl preuzet sa sajta Poreske uprave) za kolone vatRegistered i vatActive i upozorenje
+	# za kompanije koje nisu aktivni obveznici PDV-a; ukoliko nije zadata, uzima se kljuc VatList iz konfiguracionog fajla
+	[string]$VatList = "",
+
+	# Tabela indeksa potrosackih cijena (CSV fajl sa kolonama year i index) za kolone realTotalIncome i realAveragePay u cijenama
+	# bazne godine (0 = posljednja godina tabele); ukoliko nisu zadati, uzimaju se kljucevi CpiTable i CpiBaseYear iz konfiguracionog fajla
+	[string]$CpiTable = "",
+	[int]$CpiBaseYear = 0,
+
+	# Spajanje sa postojecim CSV fajlom: dodaju se samo novi redovi, a izmijenjeni (isti naziv i godina) se zamjenjuju
+	[switch]$Append,
+
+	# Inkrementalno prikupljanje: preuzimaju se samo godine kojih nema u postojecem izlazu (-Output), uz spajanje kao kod -Append
+	[switch]$Incremental,
+
+	# Nastavak prekinutog pokretanja: vec obradjene kompanije i izvjestaji se preskacu, a rezultati dodaju u postojeci CSV
+	[switch]$Resume,
+
+	# Fajl sa stanjem prikupljanja
+	[string]$StateFile = "./.scrape-state.json",
+
+	# Zaustavljanje prikupljanja nakon prve kompanije sa greskom (preskocena kompanija ili neuspjelo preuzimanje), nastavak sa -Resume
+	[switch]$FailFast,
+
+	# CSV fajl sa izvedenim pokazateljima (marza, prihod po zaposlenom, godisnji rast), prazno = bez pokazatelja
+	[string]$MetricsOutput = "./Metrics.csv",
+
+	# CSV fajl sa anomalijama (prosjecna zarada ispod minimalne, prevelik prihod po zaposlenom, neto rezultat veci od prihoda,
+	# troskovi zarada bez zaposlenih), prazno = bez provjere; minimalna neto zarada (0 = zakonski minimum za godinu izvjestaja)
+	# i najveci ocekivani prihod po zaposlenom, ukoliko nisu zadati uzimaju se istoimeni kljucevi iz konfiguracionog fajla
+	[string]$AnomaliesOutput = "./anomalies.csv",
+	[decimal]$MinimumWage = 0,
+	[decimal]$MaxRevenuePerEmployee = 1000000,
+
+	# CSV fajl sa zbirnim pregledom sektora po godinama (ukupni prihodi i zaposleni, medijana prosjecne zarade), prazno = bez pregleda
+	[string]$SummaryOutput = "./Summary.csv",
+
+	# CSV fajl sa percentilnim rasponima (25., 50. i 75. percentil) prosjecne zarade, prihoda po zaposlenom i neto marze po godinama, prazno = bez fajla
+	[string]$BenchmarksOutput = "./Benchmarks.csv",
+
+	# Folder za istoriju svake kompanije (<FOLDER>/<KOMPANIJA>/history.csv i history.json), prazno = bez fajlova po kompaniji
+	[string]$CompanyDir = "",
+
+	# SVG grafikoni kretanja prihoda, broja zaposlenih i prosjecne zarade (za svaku kompaniju i sektor) u folderu -ChartsDir
+	[switch]$Charts,
+	[string]$ChartsDir = "./charts",
+
+	# Probno pokretanje: ispis kompanija, izvjestaja i godina koji bi bili preuzeti, bez preuzimanja izvjestaja i upisa izlaza
+	# (za cache prune ispis kompanija koje bi bile obrisane iz kesa)
+	[switch]$DryRun,
+
+	# Fajl sa brojacima pokretanja u Prometheus formatu (npr. u folderu textfile collector-a node-exporter-a), prazno = bez fajla
+	[string]$PrometheusTextfile = "",
+
+	# JSON fajl sa opisom pokretanja (vrijeme, verzija, podesavanja bez tajnih podataka, brojaci, SHA-256 izlaznih fajlova), prazno = bez fajla
+	[string]$RunMetadata = "./run.json",
+
+	# Razmak izmedju dva prikupljanja za komandu watch (npr. 7d, 12h), ukoliko nije zadat uzima se kljuc WatchInterval iz konfiguracionog fajla
+	[string]$WatchInterval = "7d",
+
+	# Webhook URL (Slack/Discord) za obavjestenje o novim izvjestajima, ukoliko nije zadat uzima se kljuc WebhookUrl iz konfiguracionog fajla
+	[string]$WebhookUrl = "",
+
+	# Primalac e-maila sa izvjestajem nakon uspjesnog prikupljanja (vise adresa odvojenih zarezom)
+	# SMTP server i ostala podesavanja se zadaju u konfiguracionom fajlu (SmtpServer, SmtpPort, SmtpUser, MailFrom, MailTo...)
+	[string]$MailTo = "",
+
+	# Format poruka o toku prikupljanja: text ili json (jedan JSON dogadjaj po redu, za CI/cron pokretanja)
+	[ValidateSet("text", "json")]
+	[string]$LogFormat = "text",
+
+	# Izvjestaj o greskama pokretanja: preskocene kompanije, neuspjela preuzimanja i podaci koji nisu pronadjeni
+	[string]$ErrorReport = "./errors.json",
+
+	# CSV fajl sa kompanijama i izvjestajima koji nisu obradjeni (preskocene kompanije i neuspjela preuzimanja), prazno = bez fajla
+	[string]$FailuresOutput = "",
+
+	# CSV fajl neobradjenih kompanija i izvjestaja iz prethodnog pokretanja (-FailuresOutput), za komandu retry
+	[string]$From = "./failures.csv",
+
+	# Vremenska ogranicenja zahtjeva u sekundama (0 = podrazumijevano): uspostavljanje veze (PowerShell 7.4+), liste izvjestaja i
+	# pretraga (JSON), HTML izvjestaji; najveci broj istovremenih veza (Windows PowerShell 5.1) i iskljucivanje keep-alive veza
+	[int]$ConnectTimeout = 15,
+	[int]$ListTimeout = 30,
+	[int]$ReportTimeout = 180,
+	[int]$ConnectionLimit = 0,
+	[switch]$DisableKeepAlive,
+
+	# Proxy za zahtjeve prema portalu (http://host:port ili, u PowerShell 7, socks5://host:port), User-Agent zaglavlje i
+	# PEM fajl sa dodatnim CA sertifikatima (Windows PowerShell 5.1); ukoliko nisu zadati, uzimaju se istoimeni kljucevi iz konfiguracionog fajla
+	[string]$Proxy = "",
+	[string]$UserAgent = "",
+	[string]$CaBundle = "",
+
+	# Kontakt (npr. e-mail adresa) koji se dodaje u podrazumijevani User-Agent, kako bi administrator portala mogao da se javi;
+	# ukoliko nije zadat, uzima se kljuc Contact iz konfiguracionog fajla
+	[string]$Contact = "",
+
+	# Bez preuzimanja robots.txt portala; inace Crawl-delay iz robots.txt povecava razmak izmedju zahtjeva (kljuc IgnoreRobots u konfiguracionom fajlu)
+	[switch]$IgnoreRobots,
+
+	# Snimanje odgovora portala u folder kasete (-Record) ili reprodukovanje snimljenih odgovora bez pristupa portalu (-Replay)
+	[string]$Record = "",
+	[string]$Replay = "",
+
+	# Folder u koji se svaki zahtjev prema portalu upisuje u numerisan fajl (URL, zaglavlja bez vrijednosti cookie-ja, status i odgovor),
+	# za prilaganje uz prijavu greske nakon izmjene portala, prazno = bez zapisa
+	[string]$DebugHttp = "",
+
+	# Adresa portala (npr. lokalni testni server), ukoliko nije zadata uzima se kljuc PortalUrl iz konfiguracionog fajla
+	[string]$PortalUrl = "",
+
+	# ID sesije (taxisSession cookie), ima prednost nad promjenljivom EPRIJAVA_SESSION, skladistem kljuceva i konfiguracionim fajlom
+	[string]$Session,
+
+	# Ponasanje kada sesija istekne tokom pokretanja: auto (automatski nova sesija i ponavljanje zahtjeva), prompt (jednom se trazi
+	# unos novog ID-a sesije) ili off (prekid sa izlaznim kodom 3); ukoliko nije zadato, uzima se kljuc SessionRefresh iz konfiguracionog fajla
+	[ValidateSet("auto", "prompt", "off")]
+	[string]$SessionRefresh = "auto",
+
+	# Tajni podatak za komandu auth: Session (ID sesije, uz auth set se uzima iz -Session ili unosi), ProxyPassword ili SmtpPassword
+	[ValidateSet("Session", "ProxyPassword", "SmtpPassword")]
+	[string]$Secret = "Session",
+
+	# Pravila za izdvajanje podataka (PowerShell data fajl u formatu Eprijava/rules.psd1), prazno = podrazumijevana pravila
+	[string]$Rules = "",
+
+	# Konfiguracioni fajl (PowerShell data fajl, vidjeti eprijava.example.psd1), ukoliko nije zadat uzima se promjenljiva EPRIJAVA_CONFIG
+	[string]$Config = "$PSScriptRoot/eprijava.psd1"
+)
+
+# Ucitavanje modula sa logikom prikupljanja
+Import-Module "$PSScriptRoot/Eprijava/Eprijava.psm1" -Force
+
+# JSON poruke bez prikaza napretka, koji nije potreban kada se izlaz obradjuje automatski
+Set-EprijavaLogFormat -Format $LogFormat
+if ($LogFormat -eq "json") {
+	$ProgressPreference = "SilentlyContinue"
+}
+
+# Vrijednosti za parametre koji nisu zadati pri pokretanju: promjenljiva okruzenja EPRIJAVA_<KLJUC>, zatim kljuc konfiguracionog fajla
+if (-not $PSBoundParameters.ContainsKey("Config") -and $env:EPRIJAVA_CONFIG) {
+	$Config = $env:EPRIJAVA_CONFIG
+}
+$configValues = Import-EprijavaConfig -Path $Config
+$configKeys = @("Companies", "Output", "CacheDir", "RequestsPerSecond", "Jitter", "MaxRequests", "MaxBytes", "RetryAttempts", "RetryDelay", "RetryOnStatus", "PageSize", "MaxCacheAge", "Rules", "WatchInterval", "WebhookUrl", "Proxy", "UserAgent", "Contact", "IgnoreRobots", "CaBundle", "ConnectTimeout", "ListTimeout", "ReportTimeout", "ConnectionLimit", "DisableKeepAlive", "PortalUrl", "RegistryUrl", "VatList", "CpiTable", "CpiBaseYear", "SalaryDivisor", "ContributionRate", "TaxRate", "SurtaxRate", "MinimumWage", "MaxRevenuePerEmployee", "PdfPath", "CompressCache", "Columns", "Locale", "HistoryDatabase", "SessionRefresh", "RunMetadata", "AnonymizeSalt", "PseudonymMap")
+$environmentValues = Get-EprijavaEnvironmentConfig -Keys $configKeys
+$configuredKeys = @{}
+foreach ($key in $configKeys) {
+	if ($PSBoundParameters.ContainsKey($key)) {
+		continue
+	}
+	if ($environmentValues.ContainsKey($key)) {
+		Set-Variable -Name $key -Value (ConvertFrom-EprijavaEnvironmentValue -Name (Get-EprijavaEnvironmentName -Key $key) -Value $environmentValues[$key] -Current (Get-Variable -Name $key -ValueOnly))
+		$configuredKeys[$key] = $true
+	}
+	elseif ($configValues.ContainsKey($key)) {
+		Set-Variable -Name $key -Value $configValues[$key]
+		$configuredKeys[$key] = $true
+	}
+}
+
+# Oznake kompanija za -Tag i zbirni pregled po oznakama
+Set-EprijavaCompanyTags -Tags $configValues.Tags
+foreach ($name in $Tag) {
+	if ((Get-EprijavaTags) -notcontains $name) {
+		throw "Nepoznata oznaka '$($name)', oznake se zadaju kljucem Tags u konfiguracionom fajlu"
+	}
+}
+
+# Izmijenjena pravila za izdvajanje podataka
+if ($Rules) {
+	Import-EprijavaRules -Path $Rules
+}
+
+# Model zarade za prosjecnu neto i bruto zaradu
+Set-EprijavaSalaryModel -Divisor $SalaryDivisor -ContributionRate $ContributionRate -TaxRate $TaxRate -SurtaxRate $SurtaxRate
+
+# Izmijenjena adresa portala
+if ($PortalUrl) {
+	Set-EprijavaPortalUrl -Url $PortalUrl
+}
+
+# Izmijenjena putanja PDF verzije izvjestaja
+if ($PdfPath) {
+	Set-EprijavaStatementPdfPath -Path $PdfPath
+}
+
+# Kolone iz registra privrednih subjekata
+if ($Registry) {
+	if (-not $RegistryUrl) {
+		throw "Za -Registry je potrebna adresa stranice registra (-RegistryUrl ili kljuc RegistryUrl u konfiguracionom fajlu)"
+	}
+	Set-EprijavaRegistry -Url $RegistryUrl
+}
+
+# Lista obveznika PDV-a
+if ($VatList) {
+	$vatCount = Import-EprijavaVatList -Path $VatList
+	Write-EprijavaLog -Message "Ucitana lista obveznika PDV-a ($($vatCount) PIB-ova)" -Stage vat -Outcome ok -Data @{ path = $VatList }
+}
+
+# Tabela indeksa potrosackih cijena za realne iznose
+if ($CpiTable) {
+	$cpiCount = Import-EprijavaCpiTable -Path $CpiTable -BaseYear $CpiBaseYear
+	Write-EprijavaLog -Message "Ucitana tabela indeksa potrosackih cijena ($($cpiCount) godina)" -Stage cpi -Outcome ok -Data @{ path = $CpiTable }
+}
+
+# Jezik zaglavlja i natpisa izlaza
+Set-EprijavaLocale -Locale $Locale
+
+# Izabrane kolone CSV fajla sa rezultatima i komentar sa verzijom sheme
+Set-EprijavaResultColumnSelection -Headers $Columns
+Set-EprijavaSchemaComment -Enabled $SchemaComment.IsPresent
+
+if ($Record -and $Replay) {
+	throw "Parametri -Record i -Replay se ne mogu zadati zajedno"
+}
+
+# Opseg godina zadat kao tekst (-Years) ima prednost nad -FromYear i -ToYear
+if ($Years) {
+	$yearRange = ConvertFrom-EprijavaYearRange -Range $Years
+	$FromYear = $yearRange.FromYear
+	$ToYear = $yearRange.ToYear
+}
+
+# Podrazumijevani naziv izlaznog fajla prati format
+if (-not $PSBoundParameters.ContainsKey("Output") -and -not $configuredKeys.ContainsKey("Output") -and $Format -ne "csv") {
+	$Output = "./Results.$($Format)"
+}
+
+if ($Command -eq "export" -and $Pivot -and ($Format -ne "csv" -or $Output -match '^sqlite://')) {
+	throw "Siroki format (-Pivot) se izvozi samo u CSV fajl (-Format csv)"
+}
+
+# Klijent portala sa sesijom, ogranicenjem broja zahtjeva i ponavljanjem prema parametrima skripte (uz brojanje zahtjeva u Stats)
+function New-ScriptClient {
+	param(
+		$Stats = $null
+	)
+
+	$rateLimiter = New-EprijavaRateLimiter -RequestsPerSecond $RequestsPerSecond -Jitter $Jitter
+	$retryPolicy = New-EprijavaRetryPolicy -MaxAttempts $RetryAttempts -BaseDelay $RetryDelay -RetryOnStatus $RetryOnStatus
+
+	# Prijava na proxy iz konfiguracionog fajla (ProxyUser, ProxyPassword), lozinka moze biti i u promjenljivoj EPRIJAVA_PROXY_PASSWORD ili skladistu kljuceva
+	$proxyCredential = $null
+	if ($Proxy -and $configValues.ProxyUser) {
+		$proxyPassword = if ($env:EPRIJAVA_PROXY_PASSWORD) { $env:EPRIJAVA_PROXY_PASSWORD } else { Get-EprijavaSecret -Name ProxyPassword }
+		if (-not $proxyPassword) {
+			$proxyPassword = "$($configValues.ProxyPassword)"
+		}
+		$proxyCredential = [pscredential]::new($configValues.ProxyUser, (ConvertTo-SecureString -String $proxyPassword -AsPlainText -Force))
+	}
+	$requestOptions = New-EprijavaRequestOptions -Proxy $Proxy -ProxyCredential $proxyCredential -UserAgent $UserAgent -Contact $Contact -CaBundle $CaBundle -DisableKeepAlive:$DisableKeepAlive
+	$timeouts = New-EprijavaTimeouts -Connect $ConnectTimeout -List $ListTimeout -Report $ReportTimeout
+	Set-EprijavaConnectionSettings -ConnectionLimit $ConnectionLimit
+
+	# Kaseta sa snimljenim odgovorima portala
+	$cassette = $null
+	if ($Record) {
+		$cassette = Open-EprijavaCassette -Path $Record -Mode record
+	}
+	elseif ($Replay) {
+		$cassette = Open-EprijavaCassette -Path $Replay -Mode replay
+	}
+
+	# Zapis zahtjeva i odgovora za otklanjanje gresaka
+	$httpDump = if ($DebugHttp) { Open-EprijavaHttpDump -Path $DebugHttp } else { $null }
+
+	# Ogranicenje zahtjeva i preuzetih bajtova pokretanja
+	$budget = $null
+	if ($MaxRequests -gt 0 -or $MaxBytes) {
+		$budget = New-EprijavaBudget -MaxRequests $MaxRequests -MaxBytes (ConvertFrom-EprijavaByteSize -Size $MaxBytes)
+	}
+
+	# Crawl-delay iz robots.txt portala se primjenjuje prije uspostavljanja sesije (bez pristupa portalu uz -Replay)
+	New-EprijavaClient -Session (Resolve-EprijavaSession -Session $Session -ConfigPath $Config) -RateLimiter $rateLimiter -RetryPolicy $retryPolicy -Stats $Stats -RequestOptions $requestOptions -Timeouts $timeouts -Cassette $cassette -PageSize $PageSize -SessionRefresh $SessionRefresh -HttpDump $httpDump -Budget $budget -Robots:(-not $IgnoreRobots -and -not $Replay)
+}
+
+# Kompanije za prikupljanje iz izvora zadatog parametrima: plan ponovnog prikupljanja (retry, -From), jedna kompanija (-Pib)
+# ili lista kompanija (-Companies), uz izbor po oznakama (-Tag); tok prikupljanja (scrape, retry, -DryRun, -Top) je isti za kompanije iz bilo kog izvora
+function Get-ScriptCompanies {
+	param(
+		[switch]$Retry
+	)
+
+	# Jedna kompanija: naziv iz -Name, odnosno iz liste kompanija (bez naziva se preuzima sa portala nakon uspostavljanja sesije)
+	if ($Pib -and -not $Retry) {
+		if (-not (Test-EprijavaPib -Pib $Pib)) {
+			throw "Neispravan PIB '$($Pib)' (ocekivano 8 cifara sa ispravnom kontrolnom cifrom)"
+		}
+		$companyName = $Name
+		if (-not $companyName -and (Test-Path -Path $Companies -PathType Leaf)) {
+			$companyName = @(Import-EprijavaCompanyList -Path $Companies | Where-Object { $_.Pib -eq $Pib }).Name | Select-Object -First 1
+		}
+		return @(New-EprijavaModelObject -Type Company -Values @{ Pib = $Pib; Name = $companyName })
+	}
+
+	$companyList = if ($Retry) { Import-EprijavaRetryPlan -Path $From } else { Import-EprijavaCompanyList -Path $Companies }
+	@($companyList | Select-EprijavaTag -Tag $Tag)
+}
+
+# Podaci kompanije iz registra privrednih subjekata uz -Registry
+# Neuspjelo preuzimanje se samo prijavljuje, a kolone registra za kompaniju ostaju prazne
+function Get-ScriptRegistryRecord {
+	param(
+		[Parameter(Mandatory)] $Client,
+		[Parameter(Mandatory)] $Company
+	)
+
+	if (-not $Registry) {
+		return $null
+	}
+
+	try {
+		Get-EprijavaRegistryRecord -Client $Client -Pib $Company.Pib
+	}
+	catch {
+		Write-EprijavaLog -Message "Podaci iz registra za $($Company.Name) ($($Company.Pib)) nisu preuzeti: $($_.Exception.Message)" -Level warning -Stage registry -Outcome failed -Pib $Company.Pib -Name $Company.Name
+		$null
+	}
+}
+
+# Upozorenje za kompanije iz liste koje nisu u listi obveznika PDV-a ili im je registracija brisana (moguce neaktivne kompanije)
+function Write-ScriptVatCheck {
+	param(
+		[Parameter(Mandatory)] [object[]]$Companies
+	)
+
+	if (-not (Test-EprijavaVatListLoaded)) {
+		return
+	}
+
+	foreach ($company in $Companies) {
+		$status = Get-EprijavaVatStatus -Pib $company.Pib
+		if ($status.Active) {
+			continue
+		}
+
+		if ($status.Registered) {
+			Write-EprijavaLog -Message "$($company.Name) ($($company.Pib)): registracija za PDV je brisana" -Level warning -Stage vat -Outcome inactive -Pib $company.Pib -Name $company.Name
+		}
+		else {
+			Write-EprijavaLog -Message "$($company.Name) ($($company.Pib)): nije u listi obveznika PDV-a" -Level warning -Stage vat -Outcome unregistered -Pib $company.Pib -Name $company.Name
+		}
+	}
+}
+
+# Izvedeni pokazatelji za sve rezultate skupa podataka, upisuju se u -MetricsOutput
+function Write-ScriptMetrics {
+	param(
+		[AllowEmptyCollection()] [object[]]$Results
+	)
+
+	if (-not $MetricsOutput) {
+		return
+	}
+
+	$metrics = @(Get-EprijavaDerivedMetrics -Results $Results)
+	Export-EprijavaMetrics -Path $MetricsOutput -Metrics $metrics
+	Write-EprijavaLog -Message "Izvedeni pokazatelji ($($metrics.Count) redova) su upisani u $($MetricsOutput)" -Stage output -Outcome ok -Data @{ path = $MetricsOutput }
+}
+
+# Anomalije u rezultatima skupa podataka, upisuju se u -AnomaliesOutput uz upozorenje za svaku pronadjenu anomaliju
+function Write-ScriptAnomalies {
+	param(
+		[AllowEmptyCollection()] [object[]]$Results
+	)
+
+	if (-not $AnomaliesOutput) {
+		return
+	}
+
+	$anomalies = @(Get-EprijavaAnomalies -Results $Results -MinimumWage $MinimumWage -MaxRevenuePerEmployee $MaxRevenuePerEmployee)
+	foreach ($anomaly in $anomalies) {
+		Write-EprijavaLog -Message "$($anomaly.Name) ($($anomaly.Year)): $($anomaly.Message)" -Level warning -Stage validate -Outcome $anomaly.Check -Pib $anomaly.Pib -Name $anomaly.Name -Year $anomaly.Year
+	}
+	Export-EprijavaAnomalies -Path $AnomaliesOutput -Anomalies $anomalies
+	Write-EprijavaLog -Message "Anomalije ($($anomalies.Count)) su upisane u $($AnomaliesOutput)" -Stage output -Outcome ok -Data @{ path = $AnomaliesOutput }
+}
+
+# Percentilni rasponi sektora po godinama za sve rezultate skupa podataka, upisuju se u -BenchmarksOutput
+function Write-ScriptBenchmarks {
+	param(
+		[AllowEmptyCollection()] [object[]]$Results
+	)
+
+	if (-not $BenchmarksOutput) {
+		return
+	}
+
+	$benchmarks = @(Get-EprijavaBenchmarks -Results $Results)
+	Export-EprijavaBenchmarks -Path $BenchmarksOutput -Benchmarks $benchmarks
+	Write-EprijavaLog -Message "Percentilni rasponi ($($benchmarks.Count) redova) su upisani u $($BenchmarksOutput)" -Stage output -Outcome ok -Data @{ path = $BenchmarksOutput }
+}
+
+# Upozorenja za posljednju godinu svake kompanije, po pravilima sa granicama iz kljuca Alerts konfiguracionog fajla
+# Na webhook (-WebhookUrl) se salju samo upozorenja kojih nije bilo u prethodnom izlazu, kako se ista upozorenja ne bi slala nakon svakog prikupljanja
+function Write-ScriptAlerts {
+	param(
+		[AllowEmptyCollection()] [object[]]$Results,
+		[AllowEmptyCollection()] [object[]]$PreviousResults = $null
+	)
+
+	if (-not $configValues.Alerts -or $configValues.Alerts.Count -eq 0) {
+		return
+	}
+
+	$alerts = @(Get-EprijavaAlerts -Results $Results -Thresholds $configValues.Alerts)
+	foreach ($alert in $alerts) {
+		Write-EprijavaLog -Message "$($alert.Name) ($($alert.Year)): $($alert.Message)" -Level warning -Stage alert -Outcome $alert.Rule -Pib $alert.Pib -Name $alert.Name -Year $alert.Year
+	}
+	if ($alerts.Count -gt 0 -and -not (Test-EprijavaJsonLog)) {
+		Write-Host "`nUpozorenja ($($alerts.Count)):"
+		$alerts | Sort-Object Name, Rule | Format-Table Name, Year, Rule, PreviousValue, Value, Threshold -AutoSize | Out-Host
+	}
+
+	$known = @{}
+	if ($null -ne $PreviousResults) {
+		foreach ($alert in (Get-EprijavaAlerts -Results $PreviousResults -Thresholds $configValues.Alerts)) {
+			$known["$(Get-EprijavaCompanyKey -Result $alert)|$($alert.Year)|$($alert.Rule)"] = $true
+		}
+	}
+	$new = @($alerts | Where-Object { -not $known.ContainsKey("$(Get-EprijavaCompanyKey -Result $_)|$($_.Year)|$($_.Rule)") })
+	if (-not $WebhookUrl -or $new.Count -eq 0) {
+		return
+	}
+
+	try {
+		Send-EprijavaAlertWebhook -Url $WebhookUrl -Alerts $new
+		Write-EprijavaLog -Message "Obavjestenje o $($new.Count) novih upozorenja je poslato" -Stage notify -Outcome ok
+	}
+	catch {
+		Write-EprijavaLog -Message "Slanje upozorenja nije uspjelo: $($_.Exception.Message)" -Level warning -Stage notify -Outcome failed
+	}
+}
+
+# Zbirni pregled sektora po godinama za sve rezultate skupa podataka (i po oznakama, ukoliko su zadate), upisuje se u -SummaryOutput
+function Write-ScriptSummary {
+	param(
+		[AllowEmptyCollection()] [object[]]$Results
+	)
+
+	if (-not $SummaryOutput) {
+		return
+	}
+
+	$summary = @(Get-EprijavaSectorSummary -Results $Results)
+	$tagSummary = @(Get-EprijavaTagSummary -Results $Results)
+	Export-EprijavaSectorSummary -Path $SummaryOutput -Summary ($summary + $tagSummary)
+	Write-EprijavaLog -Message "Zbirni pregled ($($summary.Count) godina, $(@(Get-EprijavaTags).Count) oznaka) je upisan u $($SummaryOutput)" -Stage output -Outcome ok -Data @{ path = $SummaryOutput }
+}
+
+# Istorija svake kompanije u posebnim fajlovima, upisuje se u -CompanyDir
+function Write-ScriptCompanyHistory {
+	param(
+		[AllowEmptyCollection()] [object[]]$Results
+	)
+
+	if (-not $CompanyDir) {
+		return
+	}
+
+	$count = Export-EprijavaCompanyHistory -Path $CompanyDir -Results $Results -StatAnnex:$StatAnnex
+	Write-EprijavaLog -Message "Istorija $($count) kompanija je upisana u $($CompanyDir)" -Stage output -Outcome ok -Data @{ path = $CompanyDir }
+}
+
+# Grafikoni za sve rezultate skupa podataka, upisuju se u -ChartsDir uz parametar -Charts
+function Write-ScriptCharts {
+	param(
+		[AllowEmptyCollection()] [object[]]$Results
+	)
+
+	if (-not $Charts) {
+		return
+	}
+
+	$count = Export-EprijavaCharts -Path $ChartsDir -Results $Results
+	Write-EprijavaLog -Message "Grafikoni ($($count)) su upisani u $($ChartsDir)" -Stage output -Outcome ok -Data @{ path = $ChartsDir }
+}
+
+# Ispis razlika izmedju dva skupa rezultata (izmjene i nestale kompanije pojedinacno) i upis u -DiffOutput
+function Write-ScriptDiff {
+	param(
+		[AllowEmptyCollection()] [object[]]$Diff
+	)
+
+	$counts = foreach ($kind in @("NewYear", "Changed", "RemovedYear", "RemovedCompany")) {
+		"$($kind) $(@($Diff | Where-Object { $_.Kind -eq $kind }).Count)"
+	}
+	Write-EprijavaLog -Message "`nRazlike u odnosu na prethodne rezultate: $($counts -join ', ')" -Stage diff -Outcome ok
+	$details = @($Diff | Where-Object { $_.Kind -ne "NewYear" })
+	if ($details.Count -gt 0 -and -not (Test-EprijavaJsonLog)) {
+		$details | Format-Table Kind, Name, Year, Field, OldValue, NewValue -AutoSize
+	}
+
+	if ($DiffOutput) {
+		Export-EprijavaDiff -Path $DiffOutput -Diff $Diff
+		Write-EprijavaLog -Message "Razlike ($($Diff.Count)) su upisane u $($DiffOutput)" -Stage output -Outcome ok -Data @{ path = $DiffOutput }
+	}
+}
+
+# Obavjestenje na -WebhookUrl o godinama koje nisu postojale u prethodnom izlazu, sa osnovnim podacima izvjestaja
+# Neuspjelo slanje se samo prijavljuje, jer su rezultati vec upisani
+function Send-ScriptWebhook {
+	param(
+		[AllowEmptyCollection()] [object[]]$Diff,
+		[AllowEmptyCollection()] [object[]]$Results
+	)
+
+	$newYears = @{}
+	foreach ($entry in ($Diff | Where-Object { $_.Kind -eq "NewYear" })) {
+		$newYears["$(Get-EprijavaCompanyKey -Result $entry)|$($entry.Year)"] = $true
+	}
+	if (-not $WebhookUrl -or $newYears.Count -eq 0) {
+		return
+	}
+
+	$filings = @($Results | Where-Object { $_.Latest -ne 0 -and $newYears.ContainsKey("$(Get-EprijavaCompanyKey -Result $_)|$([int]$_.Year)") })
+	try {
+		Send-EprijavaWebhook -Url $WebhookUrl -Filings $filings
+		Write-EprijavaLog -Message "Obavjestenje o $($filings.Count) novih izvjestaja je poslato" -Stage notify -Outcome ok
+	}
+	catch {
+		Write-EprijavaLog -Message "Slanje obavjestenja nije uspjelo: $($_.Exception.Message)" -Level warning -Stage notify -Outcome failed
+	}
+}
+
+# E-mail sa pregledom pokretanja i greskama, uz zbirni pregled, rezultate i izvjestaj o greskama u prilogu
+# Salje se samo kada je SMTP server podesen u konfiguracionom fajlu, a neuspjelo slanje se samo prijavljuje
+function Send-ScriptMailReport {
+	param(
+		[Parameter(Mandatory)] [System.Collections.IDictionary]$Summary,
+		[Parameter(Mandatory)] $Errors
+	)
+
+	$settings = Get-EprijavaMailSettings -Config $configValues -To $MailTo
+	if (-not $settings) {
+		return
+	}
+
+	$lines = [System.Collections.Generic.List[string]]::new()
+	$lines.Add("Obradjeno kompanija: $($Summary.processed) od $($Summary.companies)")
+	$lines.Add("Preuzeto izvjestaja: $($Summary.downloaded), ucitano iz kesa: $($Summary.cacheHits)")
+	$lines.Add("Preskocene kompanije: $($Summary.skippedCompanies), neuspjela preuzimanja: $($Summary.downloadFailed), izvjestaji sa nepronadjenim podacima: $($Summary.extractionFailed)")
+	if ($Errors.Entries.Count -gt 0) {
+		$lines.Add("")
+		$lines.Add("Greske:")
+		foreach ($entry in $Errors.Entries) {
+			$year = if ($entry.Year) { " $($entry.Year)" } else { "" }
+			$lines.Add("- [$($entry.Kind)] $($entry.Name) ($($entry.Pib))$($year): $($entry.Reason)")
+		}
+	}
+
+	$attachments = @($SummaryOutput, $ErrorReport)
+	if (-not $database) {
+		$attachments += $Output
+	}
+	try {
+		Send-EprijavaMailReport -Settings $settings -Subject "ePrijava prikupljanje $((Get-Date).ToString('yyyy-MM-dd')): $($Summary.processed) kompanija, $($Errors.Entries.Count) gresaka" -Body ($lines -join "`r`n") -Attachments $attachments
+		Write-EprijavaLog -Message "Izvjestaj je poslat na $($settings.To)" -Stage notify -Outcome ok
+	}
+	catch {
+		Write-EprijavaLog -Message "Slanje e-maila nije uspjelo: $($_.Exception.Message)" -Level warning -Stage notify -Outcome failed
+	}
+}
+
+# Brojaci pokretanja za Prometheus, upisuju se u -PrometheusTextfile
+function Write-ScriptPrometheusMetrics {
+	param(
+		[Parameter(Mandatory)] $Stats,
+		$ErrorReport = $null
+	)
+
+	if ($PrometheusTextfile) {
+		Export-EprijavaPrometheusMetrics -Path $PrometheusTextfile -Stats $Stats -ErrorReport $ErrorReport
+	}
+}
+
+# Opis pokretanja za porijeklo objavljenih podataka, upisuje se u -RunMetadata
+# Podesavanja su vrijednosti kljuceva konfiguracije nakon parametara, promjenljivih okruzenja i konfiguracionog fajla, uz ID sesije bez vrijednosti
+function Write-ScriptRunMetadata {
+	param(
+		[Parameter(Mandatory)] $Stats,
+		[Parameter(Mandatory)] [System.Collections.IDictionary]$Summary,
+		[Parameter(Mandatory)] [int]$ExitCode
+	)
+
+	if (-not $RunMetadata) {
+		return
+	}
+
+	$snapshot = [ordered]@{}
+	foreach ($key in $configKeys) {
+		$snapshot[$key] = Get-Variable -Name $key -ValueOnly
+	}
+	$snapshot.Session = Resolve-EprijavaSession -Session $Session -ConfigPath $Config
+	$outputs = @($(if ($database) { $database } else { $Output }), $MetricsOutput, $AnomaliesOutput, $SummaryOutput, $BenchmarksOutput, $ErrorReport, $FailuresOutput, $PrometheusTextfile)
+
+	Export-EprijavaRunMetadata -Path $RunMetadata -Stats $Stats -Command $Command -Config $snapshot -Counts $Summary -Outputs $outputs -ExitCode $ExitCode
+	Write-EprijavaLog -Message "Opis pokretanja je upisan u $($RunMetadata)" -Stage output -Outcome ok -Data @{ path = $RunMetadata }
+}
+
+# Izvjestaj o podacima koji nisu pronadjeni u parsiranim izvjestajima (kompanija, godina i polja)
+function Write-ScriptParseReport {
+	param(
+		[AllowEmptyCollection()] [object[]]$Results
+	)
+
+	# U JSON formatu je svaki izvjestaj sa nepronadjenim podacima vec prijavljen (faza parse)
+	if (Test-EprijavaJsonLog) {
+		return
+	}
+
+	$failed = @($Results | Where-Object { $_.MissingFields.Count -gt 0 })
+	if ($failed.Count -eq 0) {
+		Write-Host "`nSvi podaci su pronadjeni u $($Results.Count) izvjestaja"
+		return
+	}
+
+	Write-Host "`nPodaci koji nisu pronadjeni ($($failed.Count) od $($Results.Count) izvjestaja):"
+	$failed | Format-Table Name, Year, Layout, @{ Label = "Polja"; Expression = { $_.MissingFields -join ", " } } -AutoSize | Out-Host
+}
+
+# Tabela kompanija i izvjestaja koji nisu obradjeni i upis u -FailuresOutput
+function Write-ScriptFailures {
+	param(
+		[Parameter(Mandatory)] $ErrorReport
+	)
+
+	$failures = @(Get-EprijavaFailures -Report $ErrorReport)
+	if ($FailuresOutput) {
+		Export-EprijavaFailures -Report $ErrorReport -Path $FailuresOutput
+		Write-EprijavaLog -Message "Neobradjene kompanije i izvjestaji ($($failures.Count)) su upisani u $($FailuresOutput)" -Stage output -Outcome ok -Data @{ path = $FailuresOutput }
+	}
+
+	# U JSON formatu je svaka greska vec prijavljena (faze company i download)
+	if ($failures.Count -eq 0 -or (Test-EprijavaJsonLog)) {
+		return
+	}
+
+	Write-Host "`nKompanije i izvjestaji koji nisu obradjeni ($($failures.Count)):"
+	$failures | Format-Table Kind, Name, Pib, Year, Number, Reason -AutoSize -Wrap | Out-Host
+}
+
+# Izlazni kod: 0 (sve uspjesno), 2 (preskocene kompanije ili neuspjela preuzimanja), 3 (istek sesije), 130 (prekid sa Ctrl+C)
+$exitCode = 0
+
+# Izlaz u SQLite bazu (-Output sqlite://<putanja>) umjesto CSV fajla
+$database = if ($Output -match '^sqlite://(?<path>.+)$') { $Matches.path } else { $null }
+
+# Istorija pokretanja: zadata baza, odnosno izlazna SQLite baza
+$historyDatabase = if ($HistoryDatabase) { $HistoryDatabase } else { $database }
+
+# Raniji nazivi kompanija iz liste (kolona aliases), kako bi postojeci rezultati imali naziv iz liste
+if ($Command -notin @("lookup", "discover", "schema", "cache", "config", "auth") -and (Test-Path -Path $Companies -PathType Leaf)) {
+	Set-EprijavaCompanyAliases -Companies @(Import-EprijavaCompanyList -Path $Companies)
+}
+
+switch ($Command) {
+	{ $_ -in @("scrape", "retry") } {
+		# Ucitavanje liste kompanija iz CSV fajla (kolone: pib, name), odnosno za retry kompanija i godina koje nisu obradjene
+		# u prethodnom pokretanju; rezultati ponovnog prikupljanja se spajaju sa postojecim izlazom kao kod -Append
+		$retryYears = @{}
+		if ($Command -eq "retry") {
+			$companyList = Get-ScriptCompanies -Retry
+			foreach ($company in $companyList) {
+				$retryYears[$company.Pib] = @($company.Years)
+			}
+			$Append = $true
+			Write-EprijavaLog -Message "Ponovno prikupljanje $($companyList.Count) kompanija iz $($From)" -Stage run -Outcome retry -Data @{ path = $From; companies = $companyList.Count }
+		}
+		else {
+			$companyList = Get-ScriptCompanies
+		}
+		Write-ScriptVatCheck -Companies @($companyList)
+
+		$stats = New-EprijavaRunStats -Companies @($companyList).Count
+		$client = New-ScriptClient -Stats $stats
+
+		# Naziv kompanije zadate sa -Pib, bez -Name i van liste kompanija, sa portala (ili PIB ukoliko pravno lice nije pronadjeno)
+		foreach ($company in @($companyList | Where-Object { -not $_.Name })) {
+			$taxpayer = @(Find-EprijavaTaxPayer -Client $client -Pib $company.Pib) | Select-Object -First 1
+			$company.Name = if ($taxpayer) { "$($taxpayer.Naziv)".Trim() } else { $company.Pib }
+		}
+
+		# Probno pokretanje salje samo zahtjeve za liste izvjestaja (provjera liste kompanija i sesije prije dugog prikupljanja)
+		if ($DryRun) {
+			$dryRunCache = if (Test-Path -Path $CacheDir -PathType Container) { Open-EprijavaCache -Path $CacheDir -MaxAge (ConvertFrom-EprijavaCacheAge -Age $MaxCacheAge) -Refresh:$Refresh } else { $null }
+			$planned = @(foreach ($company in $companyList) {
+				Get-EprijavaScrapePlan -Client $client -Pib $company.Pib -Name $company.Name -Cache $dryRunCache -FromYear $FromYear -ToYear $ToYear -Latest:$Latest
+			})
+			$planned | Format-Table Name, Pib, Year, Version, Number, Cached -AutoSize | Out-Host
+			$companiesWithoutStatements = @($companyList | Where-Object { $planned.Pib -notcontains $_.Pib })
+			foreach ($company in $companiesWithoutStatements) {
+				Write-Warning "Nema izvjestaja za: $($company.Name) ($($company.Pib))"
+			}
+			Write-Host "Probno pokretanje: $($companyList.Count) kompanija, $($planned.Count) izvjestaja ($(@($planned | Where-Object { -not $_.Cached }).Count) za preuzimanje, $(@($planned | Where-Object Cached).Count) iz kesa)"
+			break
 		}
 
-		# Pretraga podatka: employeeCount
-		$employeeCount = 0
-#		$pattern = '(?:(broj zaposlenih).+\r\n?|\n.+(002).+\r\n?|\n.+\r\n?|\n[^>]+>)(?<employeeCount>\d+)(?:</td>)'
-		$pattern = '<td style="text-align: left">Prosje\?an broj zaposlenih \(ukupan broj zaposlenih krajem svakog mjeseca podijeljen sa brojem mjeseci\)<\/td>\s*<td style="text-align: center;">001<\/td>\s*<td><\/td>\s*<td style="text-align: right; padding-right: 8px">(?<employeeCount>\d+)<\/td>'
+		$cache = Open-EprijavaCache -Path $CacheDir -MaxAge (ConvertFrom-EprijavaCacheAge -Age $MaxCacheAge) -Refresh:$Refresh -Compress:$CompressCache
 
-		$result = [regex]::Matches($content, $pattern)
-		if ($result -ne $null) {
-			$employeeCount = $result[0].Groups['employeeCount'].Value -as [int]
+		# Izbor N kompanija sa najvecim prihodima u posljednjoj godini, preuzeti posljednji izvjestaji se za puno prikupljanje citaju iz kesa
+		if ($Top -gt 0) {
+			Write-EprijavaLog -Message "Izbor $($Top) kompanija sa najvecim prihodima od $($companyList.Count) kandidata" -Stage top -Outcome started -Data @{ top = $Top; candidates = $companyList.Count }
+			try {
+				$companyList = @(Select-EprijavaTopCompanies -Client $client -Companies $companyList -Cache $cache -Top $Top -FromYear $FromYear -ToYear $ToYear -Stats $stats)
+			}
+			catch {
+				if (Test-EprijavaSessionError -ErrorRecord $_) {
+					Save-EprijavaCacheRunStats -Cache $cache -Stats $stats
+					Write-EprijavaLog -Message "Prikupljanje je prekinuto: $($_.Exception.Message)" -Level warning -Stage run -Outcome failed
+					exit 3
+				}
+				throw
+			}
+			$stats.Companies = $companyList.Count
 		}
 
-		Write-Host 'podaci ucitani - - -- -- - -- -- -'
-		Write-Host $totalIncome
-		Write-Host $employeeCount
+		# Formiranje CSV fajla za smjestanje rezultata, osim kada se nastavlja prekinuto pokretanje
+		if ($Resume -and (Test-Path -Path $StateFile -PathType Leaf)) {
+			Write-EprijavaLog -Message "Nastavak prikupljanja na osnovu $($StateFile)" -Stage run -Outcome resumed
+		}
+		else {
+			Remove-Item -Path $StateFile -ErrorAction SilentlyContinue
+		}
+		$checkpoint = Import-EprijavaCheckpoint -Path $StateFile
+		# Postojeci parovi (PIB, godina) za inkrementalno prikupljanje; redovi bez PIB-a se povezuju preko naziva iz liste kompanija
+		$knownYears = @{}
+		$outputExists = if ($database) { Test-Path -Path $database -PathType Leaf } else { Test-Path -Path $Output -PathType Leaf }
+		if ($Incremental) {
+			$Append = $true
+			if ($outputExists) {
+				$pibByName = @{}
+				foreach ($company in $companyList) {
+					$pibByName[$company.Name] = $company.Pib
+				}
+				foreach ($result in (Import-EprijavaDataset -Path $Output)) {
+					$pib = if ($result.Pib) { $result.Pib } else { $pibByName[$result.Name] }
+					if ($pib) {
+						if (-not $knownYears.ContainsKey($pib)) {
+							$knownYears[$pib] = [System.Collections.Generic.List[int]]::new()
+						}
+						$knownYears[$pib].Add([int]$result.Year)
+					}
+				}
+			}
+		}
 
-		# Pretraga podatka: netPayCosts i kalkulacija averagePay
-		$netPayCosts = 0
-		$averagePay = 0
+		# Rezultati prethodnog pokretanja, za poredjenje sa novim izlazom na kraju prikupljanja
+		$previousResults = if ($outputExists) { @(Import-EprijavaDataset -Path $Output) } else { $null }
 
-#		$pattern = '(?:(naknada zarada).+\r\n?|\n.+(212).+\r\n?|\n.+\r\n?|\n[^>]+>)(?<netPayCosts>\d+)(?:</td>)'
-		$pattern = '<td style="text-align: left">a\) Neto troškovi zarada, naknada zarada i lični rashodi<\/td>\s*<td style="text-align: center;">212<\/td>\s*<td><\/td>\s*<td style="text-align: right; padding-right: 8px">(?<netPayCosts>\d+)<\/td>'
-		$result = [regex]::Matches($content, $pattern)
-		if ($result -ne $null) {
-			$netPayCosts = $result[0].Groups['netPayCosts'].Value -as [int]
-			$averagePay = $netPayCosts / $employeeCount / 12
+		# Izlaz se upisuje u privremeni fajl (<izlaz>.tmp), koji zamjenjuje izlaz kada se prikupljanje zavrsi ili prekine sa Ctrl+C
+		# Nakon neocekivanog prekida izlaz ostaje nepromijenjen, a -Resume nastavlja upis u privremeni fajl
+		$resultPath = if ($database) { $null } else { Open-EprijavaResultFile -Path $Output -Keep:(($Append -or $Resume) -and $Format -in @("csv", "jsonl")) }
+		$index = $null
+		$existing = @()
+		$keepOutput = ($Append -or $Resume) -and (Test-Path -Path $Output -PathType Leaf)
+		$keepResults = ($Append -or $Resume) -and $resultPath -and (Test-Path -Path $resultPath -PathType Leaf)
+		# Novo pokretanje u istoriji, vrijednosti svakog rezultata se upisuju uz vrijeme pokretanja
+		$historyRun = if ($historyDatabase) { New-EprijavaDatabaseRun -Path $historyDatabase -StartedAt $stats.StartedAt } else { $null }
+		if ($database) {
+			Initialize-EprijavaDatabase -Path $database
+		}
+		elseif ($Format -eq "json") {
+			# JSON, Parquet i Excel fajl se upisuju na kraju, zajedno sa postojecim rezultatima kada se spaja ili nastavlja
+			if ($keepOutput) {
+				$existing = @(Import-EprijavaJsonResults -Path $Output)
+			}
+		}
+		elseif ($Format -eq "parquet") {
+			if ($keepOutput) {
+				$existing = @(Import-EprijavaParquetResults -Path $Output)
+			}
 		}
+		elseif ($Format -eq "xlsx") {
+			if ($keepOutput) {
+				$existing = @(Import-EprijavaExcelResults -Path $Output)
+			}
+		}
+		elseif ($Format -eq "jsonl") {
+			if (-not $keepResults) {
+				Set-Content -Path $resultPath -Value @() -Encoding UTF8
+			}
+		}
+		elseif ($Append -and $keepResults) {
+			$index = New-EprijavaResultIndex -Path $resultPath -StatAnnex:$StatAnnex
+		}
+		elseif (-not $keepResults) {
+			Set-EprijavaResultFile -Path $resultPath -StatAnnex:$StatAnnex
+		}
+		$merged = @{ Added = 0; Updated = 0; Unchanged = 0 }
+		$allResults = [System.Collections.Generic.List[object]]::new()
+		$errors = New-EprijavaErrorReport
+
+		# Ctrl+C, SIGTERM (ili dostignuto ogranicenje -MaxRequests, -MaxBytes) zaustavlja prikupljanje nakon tekuceg izvjestaja,
+		# uz upis svih rezultata i stanja (nastavak sa -Resume)
+		Enable-EprijavaStopRequest
+		try {
+			$interrupted = $false
+			$failedFast = $false
+			foreach ($company in $companyList) {
+				if ((Test-EprijavaStopRequested) -or (Test-EprijavaBudgetExceeded -Budget $client.Budget)) {
+					$interrupted = $true
+					break
+				}
+
+				Write-EprijavaRunProgress -Stats $stats -Name $company.Name
+				$stats.Processed++
+				$state = Get-EprijavaCheckpointCompany -Checkpoint $checkpoint -Pib $company.Pib
+				if ($state.Completed) {
+					Write-EprijavaLog -Message "`nPreskakanje vec obradjene kompanije: $($company.Name) ($($company.Pib))" -Stage company -Outcome skipped -Pib $company.Pib -Name $company.Name
+					continue
+				}
 
-		# Upis rezultata u Results.csv fajl
-		Add-Content -Path "./Results.csv" -Value """$($company.Value)"", $($year), $($totalIncome), $($profit), $($employeeCount), $($netPayCosts), $($averagePay)"
+				# JSON, Parquet i Excel formati upisuju kompaniju u cjelini, pa se djelimicno obradjena kompanija prikuplja ispocetka
+				$skipReports = if ($database -or $Format -eq "csv") { $state.Reports } else { @() }
+				$companyResults = [System.Collections.Generic.List[object]]::new()
+
+				# Upis svakog rezultata u CSV fajl (ili bazu) i stanje prikupljanja cim je izvjestaj obradjen, JSON formati se upisuju nakon obrade kompanije
+				$skipYears = if ($knownYears.ContainsKey($company.Pib)) { $knownYears[$company.Pib] } else { @() }
+				$years = if ($retryYears.ContainsKey($company.Pib)) { $retryYears[$company.Pib] } else { @() }
+				$errorCount = $errors.Entries.Count
+				$registryRecord = Get-ScriptRegistryRecord -Client $client -Company $company
+				try {
+					Invoke-EprijavaScrape -Client $client -Pib $company.Pib -Name $company.Name -Cache $cache -FromYear $FromYear -ToYear $ToYear -SkipReports $skipReports -SkipYears $skipYears -Years $years -Latest:$Latest -DumpStatements:$DumpStatements -Pdf:$Pdf -ErrorReport $errors -Stats $stats | Add-EprijavaRegistryRecord -Record $registryRecord | Add-EprijavaVatStatus | Add-EprijavaRealValues | ForEach-Object {
+						$companyResults.Add($_)
+						if ($historyRun) {
+							Add-EprijavaDatabaseHistory -Path $historyDatabase -RunId $historyRun -Result $_
+						}
+						if ($database) {
+							Write-EprijavaDatabaseResult -Path $database -Result $_
+						}
+						elseif ($Format -eq "csv") {
+							if ($index) {
+								$merged[(Merge-EprijavaResultRow -Path $resultPath -Result $_ -Index $index -StatAnnex:$StatAnnex)]++
+							}
+							else {
+								Add-EprijavaResultRow -Path $resultPath -Result $_ -StatAnnex:$StatAnnex
+							}
+						}
+						Add-EprijavaCheckpointReport -Checkpoint $checkpoint -Pib $company.Pib -Number $_.Number
+					}
+				}
+				catch {
+					# Istek sesije prekida pokretanje (izlazni kod 3), a ostale greske preskacu kompaniju (uz upis u izvjestaj o greskama)
+					if (Test-EprijavaSessionError -ErrorRecord $_) {
+						Save-EprijavaErrorReport -Report $errors -Path $ErrorReport
+						Write-ScriptPrometheusMetrics -Stats $stats -ErrorReport $errors
+						Save-EprijavaCacheRunStats -Cache $cache -Stats $stats
+						Write-EprijavaLog -Message "Prikupljanje je prekinuto: $($_.Exception.Message)" -Level warning -Stage run -Outcome failed
+						exit 3
+					}
+					# Zahtjev preko ogranicenja nije poslat, a kompanija ostaje nezavrsena za nastavak sa -Resume
+					if (-not (Test-EprijavaBudgetError -ErrorRecord $_)) {
+						Write-EprijavaLog -Message "Preskakanje kompanije $($company.Name) ($($company.Pib)): $($_.Exception.Message)" -Level warning -Stage company -Outcome failed -Pib $company.Pib -Name $company.Name
+						Add-EprijavaErrorEntry -Report $errors -Kind SkippedCompany -Pib $company.Pib -Name $company.Name -Reason $_.Exception.Message
+					}
+				}
+
+				if (-not $database -and $Format -eq "jsonl" -and $companyResults.Count -gt 0) {
+					Add-EprijavaJsonLine -Path $resultPath -Results $companyResults -StatAnnex:$StatAnnex
+				}
+				$allResults.AddRange($companyResults)
+
+				# Kompanija sa preskocenim izvjestajima (ili prekinuta sa Ctrl+C) ostaje nezavrsena, pa se uz -Resume ponovo obradjuje
+				$failed = @($errors.Entries | Select-Object -Skip $errorCount | Where-Object { $_.Kind -notin @("ExtractionFailed", "InconsistentTotals") })
+				if ((Test-EprijavaStopRequested) -or (Test-EprijavaBudgetExceeded -Budget $client.Budget)) {
+					$interrupted = $true
+				}
+				elseif ($failed.Count -eq 0) {
+					Complete-EprijavaCheckpointCompany -Checkpoint $checkpoint -Pib $company.Pib
+				}
+				elseif ($FailFast) {
+					$failedFast = $true
+					Write-EprijavaLog -Message "Prikupljanje je zaustavljeno nakon greske za $($company.Name) ($($company.Pib)) (-FailFast)" -Level warning -Stage run -Outcome stopped -Pib $company.Pib -Name $company.Name
+					break
+				}
+			}
+
+			Complete-EprijavaRunProgress
+		}
+		finally {
+			Disable-EprijavaStopRequest
+		}
+		$budgetExceeded = Test-EprijavaBudgetExceeded -Budget $client.Budget
+
+		# JSON, Parquet i Excel fajl: postojeci rezultati (osim kompanija-godina koje su ponovo prikupljene) i novi rezultati
+		if (-not $database -and $Format -in @("json", "parquet", "xlsx")) {
+			$collected = @{}
+			foreach ($result in $allResults) {
+				$collected[(Get-EprijavaResultKey -Result $result)] = $true
+			}
+			$kept = @($existing | Where-Object { -not $collected.ContainsKey((Get-EprijavaResultKey -Result $_)) })
+			if ($Format -eq "json") {
+				Export-EprijavaJson -Path $resultPath -Results @($kept + $allResults) -StatAnnex:$StatAnnex
+			}
+			elseif ($Format -eq "xlsx") {
+				Export-EprijavaExcel -Path $resultPath -Results @($kept + $allResults) -StatAnnex:$StatAnnex
+			}
+			else {
+				Export-EprijavaParquet -Path $resultPath -Results @($kept + $allResults) -StatAnnex:$StatAnnex
+			}
+		}
+		if (-not $database) {
+			Complete-EprijavaResultFile -Path $Output
+		}
+
+		# Prikupljanje je zavrseno, sljedece pokretanje krece ispocetka (osim kada postoje preskocene kompanije ili izvjestaji, ili je prekinuto)
+		if (-not $interrupted -and -not $failedFast -and @(Get-EprijavaFailures -Report $errors).Count -eq 0) {
+			Remove-Item -Path $StateFile -ErrorAction SilentlyContinue
+		}
 
+		Write-ScriptParseReport -Results $allResults
+		Save-EprijavaErrorReport -Report $errors -Path $ErrorReport
+		Write-EprijavaLog -Message "Izvjestaj o greskama ($($errors.Entries.Count)) je upisan u $($ErrorReport)" -Stage output -Outcome ok -Data @{ path = $ErrorReport }
+		Write-ScriptFailures -ErrorReport $errors
+
+		# Sortiranje izlaza (CSV redovi se upisuju tokom prikupljanja, pa se fajl ponovo upisuje)
+		if ($Sort -ne "none" -and -not $database) {
+			$sorted = @(Sort-EprijavaResults -Results @(Import-EprijavaDataset -Path $Output) -By $Sort)
+			Export-EprijavaDataset -Path $Output -Results $sorted -Format $Format -StatAnnex:$StatAnnex
+		}
+
+		# Pokazatelji se racunaju nad cijelim izlazom, zajedno sa ranije prikupljenim godinama (-Append, -Resume, -Incremental)
+		$dataset = @(Import-EprijavaDataset -Path $Output)
+		Write-ScriptMetrics -Results $dataset
+		Write-ScriptAnomalies -Results $dataset
+		Write-ScriptSummary -Results $dataset
+		Write-ScriptBenchmarks -Results $dataset
+		Write-ScriptCharts -Results $dataset
+		Write-ScriptCompanyHistory -Results $dataset
+		if ($null -ne $previousResults) {
+			$diff = @(Compare-EprijavaDataset -Previous $previousResults -Current $dataset)
+			Write-ScriptDiff -Diff $diff
+			Send-ScriptWebhook -Diff $diff -Results $dataset
+		}
+		Write-ScriptAlerts -Results $dataset -PreviousResults $previousResults
+
+		if ($index) {
+			Write-EprijavaLog -Message "`nSpajanje sa $($Output): dodato $($merged.Added), izmijenjeno $($merged.Updated), bez izmjena $($merged.Unchanged)" -Stage output -Outcome merged -Data @{ added = $merged.Added; updated = $merged.Updated; unchanged = $merged.Unchanged }
+		}
+
+		Write-ScriptPrometheusMetrics -Stats $stats -ErrorReport $errors
+		Save-EprijavaCacheRunStats -Cache $cache -Stats $stats
+
+		# Pregled pokretanja
+		$duration = (Get-Date) - $stats.StartedAt
+		$summary = [ordered]@{
+			companies          = $stats.Companies
+			processed          = $stats.Processed
+			downloaded         = $stats.Downloaded
+			cacheHits          = $stats.CacheHits
+			contentChanged     = $stats.ContentChanged
+			skippedCompanies   = @($errors.Entries | Where-Object { $_.Kind -eq "SkippedCompany" }).Count
+			downloadFailed     = @($errors.Entries | Where-Object { $_.Kind -eq "DownloadFailed" }).Count
+			extractionFailed   = @($errors.Entries | Where-Object { $_.Kind -eq "ExtractionFailed" }).Count
+			inconsistentTotals = @($errors.Entries | Where-Object { $_.Kind -eq "InconsistentTotals" }).Count
+			durationSeconds    = [int]$duration.TotalSeconds
+		}
+		if (Test-EprijavaJsonLog) {
+			Write-EprijavaLog -Message "Prikupljanje je zavrseno" -Stage summary -Outcome ok -Data $summary
+		}
+		else {
+			Write-Host "`nObradjeno kompanija: $($summary.processed) od $($summary.companies)"
+			Write-Host "Preuzeto izvjestaja: $($summary.downloaded), ucitano iz kesa: $($summary.cacheHits), izmijenjen sadrzaj: $($summary.contentChanged)"
+			Write-Host "Preskocene kompanije: $($summary.skippedCompanies), neuspjela preuzimanja: $($summary.downloadFailed), izvjestaji sa nepronadjenim podacima: $($summary.extractionFailed), neusaglasene zbirne pozicije: $($summary.inconsistentTotals)"
+			Write-Host "Trajanje: $($duration.ToString('hh\:mm\:ss'))"
+		}
+		Send-ScriptMailReport -Summary $summary -Errors $errors
+		if ($interrupted) {
+			Write-EprijavaLog -Message "`nPrikupljanje je prekinuto, nastavak sa -Resume" -Level warning -Stage run -Outcome interrupted
+		}
+		if ($summary.skippedCompanies -gt 0 -or $summary.downloadFailed -gt 0) {
+			$exitCode = 2
+		}
+		Write-ScriptRunMetadata -Stats $stats -Summary $summary -ExitCode $(if ($budgetExceeded) { 4 } elseif ($interrupted) { 130 } else { $exitCode })
+	}
+
+	"parse" {
+		if ($Offline) {
+			# Parsiranje svih sacuvanih izvjestaja i upis rezultata u -Output, bez preuzimanja
+			$errors = New-EprijavaErrorReport
+			$throttle = if ($ThrottleLimit -gt 0) { $ThrottleLimit } else { [Environment]::ProcessorCount }
+			# Kes cuva naziv kompanije iz vremena preuzimanja, pa se raniji nazivi zamjenjuju nazivom iz liste
+			$results = @(Invoke-EprijavaOfflineParse -Cache (Open-EprijavaCache -Path $CacheDir) -FromYear $FromYear -ToYear $ToYear -ErrorReport $errors -ThrottleLimit $throttle | Rename-EprijavaCompanyAlias | Add-EprijavaRealValues)
+			$results = @(Sort-EprijavaResults -Results $results -By $Sort)
+			Export-EprijavaDataset -Path $Output -Results $results -Format $Format -StatAnnex:$StatAnnex
+			Write-Host "Parsirano $($results.Length) izvjestaja iz $($CacheDir) u $($Output)"
+			Write-ScriptParseReport -Results $results
+			Save-EprijavaErrorReport -Report $errors -Path $ErrorReport
+			Write-ScriptMetrics -Results $results
+			Write-ScriptAnomalies -Results $results
+			Write-ScriptSummary -Results $results
+			Write-ScriptBenchmarks -Results $results
+			Write-ScriptCharts -Results $results
+			Write-ScriptCompanyHistory -Results $results
+			break
+		}
+		if (-not $Path) {
+			throw "Komanda parse zahtijeva parametar -Path sa HTML (ili PDF) fajlovima izvjestaja ili -Offline"
+		}
+
+		# Parsiranje sacuvanih izvjestaja i ispis izdvojenih vrijednosti
+		$parsed = foreach ($file in (Get-ChildItem -Path $Path -File)) {
+			$values = if ($file.Extension -eq ".pdf") {
+				ConvertFrom-EprijavaStatementPdf -Path $file.FullName
+			}
+			else {
+				ConvertFrom-EprijavaStatementHtml -Content (Read-EprijavaCacheFile -Path $file.FullName)
+			}
+			$values | Add-Member -NotePropertyName File -NotePropertyValue $file.Name -PassThru
+		}
+		$parsed | Format-Table File, Layout, Unit, TotalIncome, Profit, EmployeeCount, NetPayCosts, AveragePay, AverageGrossPay, ParseStatus -AutoSize
+	}
+
+	"export" {
+		# Izvoz postojecih rezultata (CSV, JSON, Parquet, Excel ili sqlite://) u zadati format, uz filtriranje po godinama
+		# i realne iznose po tabeli indeksa (-CpiTable)
+		$results = @(Import-EprijavaDataset -Path $Source | Select-EprijavaYear -FromYear $FromYear -ToYear $ToYear | Select-EprijavaTag -Tag $Tag | Add-EprijavaRealValues)
+		# Pseudonimizacija prije sortiranja, kako redosljed redova ne bi otkrivao nazive kompanija
+		if ($Anonymize) {
+			$pseudonymous = ConvertTo-EprijavaPseudonymousResults -Results $results -Salt $AnonymizeSalt
+			$results = $pseudonymous.Results
+			Export-EprijavaPseudonymMap -Path $PseudonymMap -Mapping $pseudonymous.Mapping
+			Write-EprijavaLog -Message "Tabela pseudonima ($($pseudonymous.Mapping.Count) kompanija) je upisana u $($PseudonymMap), fajl ne treba objavljivati" -Stage output -Outcome ok -Data @{ path = $PseudonymMap }
+		}
+		$results = @(Sort-EprijavaResults -Results $results -By $Sort)
+		if ($Pivot) {
+			$companyCount = Export-EprijavaPivot -Path $Output -Results $results
+			Write-Host "Izvezeno $($companyCount) kompanija (siroki format) u $($Output)"
+			break
+		}
+		Export-EprijavaDataset -Path $Output -Results $results -Format $Format -StatAnnex:$StatAnnex
+		Write-Host "Izvezeno $($results.Length) redova u $($Output)"
 	}
 
+	"rank" {
+		$results = @(Import-EprijavaDataset -Path $Source | Select-EprijavaTag -Tag $Tag)
+		if ($results.Count -eq 0) {
+			throw "Nema rezultata u $($Source)"
+		}
+		if (-not $Year) {
+			$Year = ($results | ForEach-Object { [int]$_.Year } | Measure-Object -Maximum).Maximum
+		}
+
+		# Rang liste po prihodima, dobiti, broju zaposlenih i prosjecnoj zaradi, uz promjenu mjesta u odnosu na prethodnu godinu
+		$ranking = @(foreach ($metric in @("revenue", "profit", "employees", "averagePay")) {
+			Get-EprijavaRanking -Results $results -Year $Year -By $metric
+		})
+		foreach ($metric in ($ranking | Group-Object Metric)) {
+			Write-Host "`nRang lista za $($Year): $($metric.Name)"
+			$metric.Group | Select-Object -First $Take | Format-Table Rank, RankChange, Name, Value, PreviousRank, PreviousValue -AutoSize
+		}
+
+		if ($RankOutput) {
+			Export-EprijavaRanking -Path $RankOutput -Ranking $ranking
+			Write-Host "Rang liste ($($ranking.Length) redova) su upisane u $($RankOutput)"
+		}
+	}
+
+	"report" {
+		# Izvjestaj iz postojecih rezultata, uz filtriranje po godinama i oznakama
+		$results = @(Import-EprijavaDataset -Path $Source | Select-EprijavaYear -FromYear $FromYear -ToYear $ToYear | Select-EprijavaTag -Tag $Tag)
+		Export-EprijavaReport -Path $ReportOutput -Results $results -Title $ReportTitle
+		Write-Host "Izvjestaj za $(@($results | Group-Object Name).Count) kompanija je upisan u $($ReportOutput)"
+	}
+
+	"diff" {
+		if (-not $Previous) {
+			throw "Komanda diff zahtijeva parametar -Previous sa prethodnim rezultatima"
+		}
+
+		# Nove godine, izmijenjene vrijednosti (npr. izmijenjeni izvjestaji) i nestale kompanije u -Source u odnosu na -Previous
+		$diff = @(Compare-EprijavaDataset -Previous @(Import-EprijavaDataset -Path $Previous) -Current @(Import-EprijavaDataset -Path $Source))
+		Write-ScriptDiff -Diff $diff
+		$diff | Where-Object { $_.Kind -eq "NewYear" } | Format-Table Name, Year -AutoSize
+	}
+
+	"watch" {
+		# Inkrementalno prikupljanje sa istim parametrima ponavlja se dok se proces ne zaustavi (Ctrl+C)
+		# Svako prikupljanje kreira novog klijenta, pa se bez -Session uspostavlja nova sesija; istek sesije prekida samo tekuce prikupljanje
+		$interval = ConvertFrom-EprijavaCacheAge -Age $WatchInterval
+		if ($interval -le [timespan]::Zero) {
+			throw "Komanda watch zahtijeva pozitivan interval (-WatchInterval)"
+		}
+
+		$scrapeParameters = @{}
+		foreach ($key in $PSBoundParameters.Keys) {
+			if ($key -notin @("Command", "WatchInterval")) {
+				$scrapeParameters[$key] = $PSBoundParameters[$key]
+			}
+		}
+		$scrapeParameters.Incremental = $true
+
+		while ($true) {
+			$startedAt = Get-Date
+			Write-EprijavaLog -Message "`nPokretanje prikupljanja ($($startedAt.ToString('yyyy-MM-dd HH:mm')))" -Stage watch -Outcome started
+			try {
+				& $PSCommandPath scrape @scrapeParameters
+				if ($LASTEXITCODE -eq 3) {
+					Write-EprijavaLog -Message "Prikupljanje nije uspjelo zbog isteka sesije" -Level warning -Stage watch -Outcome failed
+				}
+			}
+			catch {
+				Write-EprijavaLog -Message "Prikupljanje nije uspjelo: $($_.Exception.Message)" -Level warning -Stage watch -Outcome failed
+			}
+
+			$nextRun = $startedAt + $interval
+			Write-EprijavaLog -Message "Sljedece prikupljanje: $($nextRun.ToString('yyyy-MM-dd HH:mm'))" -Stage watch -Outcome scheduled -Data @{ nextRun = $nextRun.ToUniversalTime().ToString("o") }
+			$wait = $nextRun - (Get-Date)
+			if ($wait -gt [timespan]::Zero) {
+				Start-Sleep -Seconds ([int][math]::Ceiling($wait.TotalSeconds))
+			}
+		}
+	}
+
+	"lookup" {
+		if (-not $Name) {
+			throw "Komanda lookup zahtijeva parametar -Name sa dijelom naziva kompanije"
+		}
+
+		$client = New-ScriptClient
+
+		# Pronadjena pravna lica (PIB i naziv)
+		$taxpayers = @(Find-EprijavaTaxPayer -Client $client -Name $Name -Take $Take)
+		Write-Host "Pronadjeno $($taxpayers.Length) pravnih lica za: $($Name)"
+		$taxpayers | Format-Table PIB, Naziv -AutoSize
+	}
+
+	"discover" {
+		$client = New-ScriptClient
+
+		# Kompanije koje vec postoje u listi se ne predlazu ponovo
+		$known = @{}
+		if (Test-Path -Path $Companies -PathType Leaf) {
+			foreach ($company in (Import-EprijavaCompanyList -Path $Companies)) {
+				$known[$company.Pib] = $true
+			}
+		}
+
+		$candidates = [ordered]@{}
+		foreach ($keyword in $Keywords) {
+			$found = @(Search-EprijavaTaxPayer -Client $client -Name $keyword)
+			$new = @($found | Where-Object { -not $known.ContainsKey($_.Pib) -and -not $candidates.Contains($_.Pib) })
+			foreach ($company in $new) {
+				$candidates[$company.Pib] = $company
+			}
+			Write-EprijavaLog -Message "Kljucna rijec '$($keyword)': $($found.Count) pravnih lica, $($new.Count) novih" -Stage discover -Outcome ok -Data @{ keyword = $keyword; found = $found.Count; new = $new.Count }
+		}
+
+		# Upis u formatu liste kompanija (pib, name), po nazivu, za pregled prije dodavanja u -Companies
+		$candidates.Values | Sort-Object Name | Select-Object @{ Name = "pib"; Expression = { $_.Pib } }, @{ Name = "name"; Expression = { $_.Name } } |
+			Export-Csv -Path $DiscoverOutput -NoTypeInformation -Encoding UTF8
+		Write-EprijavaLog -Message "Pronadjeno $($candidates.Count) kompanija kojih nema u listi, upisane su u $($DiscoverOutput)" -Stage output -Outcome ok -Data @{ path = $DiscoverOutput }
+	}
+
+	"schema" {
+		# Shema se ispisuje na standardni izlaz, pa se moze preusmjeriti u fajl; Parquet i Excel imaju iste kolone kao CSV
+		$schemaFormat = if ($Format -in @("json", "jsonl")) { $Format } else { "csv" }
+		ConvertTo-Json -InputObject (Get-EprijavaResultSchema -Format $schemaFormat) -Depth 10
+	}
+
+	"history" {
+		if (-not $Name) {
+			throw "Komanda history zahtijeva parametar -Name sa PIB-om ili dijelom naziva kompanije"
+		}
+		if (-not $historyDatabase -or -not (Test-Path -Path $historyDatabase -PathType Leaf)) {
+			throw "Istorija pokretanja ne postoji, zadajte -HistoryDatabase ili -Output sqlite://<putanja>"
+		}
+
+		$snapshots = @(Get-EprijavaDatabaseHistory -Path $historyDatabase -Company $Name)
+		if ($snapshots.Count -eq 0) {
+			throw "Nema istorije za: $($Name)"
+		}
+
+		# Uz -AsOf vrijednosti kakve su bile u tom trenutku, inace izmjene izmedju uzastopnih pokretanja
+		if ($PSBoundParameters.ContainsKey("AsOf")) {
+			$asOfSnapshots = @(Select-EprijavaHistorySnapshot -Snapshots $snapshots -AsOf $AsOf)
+			Write-Host "Vrijednosti na dan $($AsOf.ToString('yyyy-MM-dd HH:mm')): $($asOfSnapshots.Count) izvjestaja"
+			$asOfSnapshots | Format-Table Name, Year, Version, Number, TotalIncome, Profit, EmployeeCount, AveragePay, RunAt -AutoSize | Out-Host
+		}
+		else {
+			$changes = @(Compare-EprijavaHistory -Snapshots $snapshots)
+			$runCount = @($snapshots.RunAt | Sort-Object -Unique).Count
+			Write-Host "Pokretanja: $($runCount), izmjene vrijednosti: $($changes.Count)"
+			if ($changes.Count -gt 0) {
+				$changes | Format-Table Name, Year, Version, Field, PreviousValue, Value, PreviousRunAt, RunAt -AutoSize | Out-Host
+			}
+		}
+	}
+
+	"config" {
+		if ($PSBoundParameters.ContainsKey("CacheAction") -and $CacheAction -ne "init") {
+			throw "Komanda config podrzava samo akciju init"
+		}
+		# Sablon je primjer konfiguracionog fajla: aktivni kljucevi imaju podrazumijevane vrijednosti, a ostali su pod komentarom
+		if (Test-Path -Path $Config) {
+			throw "Konfiguracioni fajl $($Config) vec postoji"
+		}
+		$template = Get-Content -Path "$PSScriptRoot/eprijava.example.psd1" -Raw -Encoding UTF8
+		$template = $template -replace '^#[^\r\n]*', "# Konfiguracioni fajl (config init): vrijednost kljuca vazi kada parametar i promjenljiva okruzenja EPRIJAVA_<KLJUC> nisu zadati"
+		Set-Content -Path $Config -Value $template -Encoding UTF8 -NoNewline
+		Write-EprijavaLog -Message "Upisan konfiguracioni fajl $($Config)" -Stage output -Outcome ok -Data @{ path = $Config }
+	}
+
+	"auth" {
+		if ($CacheAction -notin @("set", "clear")) {
+			throw "Komanda auth zahtijeva akciju set ili clear (npr. auth set -Secret Session)"
+		}
+		$secretName = Get-EprijavaSecretName -Name $Secret
+		if ($CacheAction -eq "set") {
+			$value = if ($Secret -eq "Session" -and $Session) { ConvertTo-SecureString -String $Session -AsPlainText -Force } else { Read-Host -Prompt "Vrijednost za $($Secret)" -AsSecureString }
+			Set-EprijavaSecret -Name $Secret -Value $value
+			Write-EprijavaLog -Message "Sacuvano $($secretName) u skladistu $(Get-EprijavaSecretVault)" -Stage auth -Outcome ok -Data @{ secret = $Secret }
+		}
+		elseif (Remove-EprijavaSecret -Name $Secret) {
+			Write-EprijavaLog -Message "Obrisano $($secretName) iz skladista $(Get-EprijavaSecretVault)" -Stage auth -Outcome ok -Data @{ secret = $Secret }
+		}
+		else {
+			Write-EprijavaLog -Message "$($secretName) nije sacuvan u skladistu $(Get-EprijavaSecretVault)" -Level warning -Stage auth -Outcome skipped -Data @{ secret = $Secret }
+		}
+	}
+
+	"cache" {
+		if ($CacheAction -notin @("stats", "prune", "compact")) {
+			throw "Komanda cache podrzava akcije stats, prune i compact"
+		}
+		if (-not (Test-Path -Path $CacheDir -PathType Container)) {
+			throw "Kes $($CacheDir) ne postoji"
+		}
+		$cache = Open-EprijavaCache -Path $CacheDir
+
+		switch ($CacheAction) {
+			"stats" {
+				$cacheStats = Get-EprijavaCacheStats -Cache $cache
+				if (Test-EprijavaJsonLog) {
+					Write-EprijavaLog -Message "Pregled kesa $($CacheDir)" -Stage cache -Outcome stats -Data ([ordered]@{
+						companies       = $cacheStats.Companies
+						files           = $cacheStats.Files
+						bytes           = $cacheStats.Bytes
+						reports         = $cacheStats.Reports
+						compressed      = $cacheStats.Compressed
+						pdfs            = $cacheStats.Pdfs
+						lists           = $cacheStats.Lists
+						manifestEntries = $cacheStats.ManifestEntries
+						missingFiles    = $cacheStats.MissingFiles
+						hitRate         = $cacheStats.HitRate
+					})
+					break
+				}
+
+				Write-Host "Kes: $($CacheDir)"
+				Write-Host "Kompanije: $($cacheStats.Companies), fajlovi: $($cacheStats.Files), velicina: $([math]::Round($cacheStats.Bytes / 1MB, 1)) MB"
+				Write-Host "Izvjestaji: $($cacheStats.Reports) (komprimovano $($cacheStats.Compressed)), PDF: $($cacheStats.Pdfs), liste izvjestaja: $($cacheStats.Lists)"
+				Write-Host "Unosi manifesta: $($cacheStats.ManifestEntries), bez fajla: $($cacheStats.MissingFiles)"
+				if ($cacheStats.LastRun) {
+					Write-Host "Posljednje prikupljanje ($($cacheStats.LastRun.finishedAt)): preuzeto $($cacheStats.LastRun.downloaded), iz kesa $($cacheStats.LastRun.cacheHits) ($([math]::Round($cacheStats.HitRate * 100, 1))%)"
+				}
+			}
+
+			"prune" {
+				# Kompanije kojih vise nema u listi kompanija, uz -DryRun se samo ispisuju
+				$keep = @(Import-EprijavaCompanyList -Path $Companies | ForEach-Object { $_.Pib })
+				$pruned = @(Remove-EprijavaCacheCompanies -Cache $cache -Keep $keep -DryRun:$DryRun)
+				foreach ($company in $pruned) {
+					$action = if ($DryRun) { "Bio bi obrisan" } else { "Obrisan" }
+					Write-EprijavaLog -Message "$($action) kes za $($company.Name) ($($company.Pib)): $($company.Files) fajlova" -Stage cache -Outcome $(if ($DryRun) { "planned" } else { "pruned" }) -Pib $company.Pib -Name $company.Name -Data @{ files = $company.Files; bytes = $company.Bytes }
+				}
+				$bytes = [long]($pruned | Measure-Object -Property Bytes -Sum).Sum
+				Write-EprijavaLog -Message "Kompanija van liste $($Companies): $($pruned.Count), $([math]::Round($bytes / 1MB, 1)) MB" -Stage cache -Outcome $(if ($DryRun) { "planned" } else { "pruned" }) -Data @{ companies = $pruned.Count; bytes = $bytes }
+			}
+
+			"compact" {
+				# Komprimovanje HTML fajlova sacuvanih bez kompresije (npr. prije -CompressCache), parse -Offline ih cita bez izmjena
+				$compaction = Compress-EprijavaCache -Cache $cache
+				$saved = $compaction.BytesBefore - $compaction.BytesAfter
+				Write-EprijavaLog -Message "Komprimovano $($compaction.Files) fajlova u $($CacheDir), usteda $([math]::Round($saved / 1MB, 1)) MB" -Stage cache -Outcome compacted -Data @{ files = $compaction.Files; bytesBefore = $compaction.BytesBefore; bytesAfter = $compaction.BytesAfter }
+			}
+		}
+	}
 }
 
-Write-Host "`nGotovo."
+if ($Command -ne "schema") {
+	Write-EprijavaLog -Message "`nGotovo." -Stage run -Outcome done
+}
+
+# Prikupljanje zaustavljeno zbog ogranicenja zahtjeva ili bajtova zavrsava sa kodom 4
+if ($budgetExceeded) {
+	exit 4
+}
+
+# Prikupljanje prekinuto sa Ctrl+C zavrsava sa kodom 130 (kao i proces prekinut signalom SIGINT)
+if ($interrupted) {
+	exit 130
+}
+exit $exitCode
@@ -0,0 +1,125 @@
+# Upozorenja o kretanju kompanija: pravila sa granicama iz konfiguracije (kljuc Alerts) se provjeravaju za posljednju godinu svake kompanije,
+# uz prethodnu godinu za pravila koja porede dvije godine; pregled se ispisuje na kraju prikupljanja i salje na webhook
+
+# Registrovana pravila, po nazivu
+$script:AlertRules = [ordered]@{}
+
+# Registracija pravila upozorenja (pravilo sa istim nazivom se zamjenjuje)
+# Evaluate dobija posljednju verziju izvjestaja za godinu (Current), za prethodnu godinu ($null ukoliko ne postoji) i granicu iz konfiguracije,
+# a vraca hashtable sa vrijednoscu (Value), vrijednoscu prethodne godine (PreviousValue) i porukom (Message), odnosno $null bez upozorenja
+function Register-EprijavaAlertRule {
+	param(
+		[Parameter(Mandatory)] [string]$Name,
+		[Parameter(Mandatory)] [string]$Description,
+		[Parameter(Mandatory)] [scriptblock]$Evaluate
+	)
+
+	$script:AlertRules[$Name] = [PSCustomObject]@{
+		PSTypeName  = "Eprijava.AlertRule"
+		Name        = $Name
+		Description = $Description
+		Evaluate    = $Evaluate
+	}
+}
+
+# Registrovana pravila upozorenja
+function Get-EprijavaAlertRules {
+	$script:AlertRules.Values
+}
+
+# Pad broja zaposlenih u odnosu na prethodnu godinu veci od granice (udio, 0.2 = 20%)
+Register-EprijavaAlertRule -Name "HeadcountDrop" -Description "Pad broja zaposlenih u odnosu na prethodnu godinu (udio)" -Evaluate {
+	param($Current, $Previous, [decimal]$Threshold)
+
+	if ($null -eq $Previous -or $null -eq $Current.EmployeeCount -or -not ($Previous.EmployeeCount -gt 0)) {
+		return $null
+	}
+	$change = Get-EprijavaRatio -Value ([decimal]$Current.EmployeeCount - [decimal]$Previous.EmployeeCount) -Base $Previous.EmployeeCount
+	if (-$change -gt $Threshold) {
+		@{ Value = $Current.EmployeeCount; PreviousValue = $Previous.EmployeeCount; Message = "Broj zaposlenih je pao sa $($Previous.EmployeeCount) na $($Current.EmployeeCount) ($([math]::Round($change * 100, 1))%)" }
+	}
+}
+
+# Prosjecna zarada ispod granice (EUR), samo za kompanije sa zaposlenima
+Register-EprijavaAlertRule -Name "AveragePayBelow" -Description "Prosjecna neto zarada manja od granice (EUR)" -Evaluate {
+	param($Current, $Previous, [decimal]$Threshold)
+
+	if ($null -eq $Current.AveragePay -or -not ($Current.EmployeeCount -gt 0) -or [decimal]$Current.AveragePay -ge $Threshold) {
+		return $null
+	}
+	@{ Value = $Current.AveragePay; PreviousValue = $Previous.AveragePay; Message = "Prosjecna zarada $($Current.AveragePay) je manja od $($Threshold)" }
+}
+
+# Upozorenja za posljednju godinu svake kompanije, za pravila sa granicom u Thresholds (naziv pravila = granica)
+# Pravila bez granice se ne provjeravaju, a nepoznat naziv pravila je greska u konfiguraciji
+function Get-EprijavaAlerts {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[System.Collections.IDictionary]$Thresholds = @{}
+	)
+
+	foreach ($name in $Thresholds.Keys) {
+		if (-not $script:AlertRules.Contains($name)) {
+			throw "Nepoznato pravilo upozorenja '$($name)' (dostupna pravila: $($script:AlertRules.Keys -join ', '))"
+		}
+	}
+
+	$latest = Get-EprijavaLatestByYear -Results $Results
+	foreach ($company in ($latest.Values | Group-Object { Get-EprijavaCompanyKey -Result $_ })) {
+		$current = $company.Group | Sort-Object { [int]$_.Year } | Select-Object -Last 1
+		$previous = $latest["$($company.Name)|$([int]$current.Year - 1)"]
+		foreach ($rule in $script:AlertRules.Values) {
+			if (-not $Thresholds.Contains($rule.Name)) {
+				continue
+			}
+			$threshold = [decimal]$Thresholds[$rule.Name]
+			$alert = & $rule.Evaluate $current $previous $threshold
+			if ($alert) {
+				[PSCustomObject]@{
+					PSTypeName    = "Eprijava.Alert"
+					Name          = $current.Name
+					Pib           = $current.Pib
+					Year          = [int]$current.Year
+					Rule          = $rule.Name
+					Value         = $alert.Value
+					PreviousValue = $alert.PreviousValue
+					Threshold     = $threshold
+					Message       = $alert.Message
+				}
+			}
+		}
+	}
+}
+
+# Slanje upozorenja na webhook URL, tekst je u poljima text (Slack) i content (Discord), a polje alerts sadrzi podatke za automatsku obradu
+function Send-EprijavaAlertWebhook {
+	param(
+		[Parameter(Mandatory)] [string]$Url,
+		[Parameter(Mandatory)] [object[]]$Alerts
+	)
+
+	$lines = foreach ($alert in ($Alerts | Sort-Object Name, Rule)) {
+		"- $($alert.Name) ($($alert.Year)): $($alert.Message)"
+	}
+	$text = "Upozorenja ($(@($Alerts).Count)):`n$($lines -join "`n")"
+	# Discord ogranicava poruku na 2000 karaktera
+	$content = if ($text.Length -gt 2000) { $text.Substring(0, 1997) + "..." } else { $text }
+	$payload = [ordered]@{
+		text    = $text
+		content = $content
+		alerts  = @(foreach ($alert in $Alerts) {
+			[ordered]@{
+				company       = $alert.Name
+				pib           = $alert.Pib
+				year          = $alert.Year
+				rule          = $alert.Rule
+				value         = $alert.Value
+				previousValue = $alert.PreviousValue
+				threshold     = $alert.Threshold
+			}
+		})
+	}
+
+	$body = [System.Text.Encoding]::UTF8.GetBytes((ConvertTo-Json -InputObject $payload -Depth 4))
+	Invoke-RestMethod -Uri $Url -Method 'POST' -ContentType "application/json; charset=utf-8" -Body $body -ErrorAction Stop | Out-Null
+}
@@ -0,0 +1,45 @@
+# Raniji nazivi kompanija (kolona aliases liste kompanija), kako bi redovi iz ranijih godina i pokretanja
+# nakon promjene naziva kompanije (npr. nakon preuzimanja) imali isti naziv i isti folder kao novi redovi
+
+# Naziv iz liste kompanija, po ranijem nazivu
+$script:CompanyAliases = @{}
+
+# Raniji nazivi kompanija iz liste (Import-EprijavaCompanyList), prazna lista uklanja zadate nazive
+function Set-EprijavaCompanyAliases {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Companies
+	)
+
+	$script:CompanyAliases = @{}
+	foreach ($company in $Companies) {
+		foreach ($alias in @($company.Aliases)) {
+			$script:CompanyAliases[$alias] = $company.Name
+		}
+	}
+}
+
+# Naziv kompanije iz liste za raniji naziv, ostali nazivi ostaju nepromijenjeni
+function Resolve-EprijavaCompanyName {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Name
+	)
+
+	if ($script:CompanyAliases.ContainsKey($Name)) {
+		return $script:CompanyAliases[$Name]
+	}
+	$Name
+}
+
+# Zamjena ranijih naziva nazivom iz liste u rezultatima
+function Rename-EprijavaCompanyAlias {
+	param(
+		[Parameter(Mandatory, ValueFromPipeline)] $Result
+	)
+
+	process {
+		if ($Result.Name) {
+			$Result.Name = Resolve-EprijavaCompanyName -Name $Result.Name
+		}
+		$Result
+	}
+}
@@ -0,0 +1,79 @@
+# Pseudonimizacija skupa podataka za javno objavljivanje: naziv i PIB kompanije se zamjenjuju stalnim pseudonimom, a iznosi ostaju nepromijenjeni
+# Pseudonim je HMAC-SHA256 PIB-a (odnosno naziva za redove bez PIB-a) sa tajnom vrijednoscu (salt), pa je isti u svakom izvozu sa istom
+# vrijednoscu; bez nje se pseudonim ne moze izracunati iz PIB-a (PIB-ova je malo, pa bi se hash bez tajne vrijednosti lako pogodio)
+
+# Broj heksadecimalnih znakova HMAC-a u pseudonimu
+$script:PseudonymLength = 10
+
+# Kolone koje otkrivaju kompaniju, u pseudonimizovanom skupu su prazne: broj izvjestaja na portalu, sve kolone iz registra
+# (datum osnivanja, djelatnost, adresa, osnivaci) i status u listi obveznika PDV-a, koje uz iznose po godinama lako otkrivaju kompaniju
+$script:IdentifyingProperties = @(@("Number") + @($script:RegistryColumns | ForEach-Object { $_.Property }) + @($script:VatColumns | ForEach-Object { $_.Property }))
+
+# Pseudonim kompanije, npr. C3F9A21C04B
+function Get-EprijavaPseudonym {
+	param(
+		[Parameter(Mandatory)] [string]$Key,
+		[Parameter(Mandatory)] [string]$Salt
+	)
+
+	$hmac = [System.Security.Cryptography.HMACSHA256]::new([System.Text.Encoding]::UTF8.GetBytes($Salt))
+	try {
+		$hash = $hmac.ComputeHash([System.Text.Encoding]::UTF8.GetBytes($Key))
+	}
+	finally {
+		$hmac.Dispose()
+	}
+	"C$(([BitConverter]::ToString($hash) -replace '-', '').Substring(0, $script:PseudonymLength))"
+}
+
+# Rezultati sa pseudonimom umjesto naziva i PIB-a i bez kolona koje otkrivaju kompaniju (Results)
+# i tabela pseudonima (Mapping: pib, name, pseudonym) za privatni fajl uz objavljeni skup (Export-EprijavaPseudonymMap)
+function ConvertTo-EprijavaPseudonymousResults {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Salt
+	)
+
+	if ($Salt.Trim() -eq "") {
+		throw "Pseudonimizacija zahtijeva tajnu vrijednost (-AnonymizeSalt ili kljuc AnonymizeSalt)"
+	}
+
+	$mapping = [ordered]@{}
+	$anonymous = foreach ($result in $Results) {
+		$key = Get-EprijavaCompanyKey -Result $result
+		if (-not $mapping.Contains($key)) {
+			$mapping[$key] = [PSCustomObject]@{
+				pib       = $result.Pib
+				name      = $result.Name
+				pseudonym = Get-EprijavaPseudonym -Key $key -Salt $Salt
+			}
+		}
+
+		$copy = $result.PSObject.Copy()
+		$copy.Name = $mapping[$key].pseudonym
+		if ($copy.PSObject.Properties["Pib"]) {
+			$copy.Pib = $mapping[$key].pseudonym
+		}
+		foreach ($property in $script:IdentifyingProperties) {
+			if ($copy.PSObject.Properties[$property]) {
+				$copy.$property = $null
+			}
+		}
+		$copy
+	}
+
+	[PSCustomObject]@{
+		Results = @($anonymous)
+		Mapping = @($mapping.Values)
+	}
+}
+
+# Upis tabele pseudonima u CSV fajl (pib, name, pseudonym); fajl ne treba objavljivati uz skup podataka
+function Export-EprijavaPseudonymMap {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Mapping
+	)
+
+	$Mapping | Export-Csv -Path $Path -NoTypeInformation -Encoding UTF8
+}
@@ -0,0 +1,96 @@
+# Percentilni rasponi sektora po godinama (Benchmarks.csv): 25., 50. i 75. percentil prosjecne zarade, prihoda po zaposlenom i neto marze
+# svih kompanija, i polozaj svake kompanije u odnosu na ostale kompanije iste godine (kolone *Percentile u Metrics.csv)
+
+# Pokazatelji za percentile: naziv pokazatelja i vrijednost iz rezultata (posljednja verzija izvjestaja)
+$script:BenchmarkMetrics = [ordered]@{
+	averagePay         = { param($Result) $Result.AveragePay }
+	revenuePerEmployee = { param($Result) Get-EprijavaRevenuePerEmployee -Result $Result }
+	netMargin          = { param($Result) Get-EprijavaRatio -Value $Result.Profit -Base $Result.TotalIncome }
+}
+
+# Percentil (0-1) sortiranih vrijednosti, uz linearnu interpolaciju izmedju susjednih vrijednosti; $null za praznu listu
+function Get-EprijavaPercentile {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [decimal[]]$Sorted,
+		[Parameter(Mandatory)] [decimal]$Percentile
+	)
+
+	if ($Sorted.Count -eq 0) {
+		return $null
+	}
+
+	$position = $Percentile * ($Sorted.Count - 1)
+	$lower = [int][math]::Floor($position)
+	$upper = [int][math]::Ceiling($position)
+	[math]::Round($Sorted[$lower] + ($Sorted[$upper] - $Sorted[$lower]) * ($position - $lower), 4, [MidpointRounding]::AwayFromZero)
+}
+
+# Polozaj vrijednosti medju vrijednostima kompanija (0-100): udio manjih vrijednosti, uz polovinu jednakih
+function Get-EprijavaPercentileRank {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [decimal[]]$Sorted,
+		[Parameter(Mandatory)] [decimal]$Value
+	)
+
+	if ($Sorted.Count -eq 0) {
+		return $null
+	}
+
+	$below = @($Sorted | Where-Object { $_ -lt $Value }).Count
+	$equal = @($Sorted | Where-Object { $_ -eq $Value }).Count
+	[math]::Round(100 * ($below + $equal / 2) / $Sorted.Count, 1, [MidpointRounding]::AwayFromZero)
+}
+
+# Sortirane vrijednosti svakog pokazatelja po godinama, kljuc je "<GODINA>|<POKAZATELJ>"; kompanije bez podatka se izostavljaju
+function Get-EprijavaBenchmarkValues {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	$values = @{}
+	foreach ($year in (@($Results | Where-Object { $_.Latest -ne 0 }) | Group-Object { [int]$_.Year })) {
+		foreach ($metric in $script:BenchmarkMetrics.GetEnumerator()) {
+			$values["$($year.Name)|$($metric.Key)"] = [decimal[]]@($year.Group | ForEach-Object { & $metric.Value $_ } | Where-Object { $null -ne $_ } | ForEach-Object { [decimal]$_ } | Sort-Object)
+		}
+	}
+	$values
+}
+
+# Percentilni rasponi za svaku godinu i pokazatelj (samo posljednje verzije izvjestaja)
+function Get-EprijavaBenchmarks {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	$values = Get-EprijavaBenchmarkValues -Results $Results
+	foreach ($year in (@($Results | ForEach-Object { [int]$_.Year }) | Sort-Object -Unique)) {
+		foreach ($metric in $script:BenchmarkMetrics.Keys) {
+			$sorted = $values["$($year)|$($metric)"]
+			if (-not $sorted -or $sorted.Count -eq 0) {
+				continue
+			}
+			[PSCustomObject]@{
+				PSTypeName = "Eprijava.Benchmark"
+				Year       = $year
+				Metric     = $metric
+				Companies  = $sorted.Count
+				P25        = Get-EprijavaPercentile -Sorted $sorted -Percentile 0.25
+				P50        = Get-EprijavaPercentile -Sorted $sorted -Percentile 0.5
+				P75        = Get-EprijavaPercentile -Sorted $sorted -Percentile 0.75
+			}
+		}
+	}
+}
+
+# Upis percentilnih raspona u CSV fajl
+function Export-EprijavaBenchmarks {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Benchmarks
+	)
+
+	Set-EprijavaResultFile -Path $Path -Columns $script:BenchmarkColumns
+	if ($Benchmarks.Count -gt 0) {
+		Add-Content -Path $Path -Value @($Benchmarks | ForEach-Object { ConvertTo-EprijavaResultLine -Result $_ -Columns $script:BenchmarkColumns })
+	}
+}
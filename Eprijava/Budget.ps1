@@ -0,0 +1,122 @@
+# Ogranicenje broja zahtjeva i preuzetih bajtova jednog pokretanja, kako petlja koja se ne zavrsava ne bi opterecivala portal
+# Kada se ogranicenje dostigne, prikupljanje se zaustavlja kao nakon Ctrl+C (tekuci izvjestaj se zavrsava, nastavak sa -Resume),
+# a zahtjev preko ogranicenja se ne salje (greska Eprijava.BudgetExceeded)
+
+# Velicina u bajtovima iz teksta u formatu "500MB", "2GB", "800KB" ili broja bajtova (prazno = bez ogranicenja, 0)
+function ConvertFrom-EprijavaByteSize {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Size
+	)
+
+	if (-not $Size) {
+		return [long]0
+	}
+	if ($Size -notmatch '^\s*(?<value>\d+(\.\d+)?)\s*(?<unit>B|KB|MB|GB)?\s*$') {
+		throw "Neispravna velicina '$($Size)' (ocekivano npr. 500MB, 2GB ili broj bajtova)"
+	}
+
+	$factor = switch ($Matches.unit) {
+		"KB" { 1KB }
+		"MB" { 1MB }
+		"GB" { 1GB }
+		default { 1 }
+	}
+	[long]([double]::Parse($Matches.value, [cultureinfo]::InvariantCulture) * $factor)
+}
+
+# Ogranicenje za klijenta (New-EprijavaClient -Budget), 0 = bez ogranicenja
+function New-EprijavaBudget {
+	param(
+		[int]$MaxRequests = 0,
+		[long]$MaxBytes = 0
+	)
+
+	[PSCustomObject]@{
+		PSTypeName  = "Eprijava.Budget"
+		MaxRequests = $MaxRequests
+		MaxBytes    = $MaxBytes
+		Requests    = 0
+		Bytes       = [long]0
+		Exceeded    = $false
+	}
+}
+
+# Greska za zahtjev koji nije poslat jer je ogranicenje vec dostignuto
+function New-EprijavaBudgetError {
+	param(
+		$Budget,
+		[string]$Url
+	)
+
+	$exception = [System.InvalidOperationException]::new("Ogranicenje pokretanja je dostignuto ($($Budget.Requests) zahtjeva, $($Budget.Bytes) bajtova), zahtjev $($Url) nije poslat")
+	[System.Management.Automation.ErrorRecord]::new($exception, "Eprijava.BudgetExceeded", [System.Management.Automation.ErrorCategory]::LimitsExceeded, $Url)
+}
+
+# Provjera da li je greska zahtjev preko ogranicenja, koji se ne ponavlja i ne evidentira kao neuspjelo preuzimanje
+function Test-EprijavaBudgetError {
+	param(
+		[Parameter(Mandatory)] [System.Management.Automation.ErrorRecord]$ErrorRecord
+	)
+
+	$ErrorRecord.FullyQualifiedErrorId -like "Eprijava.BudgetExceeded*"
+}
+
+# Da li je ogranicenje klijenta dostignuto ($false bez ogranicenja)
+function Test-EprijavaBudgetExceeded {
+	param(
+		$Budget
+	)
+
+	$null -ne $Budget -and $Budget.Exceeded
+}
+
+# Oznaka dostignutog ogranicenja, uz upozorenje samo prvi put
+function Set-EprijavaBudgetExceeded {
+	param(
+		[Parameter(Mandatory)] $Budget,
+		[Parameter(Mandatory)] [string]$Reason
+	)
+
+	if ($Budget.Exceeded) {
+		return
+	}
+	$Budget.Exceeded = $true
+	Write-EprijavaLog -Message "Dostignuto je ogranicenje $($Reason), prikupljanje se zaustavlja nakon tekuceg izvjestaja" -Level warning -Stage run -Outcome budget -Data @{ requests = $Budget.Requests; bytes = $Budget.Bytes; maxRequests = $Budget.MaxRequests; maxBytes = $Budget.MaxBytes }
+}
+
+# Brojanje zahtjeva prije slanja: zahtjev nakon dostignutog ogranicenja se ne salje (greska), a zahtjev kojim se dostize -MaxRequests se salje
+function Assert-EprijavaBudget {
+	param(
+		$Budget,
+		[string]$Url
+	)
+
+	if ($null -eq $Budget) {
+		return
+	}
+	if ($Budget.Exceeded) {
+		throw (New-EprijavaBudgetError -Budget $Budget -Url $Url)
+	}
+
+	$Budget.Requests++
+	if ($Budget.MaxRequests -gt 0 -and $Budget.Requests -ge $Budget.MaxRequests) {
+		Set-EprijavaBudgetExceeded -Budget $Budget -Reason "od $($Budget.MaxRequests) zahtjeva"
+	}
+}
+
+# Brojanje preuzetih bajtova nakon odgovora
+function Add-EprijavaBudgetBytes {
+	param(
+		$Budget,
+		[long]$Bytes
+	)
+
+	if ($null -eq $Budget) {
+		return
+	}
+
+	$Budget.Bytes += $Bytes
+	if ($Budget.MaxBytes -gt 0 -and $Budget.Bytes -ge $Budget.MaxBytes) {
+		Set-EprijavaBudgetExceeded -Budget $Budget -Reason "od $($Budget.MaxBytes) preuzetih bajtova"
+	}
+}
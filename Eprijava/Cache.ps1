@@ -0,0 +1,604 @@
+# Kes preuzetih HTML fajlova sa jedinstvenim rasporedom: <KES>/<PIB>/<GODINA>.html za izvjestaje, <KES>/<PIB>/<GODINA>-<VERZIJA>.html
+# za izmijenjene izvjestaje iste godine i <KES>/<PIB>/details.html za detalje
+# PDF verzije izvjestaja imaju isti naziv sa ekstenzijom .pdf (<KES>/<PIB>/<GODINA>.pdf, <KES>/<PIB>/<GODINA>-<VERZIJA>.pdf)
+# HTML fajlovi mogu biti komprimovani (gzip, <GODINA>.html.gz), a citaju se jednako kao nekomprimovani
+# Fajl manifest.json za svaki sacuvani fajl biljezi PIB, naziv, godinu, broj izvjestaja, URL, HTTP status, vrijeme preuzimanja i SHA-256 sadrzaja
+# Izvjestaj koji je ponovo preuzet pod istim brojem, a sa drugacijim sadrzajem (naknadna ispravka na portalu), se evidentira u Changes
+# Odgovori liste izvjestaja (JSON) se cuvaju kao <KES>/<PIB>/lists/<VRIJEME>.json, jedan fajl za svako preuzimanje liste
+# Brojaci posljednjeg prikupljanja (preuzeti izvjestaji, izvjestaji iz kesa) se cuvaju u <KES>/last-run.json
+
+# Starost kesa iz teksta u formatu <BROJ><JEDINICA>, npr. 30d, 12h, 90m ili 2w ("0" znaci bez ogranicenja)
+function ConvertFrom-EprijavaCacheAge {
+	param(
+		[Parameter(Mandatory)] [string]$Age
+	)
+
+	if ($Age.Trim() -eq "0") {
+		return [timespan]::Zero
+	}
+	if ($Age -notmatch '^\s*(?<value>\d+)\s*(?<unit>[smhdw])\s*$') {
+		throw "Neispravna starost kesa '$($Age)' (ocekivano npr. 30d, 12h, 90m ili 2w)"
+	}
+
+	$value = [int]$Matches.value
+	switch ($Matches.unit) {
+		"s" { [timespan]::FromSeconds($value) }
+		"m" { [timespan]::FromMinutes($value) }
+		"h" { [timespan]::FromHours($value) }
+		"d" { [timespan]::FromDays($value) }
+		"w" { [timespan]::FromDays($value * 7) }
+	}
+}
+
+# Otvaranje kesa (folder se kreira ukoliko ne postoji) i ucitavanje manifesta
+# Sacuvani fajl se koristi dok nije stariji od MaxAge (nula znaci bez ogranicenja), a uz Refresh se uvijek ponovo preuzima
+# Uz Compress se novi HTML fajlovi cuvaju komprimovani (gzip)
+function Open-EprijavaCache {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[timespan]$MaxAge = [timespan]::Zero,
+		[switch]$Refresh,
+		[switch]$Compress
+	)
+
+	New-Item -ItemType Directory -Force -Path $Path | Out-Null
+	$manifestPath = Join-Path $Path "manifest.json"
+
+	$entries = @{}
+	if (Test-Path -Path $manifestPath -PathType Leaf) {
+		$manifest = Get-Content -Path $manifestPath -Raw -Encoding UTF8 | ConvertFrom-Json
+		foreach ($entry in $manifest.entries.PSObject.Properties) {
+			# PowerShell 7 datume iz JSON-a ucitava kao DateTime, pa se vracaju u isti tekstualni oblik
+			$downloadedAt = $entry.Value.downloadedAt
+			if ($downloadedAt -is [datetime]) {
+				$downloadedAt = $downloadedAt.ToUniversalTime().ToString("o")
+			}
+			$entries[$entry.Name] = [PSCustomObject]@{
+				PSTypeName   = "Eprijava.CacheEntry"
+				Key          = $entry.Name
+				Pib          = $entry.Value.pib
+				Name         = $entry.Value.name
+				Year         = $entry.Value.year
+				Version      = $entry.Value.version
+				Number       = $entry.Value.number
+				Url          = $entry.Value.url
+				Status       = $entry.Value.status
+				DownloadedAt = $downloadedAt
+				Sha256       = $entry.Value.sha256
+			}
+		}
+	}
+
+	[PSCustomObject]@{
+		PSTypeName   = "Eprijava.Cache"
+		Path         = $Path
+		ManifestPath = $manifestPath
+		Entries      = $entries
+		MaxAge       = $MaxAge
+		Refresh      = [bool]$Refresh
+		Compress     = [bool]$Compress
+		Changes      = [System.Collections.Generic.List[object]]::new()
+	}
+}
+
+# Relativna putanja fajla u kesu: <PIB>/<GODINA>.html (<PIB>/<GODINA>-<VERZIJA>.html od druge verzije), odnosno <PIB>/details.html bez godine
+# Extension pdf daje putanju PDF verzije izvjestaja
+function Get-EprijavaCacheKey {
+	param(
+		[Parameter(Mandatory)] [string]$Pib,
+		[string]$Year = "",
+		[int]$Version = 1,
+		[ValidateSet("html", "pdf")] [string]$Extension = "html"
+	)
+
+	if (-not $Year) { "$($Pib)/details.$($Extension)" }
+	elseif ($Version -gt 1) { "$($Pib)/$($Year)-$($Version).$($Extension)" }
+	else { "$($Pib)/$($Year).$($Extension)" }
+}
+
+# Puna putanja fajla u kesu
+function Get-EprijavaCachePath {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] [string]$Pib,
+		[string]$Year = "",
+		[int]$Version = 1,
+		[ValidateSet("html", "pdf")] [string]$Extension = "html"
+	)
+
+	Join-Path $Cache.Path (Get-EprijavaCacheKey -Pib $Pib -Year $Year -Version $Version -Extension $Extension)
+}
+
+# Da li se sacuvani fajl prema pravilima kesa moze koristiti
+# Fajl se ne koristi ako ga nema u manifestu, ako je stariji od dozvoljenog ili ako se broj izvjestaja promijenio (izmijenjen izvjestaj)
+function Test-EprijavaCachedFile {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] [string]$Pib,
+		[string]$Year = "",
+		[int]$Version = 1,
+		[string]$Number = "",
+		[ValidateSet("html", "pdf")] [string]$Extension = "html"
+	)
+
+	if ($Cache.Refresh) {
+		return $false
+	}
+
+	$key = Get-EprijavaCacheKey -Pib $Pib -Year $Year -Version $Version -Extension $Extension
+	$entry = $Cache.Entries[$key]
+	if (-not $entry -or -not (Get-EprijavaCacheFile -Cache $Cache -Key $key)) {
+		return $false
+	}
+	if ($Number -and "$($entry.Number)" -ne $Number) {
+		return $false
+	}
+	if ($Cache.MaxAge -gt [timespan]::Zero) {
+		$downloadedAt = [datetime]::Parse($entry.DownloadedAt, [cultureinfo]::InvariantCulture, [System.Globalization.DateTimeStyles]::RoundtripKind)
+		if ((Get-Date).ToUniversalTime() - $downloadedAt.ToUniversalTime() -gt $Cache.MaxAge) {
+			return $false
+		}
+	}
+
+	$true
+}
+
+# Sadrzaj sacuvanog fajla ukoliko se prema pravilima kesa moze koristiti (Test-EprijavaCachedFile), inace $null
+function Get-EprijavaCachedContent {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] [string]$Pib,
+		[string]$Year = "",
+		[int]$Version = 1,
+		[string]$Number = ""
+	)
+
+	if (-not (Test-EprijavaCachedFile -Cache $Cache -Pib $Pib -Year $Year -Version $Version -Number $Number)) {
+		return $null
+	}
+
+	Read-EprijavaCacheFile -Path (Get-EprijavaCacheFile -Cache $Cache -Key (Get-EprijavaCacheKey -Pib $Pib -Year $Year -Version $Version))
+}
+
+# Putanja sacuvanog fajla za kljuc iz manifesta: nekomprimovani fajl ili <KLJUC>.gz, $null ukoliko fajla nema
+function Get-EprijavaCacheFile {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] [string]$Key
+	)
+
+	$path = Join-Path $Cache.Path $Key
+	foreach ($candidate in @($path, "$($path).gz")) {
+		if (Test-Path -Path $candidate -PathType Leaf) {
+			return $candidate
+		}
+	}
+
+	$null
+}
+
+# Sadrzaj fajla iz kesa (UTF-8), fajl sa ekstenzijom .gz se raspakuje
+function Read-EprijavaCacheFile {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if ($Path -notlike "*.gz") {
+		return [IO.File]::ReadAllText($Path)
+	}
+
+	[System.Text.Encoding]::UTF8.GetString((Read-EprijavaCacheBytes -Path $Path)).TrimStart([char]0xFEFF)
+}
+
+# Bajtovi fajla iz kesa, fajl sa ekstenzijom .gz se raspakuje
+function Read-EprijavaCacheBytes {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if ($Path -notlike "*.gz") {
+		return [IO.File]::ReadAllBytes($Path)
+	}
+
+	$file = [IO.File]::OpenRead($Path)
+	try {
+		$gzip = New-Object IO.Compression.GZipStream($file, [IO.Compression.CompressionMode]::Decompress)
+		$content = New-Object IO.MemoryStream
+		$gzip.CopyTo($content)
+		, $content.ToArray()
+	}
+	finally {
+		$file.Dispose()
+	}
+}
+
+# Upis bajtova u kes, komprimovano u <PUTANJA>.gz ili nekomprimovano; druga varijanta istog fajla se brise, vraca putanju upisanog fajla
+function Write-EprijavaCacheBytes {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [byte[]]$Bytes,
+		[switch]$Compress
+	)
+
+	$target = if ($Compress) { "$($Path).gz" } else { $Path }
+	$other = if ($Compress) { $Path } else { "$($Path).gz" }
+
+	if ($Compress) {
+		$file = [IO.File]::Create($target)
+		try {
+			$gzip = New-Object IO.Compression.GZipStream($file, [IO.Compression.CompressionLevel]::Optimal)
+			$gzip.Write($Bytes, 0, $Bytes.Length)
+			$gzip.Dispose()
+		}
+		finally {
+			$file.Dispose()
+		}
+	}
+	else {
+		[IO.File]::WriteAllBytes($target, $Bytes)
+	}
+	if (Test-Path -Path $other -PathType Leaf) {
+		Remove-Item -Path $other -Force
+	}
+
+	$target
+}
+
+# Upis manifesta
+function Save-EprijavaCacheManifest {
+	param(
+		[Parameter(Mandatory)] $Cache
+	)
+
+	$entries = [ordered]@{}
+	foreach ($key in ($Cache.Entries.Keys | Sort-Object)) {
+		$entry = $Cache.Entries[$key]
+		$entries[$key] = [ordered]@{
+			pib          = $entry.Pib
+			name         = $entry.Name
+			year         = $entry.Year
+			version      = $entry.Version
+			number       = $entry.Number
+			url          = $entry.Url
+			status       = $entry.Status
+			downloadedAt = $entry.DownloadedAt
+			sha256       = $entry.Sha256
+		}
+	}
+
+	ConvertTo-Json -InputObject @{ entries = $entries } -Depth 4 | Set-Content -Path $Cache.ManifestPath -Encoding UTF8
+}
+
+# Snimanje preuzetog sadrzaja u kes i evidentiranje u manifestu, vraca punu putanju fajla
+function Save-EprijavaCacheEntry {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] [string]$Pib,
+		[Parameter(Mandatory)] [string]$Name,
+		[string]$Year = "",
+		[int]$Version = 1,
+		[string]$Number = "",
+		[Parameter(Mandatory)] [string]$Url,
+		[int]$Status = 200,
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Content
+	)
+
+	$key = Get-EprijavaCacheKey -Pib $Pib -Year $Year -Version $Version
+	$path = Join-Path $Cache.Path $key
+	New-Item -ItemType Directory -Force -Path (Split-Path -Path $path -Parent) | Out-Null
+
+	# Manifest iz ranijih verzija nema SHA-256, pa se racuna iz fajla prije nego sto se prepise
+	$previous = $Cache.Entries[$key]
+	$existing = Get-EprijavaCacheFile -Cache $Cache -Key $key
+	if ($previous -and -not $previous.Sha256 -and $existing) {
+		$previous.Sha256 = Get-EprijavaFileSha256 -Path $existing
+	}
+	$bytes = [System.Text.UTF8Encoding]::new($false).GetBytes($Content + [Environment]::NewLine)
+	$path = Write-EprijavaCacheBytes -Path $path -Bytes $bytes -Compress:$Cache.Compress
+
+	Add-EprijavaCacheManifestEntry -Cache $Cache -Key $key -Pib $Pib -Name $Name -Year $Year -Version $Version -Number $Number -Url $Url -Status $Status
+
+	$path
+}
+
+# SHA-256 sadrzaja fajla (heksadecimalno, malim slovima), za komprimovani fajl se racuna iz raspakovanog sadrzaja
+function Get-EprijavaFileSha256 {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if ($Path -notlike "*.gz") {
+		return (Get-FileHash -Path $Path -Algorithm SHA256).Hash.ToLowerInvariant()
+	}
+
+	$sha256 = [System.Security.Cryptography.SHA256]::Create()
+	try {
+		([BitConverter]::ToString($sha256.ComputeHash((Read-EprijavaCacheBytes -Path $Path))) -replace '-', '').ToLowerInvariant()
+	}
+	finally {
+		$sha256.Dispose()
+	}
+}
+
+# Evidentiranje fajla sacuvanog u kesu u manifestu (npr. PDF izvjestaja koji je upisan direktno u fajl), uz SHA-256 sadrzaja
+# HTML izvjestaja sa istim brojem cija se SHA-256 razlikuje od ranije preuzetog se dodaje u Changes kesa (Eprijava.ContentChange),
+# a PDF se ne poredi jer se pri svakom generisanju razlikuje (npr. vrijeme kreiranja)
+function Add-EprijavaCacheManifestEntry {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] [string]$Key,
+		[Parameter(Mandatory)] [string]$Pib,
+		[Parameter(Mandatory)] [string]$Name,
+		[string]$Year = "",
+		[int]$Version = 1,
+		[string]$Number = "",
+		[Parameter(Mandatory)] [string]$Url,
+		[int]$Status = 200
+	)
+
+	$previous = $Cache.Entries[$Key]
+	$path = Get-EprijavaCacheFile -Cache $Cache -Key $Key
+	$sha256 = if ($path) { Get-EprijavaFileSha256 -Path $path } else { $null }
+
+	$Cache.Entries[$Key] = [PSCustomObject]@{
+		PSTypeName   = "Eprijava.CacheEntry"
+		Key          = $Key
+		Pib          = $Pib
+		Name         = $Name
+		Year         = $Year
+		Version      = $Version
+		Number       = $Number
+		Url          = $Url
+		Status       = $Status
+		DownloadedAt = (Get-Date).ToUniversalTime().ToString("o")
+		Sha256       = $sha256
+	}
+	Save-EprijavaCacheManifest -Cache $Cache
+
+	if ($Year -and $Key -like "*.html" -and $previous -and $previous.Sha256 -and $sha256 -and $previous.Sha256 -ne $sha256 -and "$($previous.Number)" -eq $Number) {
+		$Cache.Changes.Add([PSCustomObject]@{
+			PSTypeName           = "Eprijava.ContentChange"
+			Key                  = $Key
+			Pib                  = $Pib
+			Name                 = $Name
+			Year                 = $Year
+			Version              = $Version
+			Number               = $Number
+			Url                  = $Url
+			PreviousSha256       = $previous.Sha256
+			Sha256               = $sha256
+			PreviousDownloadedAt = $previous.DownloadedAt
+		})
+	}
+}
+
+# Snimanje svih stranica liste izvjestaja jednog preuzimanja u <KES>/<PIB>/lists/<VRIJEME>.json (vrijeme u UTC, npr. 20240131T081500123Z)
+# Odgovori se cuvaju u obliku u kojem ih portal vraca, kako bi se promjene liste (novi brojevi izvjestaja, izmijenjena polja) mogle analizirati bez portala
+# ETag i Last-Modified zaglavlja stranice (kada ih portal salje) se cuvaju za uslovne zahtjeve sljedeceg preuzimanja
+function Save-EprijavaStatementListSnapshot {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] [string]$Pib,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Pages
+	)
+
+	$fetchedAt = (Get-Date).ToUniversalTime()
+	$folder = Join-Path (Join-Path $Cache.Path $Pib) "lists"
+	New-Item -ItemType Directory -Force -Path $folder | Out-Null
+	$path = Join-Path $folder "$($fetchedAt.ToString("yyyyMMdd'T'HHmmssfff'Z'")).json"
+
+	$snapshot = [ordered]@{
+		pib       = $Pib
+		fetchedAt = $fetchedAt.ToString("o")
+		pages     = @($Pages | ForEach-Object {
+			$page = [ordered]@{ path = $_.Path; response = $_.Response }
+			if ($_.ETag) {
+				$page.etag = $_.ETag
+			}
+			if ($_.LastModified) {
+				$page.lastModified = $_.LastModified
+			}
+			$page
+		})
+	}
+	ConvertTo-Json -InputObject $snapshot -Depth 10 | Set-Content -Path $path -Encoding UTF8
+
+	$path
+}
+
+# Sacuvane liste izvjestaja kompanije, od najstarije do najnovije
+function Get-EprijavaCachedStatementLists {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] [string]$Pib
+	)
+
+	$folder = Join-Path (Join-Path $Cache.Path $Pib) "lists"
+	if (-not (Test-Path -Path $folder -PathType Container)) {
+		return
+	}
+
+	foreach ($file in (Get-ChildItem -Path $folder -File -Filter "*.json" | Sort-Object Name)) {
+		$snapshot = Get-Content -Path $file.FullName -Raw -Encoding UTF8 | ConvertFrom-Json
+		$fetchedAt = $snapshot.fetchedAt
+		if ($fetchedAt -is [datetime]) {
+			$fetchedAt = $fetchedAt.ToUniversalTime().ToString("o")
+		}
+
+		[PSCustomObject]@{
+			PSTypeName = "Eprijava.StatementListSnapshot"
+			Pib        = $Pib
+			FetchedAt  = $fetchedAt
+			Pages      = @($snapshot.pages)
+			Path       = $file.FullName
+		}
+	}
+}
+
+# Sacuvani izvjestaji (bez detalja pravnih lica), uz podatke iz manifesta
+# Fajlovi kojih nema u manifestu se takodje vracaju, sa PIB-om umjesto naziva kompanije; posljednja sacuvana verzija godine ima Latest
+function Get-EprijavaCachedStatements {
+	param(
+		[Parameter(Mandatory)] $Cache
+	)
+
+	foreach ($folder in (Get-ChildItem -Path $Cache.Path -Directory | Where-Object { $_.Name -match '^\d{8}$' } | Sort-Object Name)) {
+		$files = foreach ($file in (Get-ChildItem -Path $folder.FullName -File -Filter "*.html*")) {
+			if ($file.Name -match '^(?<year>\d{4})(-(?<version>\d+))?\.html(\.gz)?$') {
+				[PSCustomObject]@{
+					File    = $file
+					Year    = $Matches.year
+					Version = if ($Matches.version) { [int]$Matches.version } else { 1 }
+				}
+			}
+		}
+
+		foreach ($year in ($files | Group-Object Year | Sort-Object Name)) {
+			$versions = @($year.Group | Sort-Object Version)
+			foreach ($version in $versions) {
+				$entry = $Cache.Entries[(Get-EprijavaCacheKey -Pib $folder.Name -Year $version.Year -Version $version.Version)]
+
+				[PSCustomObject]@{
+					PSTypeName = "Eprijava.CachedStatement"
+					Pib        = $folder.Name
+					Name       = if ($entry -and $entry.Name) { $entry.Name } else { $folder.Name }
+					Year       = $version.Year
+					Version    = $version.Version
+					Latest     = $version -eq $versions[-1]
+					Number     = if ($entry) { $entry.Number } else { $null }
+					Url        = if ($entry) { $entry.Url } else { $null }
+					Path       = $version.File.FullName
+				}
+			}
+		}
+	}
+}
+
+# Komprimovanje svih nekomprimovanih HTML fajlova kesa (izvjestaji i detalji) u <FAJL>.html.gz, npr. za kes iz ranijih pokretanja
+# Original se brise tek kada raspakovani sadrzaj ima isti SHA-256, a manifest ostaje nepromijenjen (kljucevi su bez .gz)
+function Compress-EprijavaCache {
+	param(
+		[Parameter(Mandatory)] $Cache
+	)
+
+	$files = 0
+	$bytesBefore = [long]0
+	$bytesAfter = [long]0
+	foreach ($folder in (Get-ChildItem -Path $Cache.Path -Directory | Where-Object { $_.Name -match '^\d{8}$' } | Sort-Object Name)) {
+		foreach ($file in (Get-ChildItem -Path $folder.FullName -File -Filter "*.html" | Where-Object { $_.Extension -eq ".html" })) {
+			$partial = Write-EprijavaCacheBytes -Path "$($file.FullName).part" -Bytes ([IO.File]::ReadAllBytes($file.FullName)) -Compress
+			if ((Get-EprijavaFileSha256 -Path $partial) -ne (Get-EprijavaFileSha256 -Path $file.FullName)) {
+				Remove-Item -Path $partial -Force
+				throw "Komprimovani sadrzaj fajla $($file.FullName) se razlikuje od originala"
+			}
+
+			$target = "$($file.FullName).gz"
+			Move-Item -Path $partial -Destination $target -Force
+			$files++
+			$bytesBefore += $file.Length
+			$bytesAfter += (Get-Item -Path $target).Length
+			Remove-Item -Path $file.FullName -Force
+		}
+	}
+
+	[PSCustomObject]@{
+		PSTypeName  = "Eprijava.CacheCompaction"
+		Files       = $files
+		BytesBefore = $bytesBefore
+		BytesAfter  = $bytesAfter
+	}
+}
+
+# Upis brojaca zavrsenog prikupljanja u <KES>/last-run.json (za pregled kesa komandom cache stats)
+function Save-EprijavaCacheRunStats {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] $Stats
+	)
+
+	$lastRun = [ordered]@{
+		finishedAt     = (Get-Date).ToUniversalTime().ToString("o")
+		companies      = $Stats.Companies
+		processed      = $Stats.Processed
+		downloaded     = $Stats.Downloaded
+		cacheHits      = $Stats.CacheHits
+		contentChanged = $Stats.ContentChanged
+		requests       = $Stats.Requests
+	}
+	ConvertTo-Json -InputObject $lastRun | Set-Content -Path (Join-Path $Cache.Path "last-run.json") -Encoding UTF8
+}
+
+# Pregled kesa: broj kompanija i fajlova po vrsti, ukupna velicina, unosi manifesta bez fajla i brojaci posljednjeg prikupljanja
+# HitRate je udio izvjestaja ucitanih iz kesa u posljednjem prikupljanju ($null ukoliko prikupljanja nije bilo)
+function Get-EprijavaCacheStats {
+	param(
+		[Parameter(Mandatory)] $Cache
+	)
+
+	$folders = @(Get-ChildItem -Path $Cache.Path -Directory | Where-Object { $_.Name -match '^\d{8}$' })
+	$files = @($folders | ForEach-Object { Get-ChildItem -Path $_.FullName -File -Recurse })
+	$missing = @($Cache.Entries.Keys | Where-Object { -not (Get-EprijavaCacheFile -Cache $Cache -Key $_) })
+
+	$lastRun = $null
+	$lastRunPath = Join-Path $Cache.Path "last-run.json"
+	if (Test-Path -Path $lastRunPath -PathType Leaf) {
+		$lastRun = Get-Content -Path $lastRunPath -Raw -Encoding UTF8 | ConvertFrom-Json
+		if ($lastRun.finishedAt -is [datetime]) {
+			$lastRun.finishedAt = $lastRun.finishedAt.ToUniversalTime().ToString("o")
+		}
+	}
+	$reports = if ($lastRun) { [int]$lastRun.downloaded + [int]$lastRun.cacheHits } else { 0 }
+
+	[PSCustomObject]@{
+		PSTypeName      = "Eprijava.CacheStats"
+		Path            = $Cache.Path
+		Companies       = $folders.Count
+		Files           = $files.Count
+		Bytes           = [long]($files | Measure-Object -Property Length -Sum).Sum
+		Reports         = @($files | Where-Object { $_.Name -match '^\d{4}(-\d+)?\.html(\.gz)?$' }).Count
+		Compressed      = @($files | Where-Object { $_.Name -like "*.gz" }).Count
+		Pdfs            = @($files | Where-Object { $_.Extension -eq ".pdf" }).Count
+		Lists           = @($files | Where-Object { $_.Directory.Name -eq "lists" }).Count
+		ManifestEntries = $Cache.Entries.Count
+		MissingFiles    = $missing.Count
+		LastRun         = $lastRun
+		HitRate         = if ($reports -gt 0) { [math]::Round([int]$lastRun.cacheHits / $reports, 3) } else { $null }
+	}
+}
+
+# Brisanje foldera kompanija koje nisu u zadatoj listi PIB-ova (npr. uklonjene iz liste kompanija), zajedno sa unosima manifesta
+# Vraca obrisane kompanije sa brojem i velicinom fajlova; uz DryRun se kes ne mijenja
+function Remove-EprijavaCacheCompanies {
+	param(
+		[Parameter(Mandatory)] $Cache,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [string[]]$Keep,
+		[switch]$DryRun
+	)
+
+	$removed = @(foreach ($folder in (Get-ChildItem -Path $Cache.Path -Directory | Where-Object { $_.Name -match '^\d{8}$' -and $_.Name -notin $Keep } | Sort-Object Name)) {
+		$files = @(Get-ChildItem -Path $folder.FullName -File -Recurse)
+		$keys = @($Cache.Entries.Keys | Where-Object { $_ -like "$($folder.Name)/*" })
+		$entry = $keys | ForEach-Object { $Cache.Entries[$_] } | Where-Object { $_.Name } | Select-Object -First 1
+
+		if (-not $DryRun) {
+			Remove-Item -Path $folder.FullName -Recurse -Force
+			foreach ($key in $keys) {
+				$Cache.Entries.Remove($key)
+			}
+		}
+
+		[PSCustomObject]@{
+			PSTypeName = "Eprijava.PrunedCompany"
+			Pib        = $folder.Name
+			Name       = if ($entry) { $entry.Name } else { $null }
+			Files      = $files.Count
+			Bytes      = [long]($files | Measure-Object -Property Length -Sum).Sum
+		}
+	})
+
+	if (-not $DryRun -and $removed.Count -gt 0) {
+		Save-EprijavaCacheManifest -Cache $Cache
+	}
+
+	$removed
+}
@@ -0,0 +1,110 @@
+# Snimanje i reprodukovanje odgovora portala (kaseta)
+# U rezimu record se odgovor svakog uspjesnog zahtjeva upisuje u folder kasete, a u rezimu replay se odgovori citaju iz kasete
+# bez pristupa portalu, sto omogucava rad bez mreze i ponavljanje greske u parsiranju na istim podacima
+
+# Otvaranje kasete: <FOLDER>/cassette.json sa listom snimljenih zahtjeva i po jedan fajl za svaki odgovor
+# Zahtjevi se prepoznaju po metodu i putanji relativnoj u odnosu na adresu portala, pa se kaseta moze reprodukovati i uz drugu adresu
+function Open-EprijavaCassette {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [ValidateSet("record", "replay")] [string]$Mode
+	)
+
+	$indexPath = Join-Path $Path "cassette.json"
+	if ($Mode -eq "replay" -and -not (Test-Path -Path $indexPath -PathType Leaf)) {
+		throw "Kaseta nije pronadjena: $($indexPath)"
+	}
+	New-Item -ItemType Directory -Force -Path $Path | Out-Null
+
+	$interactions = @{}
+	if (Test-Path -Path $indexPath -PathType Leaf) {
+		foreach ($entry in (Get-Content -Path $indexPath -Raw -Encoding UTF8 | ConvertFrom-Json).interactions) {
+			$interactions["$($entry.method) $($entry.path)"] = [PSCustomObject]@{
+				PSTypeName = "Eprijava.CassetteEntry"
+				Method     = $entry.method
+				Path       = $entry.path
+				Format     = $entry.format
+				File       = $entry.file
+				RecordedAt = $entry.recordedAt
+			}
+		}
+	}
+
+	[PSCustomObject]@{
+		PSTypeName   = "Eprijava.Cassette"
+		Path         = $Path
+		IndexPath    = $indexPath
+		Mode         = $Mode
+		Interactions = $interactions
+	}
+}
+
+# Upis liste snimljenih zahtjeva, sortirane po putanji kako bi izmjene kasete bile pregledne
+function Save-EprijavaCassetteIndex {
+	param(
+		[Parameter(Mandatory)] $Cassette
+	)
+
+	$interactions = @(foreach ($key in ($Cassette.Interactions.Keys | Sort-Object)) {
+		$entry = $Cassette.Interactions[$key]
+		[ordered]@{
+			method     = $entry.Method
+			path       = $entry.Path
+			format     = $entry.Format
+			file       = $entry.File
+			recordedAt = $entry.RecordedAt
+		}
+	})
+
+	ConvertTo-Json -InputObject @{ interactions = $interactions } -Depth 4 | Set-Content -Path $Cassette.IndexPath -Encoding UTF8
+}
+
+# Snimanje odgovora: tekst (HTML) se upisuje neizmijenjen, a JSON objekat kao JSON
+# Ponovno snimanje istog zahtjeva zamjenjuje prethodni odgovor
+function Save-EprijavaCassetteResponse {
+	param(
+		[Parameter(Mandatory)] $Cassette,
+		[Parameter(Mandatory)] [string]$Method,
+		[Parameter(Mandatory)] [string]$Path,
+		$Response
+	)
+
+	$key = "$($Method) $($Path)"
+	$format = if ($Response -is [string]) { "text" } else { "json" }
+	$entry = $Cassette.Interactions[$key]
+	$file = if ($entry -and $entry.Format -eq $format) { $entry.File } else { "{0:d4}.{1}" -f ($Cassette.Interactions.Count + 1), $(if ($format -eq "text") { "html" } else { "json" }) }
+
+	$content = if ($format -eq "text") { $Response } else { ConvertTo-Json -InputObject $Response -Depth 10 }
+	Set-Content -Path (Join-Path $Cassette.Path $file) -Value $content -Encoding UTF8 -NoNewline
+
+	$Cassette.Interactions[$key] = [PSCustomObject]@{
+		PSTypeName = "Eprijava.CassetteEntry"
+		Method     = $Method
+		Path       = $Path
+		Format     = $format
+		File       = $file
+		RecordedAt = (Get-Date).ToUniversalTime().ToString("o")
+	}
+	Save-EprijavaCassetteIndex -Cassette $Cassette
+}
+
+# Snimljeni odgovor u istom obliku koji vraca Invoke-RestMethod (tekst ili JSON objekat)
+# Zahtjev koji nije snimljen prekida reprodukovanje, jer bi se inace tiho preskocili podaci
+function Get-EprijavaCassetteResponse {
+	param(
+		[Parameter(Mandatory)] $Cassette,
+		[Parameter(Mandatory)] [string]$Method,
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$entry = $Cassette.Interactions["$($Method) $($Path)"]
+	if (-not $entry) {
+		throw "Zahtjev $($Method) $($Path) nije snimljen u kaseti $($Cassette.Path). Snimite ga ponovo sa -Record."
+	}
+
+	$content = [IO.File]::ReadAllText((Join-Path $Cassette.Path $entry.File))
+	if ($entry.Format -eq "json") {
+		return $content | ConvertFrom-Json
+	}
+	$content
+}
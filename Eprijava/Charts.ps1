@@ -0,0 +1,97 @@
+# SVG grafikoni kretanja po godinama (prihodi, broj zaposlenih, prosjecna zarada) za svaku kompaniju i za cijeli sektor
+# Grafikoni se formiraju kao tekst, bez dodatnih modula
+
+# Grafikoni koji se formiraju: naziv u imenu fajla, naslov i svojstvo rezultata (za sektor svojstvo zbirnog pregleda)
+$script:ChartSeries = @(
+	@{ Name = "revenue"; Title = "Ukupni prihodi"; Property = "TotalIncome"; SectorProperty = "TotalIncome" }
+	@{ Name = "employees"; Title = "Broj zaposlenih"; Property = "EmployeeCount"; SectorProperty = "EmployeeCount" }
+	@{ Name = "average-pay"; Title = "Prosjecna zarada"; Property = "AveragePay"; SectorProperty = "MedianAveragePay" }
+)
+
+# Linijski grafikon vrijednosti po godinama kao SVG dokument, tacke bez vrijednosti se preskacu
+# Vertikalna osa pocinje od nule (ili od najmanje vrijednosti ukoliko je negativna)
+function ConvertTo-EprijavaChartSvg {
+	param(
+		[Parameter(Mandatory)] [string]$Title,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Points,
+		[int]$Width = 640,
+		[int]$Height = 320
+	)
+
+	$invariant = [cultureinfo]::InvariantCulture
+	$left = 90; $right = 20; $top = 40; $bottom = 40
+	$plotWidth = $Width - $left - $right
+	$plotHeight = $Height - $top - $bottom
+
+	$known = @($Points | Where-Object { $null -ne $_.Value } | Sort-Object { [int]$_.Year })
+	$lines = [System.Collections.Generic.List[string]]::new()
+	$lines.Add("<svg xmlns=""http://www.w3.org/2000/svg"" width=""$($Width)"" height=""$($Height)"" viewBox=""0 0 $($Width) $($Height)"" font-family=""sans-serif"" font-size=""12"">")
+	$lines.Add("<rect width=""100%"" height=""100%"" fill=""white""/>")
+	$lines.Add("<text x=""$($Width / 2)"" y=""24"" text-anchor=""middle"" font-size=""16"">$([System.Net.WebUtility]::HtmlEncode($Title))</text>")
+	$lines.Add("<line x1=""$($left)"" y1=""$($top + $plotHeight)"" x2=""$($left + $plotWidth)"" y2=""$($top + $plotHeight)"" stroke=""#333""/>")
+	$lines.Add("<line x1=""$($left)"" y1=""$($top)"" x2=""$($left)"" y2=""$($top + $plotHeight)"" stroke=""#333""/>")
+
+	if ($known.Count -gt 0) {
+		$values = @($known | ForEach-Object { [double]$_.Value })
+		$min = [math]::Min(0, ($values | Measure-Object -Minimum).Minimum)
+		$max = ($values | Measure-Object -Maximum).Maximum
+		if ($max -le $min) {
+			$max = $min + 1
+		}
+		$firstYear = [int]$known[0].Year
+		$yearSpan = [math]::Max(1, [int]$known[-1].Year - $firstYear)
+
+		# Oznake vertikalne ose (najmanja, srednja i najveca vrijednost)
+		foreach ($value in @($min, (($min + $max) / 2), $max)) {
+			$y = $top + $plotHeight - ($value - $min) / ($max - $min) * $plotHeight
+			$lines.Add("<line x1=""$($left)"" y1=""$($y.ToString('0.#', $invariant))"" x2=""$($left + $plotWidth)"" y2=""$($y.ToString('0.#', $invariant))"" stroke=""#eee""/>")
+			$lines.Add("<text x=""$($left - 6)"" y=""$(($y + 4).ToString('0.#', $invariant))"" text-anchor=""end"">$($value.ToString('N0', $invariant))</text>")
+		}
+
+		$coordinates = foreach ($point in $known) {
+			$x = $left + ([int]$point.Year - $firstYear) / $yearSpan * $plotWidth
+			$y = $top + $plotHeight - ([double]$point.Value - $min) / ($max - $min) * $plotHeight
+			$lines.Add("<text x=""$($x.ToString('0.#', $invariant))"" y=""$($top + $plotHeight + 18)"" text-anchor=""middle"">$($point.Year)</text>")
+			$lines.Add("<circle cx=""$($x.ToString('0.#', $invariant))"" cy=""$($y.ToString('0.#', $invariant))"" r=""3"" fill=""#1f77b4""/>")
+			"$($x.ToString('0.#', $invariant)),$($y.ToString('0.#', $invariant))"
+		}
+		$lines.Add("<polyline points=""$($coordinates -join ' ')"" fill=""none"" stroke=""#1f77b4"" stroke-width=""2""/>")
+	}
+
+	$lines.Add("</svg>")
+	$lines -join "`n"
+}
+
+# Upis grafikona u folder: <PIB>-<GRAFIKON>.svg za svaku kompaniju (naziv umjesto PIB-a za redove bez PIB-a) i sector-<GRAFIKON>.svg
+# Koriste se samo posljednje verzije izvjestaja, vraca broj upisanih fajlova
+function Export-EprijavaCharts {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	New-Item -ItemType Directory -Force -Path $Path | Out-Null
+	$count = 0
+
+	$summary = @(Get-EprijavaSectorSummary -Results $Results)
+	foreach ($series in $script:ChartSeries) {
+		$points = @($summary | ForEach-Object { [PSCustomObject]@{ Year = $_.Year; Value = $_.($series.SectorProperty) } })
+		$svg = ConvertTo-EprijavaChartSvg -Title "$(ConvertTo-EprijavaLocalLabel -Text "Sektor"): $(ConvertTo-EprijavaLocalLabel -Text $series.Title)" -Points $points
+		Set-Content -Path (Join-Path $Path "sector-$($series.Name).svg") -Value $svg -Encoding UTF8
+		$count++
+	}
+
+	$latest = @($Results | Where-Object { $_.Latest -ne 0 })
+	foreach ($company in ($latest | Group-Object { if ($_.Pib) { $_.Pib } else { $_.Name } })) {
+		$name = $company.Group[0].Name
+		$fileName = ConvertTo-EprijavaSafeFileName -Name $company.Name
+		foreach ($series in $script:ChartSeries) {
+			$points = @($company.Group | ForEach-Object { [PSCustomObject]@{ Year = $_.Year; Value = $_.($series.Property) } })
+			$svg = ConvertTo-EprijavaChartSvg -Title "$($name): $(ConvertTo-EprijavaLocalLabel -Text $series.Title)" -Points $points
+			Set-Content -Path (Join-Path $Path "$($fileName)-$($series.Name).svg") -Value $svg -Encoding UTF8
+			$count++
+		}
+	}
+
+	$count
+}
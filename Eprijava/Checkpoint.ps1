@@ -0,0 +1,83 @@
+# Stanje prikupljanja (.scrape-state.json) za nastavak prekinutog pokretanja
+
+# Ucitavanje stanja iz fajla, prazno stanje ukoliko fajl ne postoji
+# Za svaki PIB se pamti da li je kompanija zavrsena i brojevi vec obradjenih izvjestaja
+function Import-EprijavaCheckpoint {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$companies = @{}
+	if (Test-Path -Path $Path -PathType Leaf) {
+		$state = Get-Content -Path $Path -Raw -Encoding UTF8 | ConvertFrom-Json
+		foreach ($company in $state.companies.PSObject.Properties) {
+			$companies[$company.Name] = @{
+				Completed = [bool]$company.Value.completed
+				Reports   = [System.Collections.Generic.List[string]]@($company.Value.reports)
+			}
+		}
+	}
+
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.Checkpoint"
+		Path       = $Path
+		Companies  = $companies
+	}
+}
+
+# Upis stanja u fajl
+function Save-EprijavaCheckpoint {
+	param(
+		[Parameter(Mandatory)] $Checkpoint
+	)
+
+	$companies = [ordered]@{}
+	foreach ($pib in ($Checkpoint.Companies.Keys | Sort-Object)) {
+		$companies[$pib] = [ordered]@{
+			completed = $Checkpoint.Companies[$pib].Completed
+			reports   = @($Checkpoint.Companies[$pib].Reports)
+		}
+	}
+
+	ConvertTo-Json -InputObject @{ companies = $companies } -Depth 4 | Set-Content -Path $Checkpoint.Path -Encoding UTF8
+}
+
+# Stanje jedne kompanije (kreira se ukoliko ne postoji)
+function Get-EprijavaCheckpointCompany {
+	param(
+		[Parameter(Mandatory)] $Checkpoint,
+		[Parameter(Mandatory)] [string]$Pib
+	)
+
+	if (-not $Checkpoint.Companies.ContainsKey($Pib)) {
+		$Checkpoint.Companies[$Pib] = @{
+			Completed = $false
+			Reports   = [System.Collections.Generic.List[string]]::new()
+		}
+	}
+
+	$Checkpoint.Companies[$Pib]
+}
+
+# Evidentiranje obradjenog izvjestaja i snimanje stanja
+function Add-EprijavaCheckpointReport {
+	param(
+		[Parameter(Mandatory)] $Checkpoint,
+		[Parameter(Mandatory)] [string]$Pib,
+		[Parameter(Mandatory)] [string]$Number
+	)
+
+	(Get-EprijavaCheckpointCompany -Checkpoint $Checkpoint -Pib $Pib).Reports.Add($Number)
+	Save-EprijavaCheckpoint -Checkpoint $Checkpoint
+}
+
+# Evidentiranje zavrsene kompanije i snimanje stanja
+function Complete-EprijavaCheckpointCompany {
+	param(
+		[Parameter(Mandatory)] $Checkpoint,
+		[Parameter(Mandatory)] [string]$Pib
+	)
+
+	(Get-EprijavaCheckpointCompany -Checkpoint $Checkpoint -Pib $Pib).Completed = $true
+	Save-EprijavaCheckpoint -Checkpoint $Checkpoint
+}
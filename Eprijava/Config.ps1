@@ -0,0 +1,64 @@
+# Konfiguracioni fajl (PowerShell data fajl, npr. eprijava.psd1) i promjenljive okruzenja EPRIJAVA_<KLJUC>
+# Redosljed vaznosti: parametri skripte, promjenljive okruzenja, konfiguracioni fajl, podrazumijevane vrijednosti
+
+# Ucitavanje konfiguracije, prazna konfiguracija ukoliko fajl nije zadat ili ne postoji
+function Import-EprijavaConfig {
+	param(
+		[string]$Path
+	)
+
+	if ($Path -and (Test-Path -Path $Path -PathType Leaf)) {
+		return Import-PowerShellDataFile -Path $Path
+	}
+
+	@{}
+}
+
+# Naziv promjenljive okruzenja za kljuc konfiguracije, npr. RequestsPerSecond -> EPRIJAVA_REQUESTS_PER_SECOND
+function Get-EprijavaEnvironmentName {
+	param(
+		[Parameter(Mandatory)] [string]$Key
+	)
+
+	"EPRIJAVA_$(($Key -creplace '(?<=[a-z0-9])([A-Z])', '_$1').ToUpperInvariant())"
+}
+
+# Vrijednosti zadatih kljuceva iz promjenljivih okruzenja (EPRIJAVA_<KLJUC>), kao tekst; prazne promjenljive se preskacu
+function Get-EprijavaEnvironmentConfig {
+	param(
+		[Parameter(Mandatory)] [string[]]$Keys
+	)
+
+	$values = @{}
+	foreach ($key in $Keys) {
+		$value = [Environment]::GetEnvironmentVariable((Get-EprijavaEnvironmentName -Key $key))
+		if ($value) {
+			$values[$key] = $value
+		}
+	}
+
+	$values
+}
+
+# Tekst promjenljive okruzenja u tipu trenutne vrijednosti parametra: niz (vrijednosti odvojene zarezom),
+# prekidac (1, true, yes, da ili 0, false, no, ne) ili tekst, koji PowerShell pretvara u tip parametra
+function ConvertFrom-EprijavaEnvironmentValue {
+	param(
+		[Parameter(Mandatory)] [string]$Name,
+		[Parameter(Mandatory)] [string]$Value,
+		$Current
+	)
+
+	if ($Current -is [array]) {
+		return , @($Value -split ',' | ForEach-Object { $_.Trim() } | Where-Object { $_ })
+	}
+	if ($Current -is [switch] -or $Current -is [bool]) {
+		switch ($Value.Trim().ToLowerInvariant()) {
+			{ $_ -in @("1", "true", "yes", "da") } { return $true }
+			{ $_ -in @("0", "false", "no", "ne") } { return $false }
+		}
+		throw "Neispravna vrijednost promjenljive $($Name): '$($Value)' (ocekuje se true ili false)"
+	}
+
+	$Value
+}
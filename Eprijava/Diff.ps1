@@ -0,0 +1,114 @@
+# Poredjenje dva skupa rezultata (npr. prethodnog i novog pokretanja): nove godine, izmijenjene vrijednosti i nestale kompanije
+
+# Vrste razlika
+$script:DiffKinds = @("NewYear", "Changed", "RemovedYear", "RemovedCompany")
+
+# Kljuc kompanije: PIB, odnosno naziv za redove bez PIB-a
+function Get-EprijavaCompanyKey {
+	param(
+		[Parameter(Mandatory)] $Result
+	)
+
+	if ($Result.Pib) { "$($Result.Pib)" } else { "$($Result.Name)" }
+}
+
+# Posljednja verzija izvjestaja za svaku kompaniju i godinu, kljuc je "<KOMPANIJA>|<GODINA>"
+function Get-EprijavaLatestByYear {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	$latest = [ordered]@{}
+	foreach ($result in ($Results | Sort-Object { [int]$_.Year }, { [int]$_.Version })) {
+		$latest["$(Get-EprijavaCompanyKey -Result $result)|$([int]$result.Year)"] = $result
+	}
+	$latest
+}
+
+# Razlike izmedju prethodnog i novog skupa rezultata, porede se posljednje verzije izvjestaja za svaku godinu
+# Za izmijenjeni izvjestaj se vraca po jedna razlika za svaku promijenjenu kolonu (ukljucujuci verziju izvjestaja)
+function Compare-EprijavaDataset {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Previous,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Current
+	)
+
+	$before = Get-EprijavaLatestByYear -Results $Previous
+	$after = Get-EprijavaLatestByYear -Results $Current
+	$columns = @(Get-EprijavaResultColumns -StatAnnex | Where-Object { $_.Property -notin @("Name", "Year", "Latest") })
+	$currentCompanies = @{}
+	foreach ($result in $Current) {
+		$currentCompanies[(Get-EprijavaCompanyKey -Result $result)] = $true
+	}
+
+	foreach ($key in $after.Keys) {
+		$result = $after[$key]
+		if (-not $before.Contains($key)) {
+			New-EprijavaDiffEntry -Kind NewYear -Result $result
+			continue
+		}
+
+		$old = $before[$key]
+		foreach ($column in $columns) {
+			$oldValue = $old.($column.Property)
+			$newValue = $result.($column.Property)
+			# Kolone kojih nema u jednom od skupova (npr. statisticki aneks) se ne porede
+			if ($null -eq $oldValue -and $old.PSObject.Properties.Name -notcontains $column.Property) { continue }
+			if ($null -eq $newValue -and $result.PSObject.Properties.Name -notcontains $column.Property) { continue }
+			if ("$oldValue" -ne "$newValue") {
+				New-EprijavaDiffEntry -Kind Changed -Result $result -Field $column.Header -OldValue $oldValue -NewValue $newValue
+			}
+		}
+	}
+
+	$removedCompanies = @{}
+	foreach ($key in $before.Keys) {
+		if ($after.Contains($key)) {
+			continue
+		}
+		$result = $before[$key]
+		$company = Get-EprijavaCompanyKey -Result $result
+		if ($currentCompanies.ContainsKey($company)) {
+			New-EprijavaDiffEntry -Kind RemovedYear -Result $result
+		}
+		elseif (-not $removedCompanies.ContainsKey($company)) {
+			$removedCompanies[$company] = $true
+			New-EprijavaDiffEntry -Kind RemovedCompany -Result $result
+		}
+	}
+}
+
+# Jedna razlika; za nestalu kompaniju godina je posljednja godina iz prethodnog skupa
+function New-EprijavaDiffEntry {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("NewYear", "Changed", "RemovedYear", "RemovedCompany")] [string]$Kind,
+		[Parameter(Mandatory)] $Result,
+		[string]$Field,
+		$OldValue,
+		$NewValue
+	)
+
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.DiffEntry"
+		Kind       = $Kind
+		Name       = $Result.Name
+		Pib        = $Result.Pib
+		Year       = [int]$Result.Year
+		Field      = $Field
+		OldValue   = $OldValue
+		NewValue   = $NewValue
+	}
+}
+
+# Upis razlika u CSV fajl
+function Export-EprijavaDiff {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Diff
+	)
+
+	Set-EprijavaResultFile -Path $Path -Columns $script:DiffColumns
+	if ($Diff.Count -gt 0) {
+		Add-Content -Path $Path -Value @($Diff | ForEach-Object { ConvertTo-EprijavaResultLine -Result $_ -Columns $script:DiffColumns })
+	}
+}
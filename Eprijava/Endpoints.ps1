@@ -0,0 +1,71 @@
+# Adrese endpoint-a portala ePrijava
+# Sve adrese se formiraju ovdje, relativno u odnosu na adresu portala koja se moze izmijeniti (npr. za testni server)
+
+$script:PortalUrl = "https://eprijava.tax.gov.me/TaxisPortal"
+
+# Putanja PDF verzije finansijskog izvjestaja, {number} se zamjenjuje rednim brojem izvjestaja
+$script:StatementPdfPath = "FinancialStatement/DetailsPdf?rbr={number}"
+
+# Adresa portala na koju se salju svi zahtjevi (npr. lokalni testni server umjesto eprijava.tax.gov.me)
+# Prihvata se apsolutna http(s) adresa, a zavrsna kosa crta se uklanja
+function Set-EprijavaPortalUrl {
+	param(
+		[Parameter(Mandatory)] [string]$Url
+	)
+
+	$uri = $null
+	if (-not [uri]::TryCreate($Url, [UriKind]::Absolute, [ref]$uri) -or $uri.Scheme -notin @("http", "https")) {
+		throw "Neispravna adresa portala '$($Url)' (ocekivano npr. https://eprijava.tax.gov.me/TaxisPortal)"
+	}
+
+	$script:PortalUrl = $Url.TrimEnd("/")
+}
+
+# Trenutna adresa portala
+function Get-EprijavaPortalUrl {
+	$script:PortalUrl
+}
+
+# Izmijenjena putanja PDF verzije izvjestaja (relativno u odnosu na adresu portala, sa {number})
+function Set-EprijavaStatementPdfPath {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if ($Path -notmatch '\{number\}') {
+		throw "Putanja PDF izvjestaja '$($Path)' ne sadrzi {number} (ocekivano npr. FinancialStatement/DetailsPdf?rbr={number})"
+	}
+	$script:StatementPdfPath = $Path.TrimStart("/")
+}
+
+# Putanja endpoint-a relativna u odnosu na adresu portala:
+# Home (pocetna stranica, dodjeljuje sesiju), TaxPayerSearch (pretraga po PIB-u ili dijelu naziva), CompanyDetails (detalji pravnog lica),
+# StatementList (lista finansijskih izvjestaja), StatementDetails (finansijski izvjestaj po rednom broju) i StatementPdf (PDF verzija izvjestaja)
+function Get-EprijavaEndpoint {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("Home", "TaxPayerSearch", "CompanyDetails", "StatementList", "StatementDetails", "StatementPdf")] [string]$Name,
+		[string]$Pib = "",
+		[string]$TaxPayerName = "",
+		[string]$Number = "",
+		[int]$Skip = 0,
+		[int]$Take = 20
+	)
+
+	switch ($Name) {
+		"Home" { "FinancialStatement" }
+		"TaxPayerSearch" { "FinancialStatement/Grid?pib=$($Pib)&naziv=$([uri]::EscapeDataString($TaxPayerName))&orderBy=naziv&skip=$($Skip)&take=$($Take)" }
+		"CompanyDetails" { "TaxPayerCompanies/Details?PIB=$($Pib)" }
+		"StatementList" { "FinancialStatement/TaxPayerStatementsList?PIB=$($Pib)&take=$($Take)&skip=$($Skip)&page=$([math]::Floor($Skip / [math]::Max($Take, 1)) + 1)&pageSize=$($Take)" }
+		"StatementDetails" { "FinancialStatement/Details?rbr=$($Number)" }
+		"StatementPdf" { $script:StatementPdfPath.Replace("{number}", [uri]::EscapeDataString($Number)) }
+	}
+}
+
+# Puna adresa za putanju relativnu u odnosu na adresu portala
+function Get-EprijavaEndpointUrl {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Path
+	)
+
+	"$($script:PortalUrl)/$($Path.TrimStart("/"))"
+}
@@ -0,0 +1,159 @@
+# Izvjestaj o greskama pokretanja (errors.json): preskocene kompanije, neuspjela preuzimanja, podaci koji nisu pronadjeni u izvjestajima
+# i zbirne pozicije koje nisu jednake zbiru svojih dijelova (najcesce znak da parsiranje ne odgovara izgledu izvjestaja)
+
+# Vrste gresaka u izvjestaju
+$script:ErrorKinds = @("SkippedCompany", "DownloadFailed", "ExtractionFailed", "InconsistentTotals")
+
+# Prazan izvjestaj o greskama
+function New-EprijavaErrorReport {
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.ErrorReport"
+		StartedAt  = (Get-Date).ToUniversalTime().ToString("o")
+		Entries    = [System.Collections.Generic.List[object]]::new()
+	}
+}
+
+# Evidentiranje jedne greske sa razlogom i URL-om (ukoliko je poznat)
+function Add-EprijavaErrorEntry {
+	param(
+		[Parameter(Mandatory)] $Report,
+		[Parameter(Mandatory)] [ValidateSet("SkippedCompany", "DownloadFailed", "ExtractionFailed", "InconsistentTotals")] [string]$Kind,
+		[string]$Pib,
+		[string]$Name,
+		[string]$Year,
+		[string]$Number,
+		[string]$Url,
+		[Parameter(Mandatory)] [string]$Reason
+	)
+
+	$Report.Entries.Add([PSCustomObject]@{
+		PSTypeName = "Eprijava.ErrorEntry"
+		Kind       = $Kind
+		Pib        = $Pib
+		Name       = $Name
+		Year       = $Year
+		Number     = $Number
+		Url        = $Url
+		Reason     = $Reason
+	})
+}
+
+# Provjera da li je greska istek sesije, koja prekida cijelo pokretanje umjesto da se evidentira
+function Test-EprijavaSessionError {
+	param(
+		[Parameter(Mandatory)] [System.Management.Automation.ErrorRecord]$ErrorRecord
+	)
+
+	$ErrorRecord.FullyQualifiedErrorId -like "Eprijava.SessionExpired*"
+}
+
+# Greske zbog kojih kompanija ili izvjestaj nisu obradjeni (preskocene kompanije i neuspjela preuzimanja), za razliku od
+# izvjestaja sa nepronadjenim podacima ili neusaglasenim zbirnim pozicijama, koji su obradjeni i ne obradjuju se ponovo
+function Get-EprijavaFailures {
+	param(
+		[Parameter(Mandatory)] $Report
+	)
+
+	$Report.Entries | Where-Object { $_.Kind -in @("SkippedCompany", "DownloadFailed") }
+}
+
+# Upis neobradjenih kompanija i izvjestaja u CSV fajl (kind, pib, name, year, number, url, reason)
+function Export-EprijavaFailures {
+	param(
+		[Parameter(Mandatory)] $Report,
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$rows = foreach ($entry in (Get-EprijavaFailures -Report $Report)) {
+		[PSCustomObject][ordered]@{
+			kind   = $entry.Kind
+			pib    = $entry.Pib
+			name   = $entry.Name
+			year   = $entry.Year
+			number = $entry.Number
+			url    = $entry.Url
+			reason = $entry.Reason
+		}
+	}
+	if ($rows) {
+		$rows | Export-Csv -Path $Path -NoTypeInformation -Encoding UTF8
+	}
+	else {
+		Set-Content -Path $Path -Value '"kind","pib","name","year","number","url","reason"' -Encoding UTF8
+	}
+}
+
+# Kompanije i godine za ponovno prikupljanje iz CSV fajla neobradjenih stavki (Export-EprijavaFailures)
+# Preskocena kompanija se prikuplja u cjelini (Years je prazan), a za neuspjela preuzimanja samo godine tih izvjestaja
+function Import-EprijavaRetryPlan {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if (-not (Test-Path -Path $Path -PathType Leaf)) {
+		throw "Fajl sa neobradjenim kompanijama nije pronadjen: $($Path)"
+	}
+
+	$companies = [ordered]@{}
+	foreach ($row in (Import-Csv -Path $Path -Encoding UTF8)) {
+		$pib = "$($row.pib)".Trim()
+		if ($pib -eq "" -or $row.kind -notin @("SkippedCompany", "DownloadFailed")) {
+			continue
+		}
+		if (-not $companies.Contains($pib)) {
+			$companies[$pib] = [PSCustomObject]@{
+				PSTypeName = "Eprijava.RetryCompany"
+				Pib        = $pib
+				Name       = if ("$($row.name)".Trim()) { "$($row.name)".Trim() } else { $pib }
+				AllYears   = $false
+				Years      = [System.Collections.Generic.List[int]]::new()
+			}
+		}
+		$company = $companies[$pib]
+		if ($row.kind -eq "SkippedCompany" -or "$($row.year)".Trim() -eq "") {
+			$company.AllYears = $true
+		}
+		elseif (-not $company.Years.Contains([int]$row.year)) {
+			$company.Years.Add([int]$row.year)
+		}
+	}
+
+	foreach ($company in $companies.Values) {
+		if ($company.AllYears) {
+			$company.Years.Clear()
+		}
+		$company
+	}
+}
+
+# Upis izvjestaja u JSON fajl: vrijeme pokretanja, broj gresaka po vrsti i lista gresaka
+function Save-EprijavaErrorReport {
+	param(
+		[Parameter(Mandatory)] $Report,
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$counts = [ordered]@{}
+	foreach ($kind in $script:ErrorKinds) {
+		$counts[$kind] = @($Report.Entries | Where-Object { $_.Kind -eq $kind }).Count
+	}
+
+	$errors = @(foreach ($entry in $Report.Entries) {
+		[ordered]@{
+			kind   = $entry.Kind
+			pib    = $entry.Pib
+			name   = $entry.Name
+			year   = $entry.Year
+			number = $entry.Number
+			url    = $entry.Url
+			reason = $entry.Reason
+		}
+	})
+
+	ConvertTo-Json -InputObject ([ordered]@{
+		startedAt  = $Report.StartedAt
+		finishedAt = (Get-Date).ToUniversalTime().ToString("o")
+		counts     = $counts
+		errors     = $errors
+	}) -Depth 4 | Set-Content -Path $Path -Encoding UTF8
+}
@@ -0,0 +1,430 @@
+# Izvoz rezultata u JSON, JSON Lines, Apache Parquet i Excel formatu
+# U JSON formatima rezultati su grupisani po kompaniji: { name, pib, statements: [ { Year, totalIncome, ... } ] }
+
+# Objekat jedne kompanije sa nizom godisnjih izvjestaja (nazivi polja su isti kao kolone CSV fajla) i verzijom sheme izlaza
+function ConvertTo-EprijavaCompanyRecord {
+	param(
+		[Parameter(Mandatory)] [object[]]$Results,
+		[switch]$StatAnnex
+	)
+
+	$columns = @(Get-EprijavaResultColumns -StatAnnex:$StatAnnex | Where-Object { $_.Property -ne "Name" })
+	$statements = foreach ($result in ($Results | Sort-Object Year -Descending)) {
+		$statement = [ordered]@{}
+		foreach ($column in $columns) {
+			$statement[$column.Header] = $result.($column.Property)
+		}
+		$statement["number"] = $result.Number
+		$statement
+	}
+
+	[ordered]@{
+		schemaVersion = Get-EprijavaSchemaVersion
+		name          = $Results[0].Name
+		pib           = $Results[0].Pib
+		statements    = @($statements)
+	}
+}
+
+# Upis svih rezultata u JSON fajl (niz kompanija) ili JSON Lines fajl (jedna kompanija po redu)
+function Export-EprijavaJson {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[switch]$Lines,
+		[switch]$StatAnnex
+	)
+
+	$records = @(foreach ($group in ($Results | Group-Object Name)) {
+		ConvertTo-EprijavaCompanyRecord -Results $group.Group -StatAnnex:$StatAnnex
+	})
+
+	if ($Lines) {
+		Set-Content -Path $Path -Value @($records | ForEach-Object { ConvertTo-Json -InputObject $_ -Depth 4 -Compress }) -Encoding UTF8
+	}
+	else {
+		ConvertTo-Json -InputObject $records -Depth 4 | Set-Content -Path $Path -Encoding UTF8
+	}
+}
+
+# Dodavanje jedne kompanije u JSON Lines fajl
+function Add-EprijavaJsonLine {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [object[]]$Results,
+		[switch]$StatAnnex
+	)
+
+	$record = ConvertTo-EprijavaCompanyRecord -Results $Results -StatAnnex:$StatAnnex
+	Add-Content -Path $Path -Value (ConvertTo-Json -InputObject $record -Depth 4 -Compress) -Encoding UTF8
+}
+
+# Ucitavanje rezultata iz JSON ili JSON Lines fajla, u istom obliku kao Import-EprijavaResults
+function Import-EprijavaJsonResults {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$columns = @(Get-EprijavaResultColumns -StatAnnex | Where-Object { $_.Property -ne "Name" })
+	$content = Get-Content -Path $Path -Raw -Encoding UTF8
+	$records = if ($content.TrimStart().StartsWith("[")) {
+		$content | ConvertFrom-Json | ForEach-Object { $_ }
+	}
+	else {
+		$content -split "`r?`n" | Where-Object { $_.Trim() } | ForEach-Object { $_ | ConvertFrom-Json }
+	}
+
+	$fields = @(@{ Property = "Name"; Type = [string] }, @{ Property = "Pib"; Type = [string] }, @{ Property = "Number"; Type = [string] }) + $columns
+	foreach ($record in $records) {
+		foreach ($statement in $record.statements) {
+			$values = @{ Name = $record.name; Pib = $record.pib; Number = $statement.number }
+			foreach ($column in $columns) {
+				$values[$column.Property] = $statement.($column.Header)
+			}
+			$result = New-EprijavaModelObject -Type ParsedStatement -Values $values -Fields $fields
+			Rename-EprijavaCompanyAlias -Result $result
+		}
+	}
+}
+
+# Putanja do DuckDB komandne linije, koja se koristi za citanje i pisanje Parquet fajlova
+function Get-EprijavaDuckDb {
+	$command = Get-Command -Name duckdb -CommandType Application -ErrorAction SilentlyContinue | Select-Object -First 1
+	if (-not $command) {
+		throw "Za Parquet format potrebna je DuckDB komandna linija (duckdb) u PATH-u: https://duckdb.org/docs/installation"
+	}
+
+	$command.Source
+}
+
+# Izvrsavanje DuckDB upita, uz gresku ukoliko duckdb zavrsi neuspjesno
+function Invoke-EprijavaDuckDb {
+	param(
+		[Parameter(Mandatory)] [string]$Query
+	)
+
+	& (Get-EprijavaDuckDb) -c $Query
+	if ($LASTEXITCODE -ne 0) {
+		throw "DuckDB upit nije uspio: $($Query)"
+	}
+}
+
+# Upis svih rezultata u Parquet fajl sa tipiziranim kolonama (kolone CSV fajla i pib)
+# Rezultati se prvo upisuju u privremeni CSV, koji DuckDB pretvara u Parquet
+function Export-EprijavaParquet {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[switch]$StatAnnex
+	)
+
+	$columns = @(Get-EprijavaResultColumns -StatAnnex:$StatAnnex)
+	$types = @(foreach ($column in $columns) {
+		$sqlType = switch ($column.Type) {
+			([int]) { "INTEGER" }
+			([decimal]) { "DECIMAL(18, 2)" }
+			default { "VARCHAR" }
+		}
+		"'$($column.Header)': '$($sqlType)'"
+	}) + "'pib': 'VARCHAR'"
+
+	$temp = [IO.Path]::GetTempFileName()
+	try {
+		$headers = @($columns.Header) + "pib"
+		Set-Content -Path $temp -Value (($headers | ForEach-Object { """$($_)""" }) -join ",") -Encoding UTF8
+		$rows = foreach ($result in $Results) {
+			$row = [ordered]@{}
+			foreach ($column in $columns) {
+				$row[$column.Header] = $result.($column.Property)
+			}
+			$row["pib"] = $result.Pib
+			[PSCustomObject]$row
+		}
+		if ($rows) {
+			$rows | ConvertTo-Csv -NoTypeInformation | Select-Object -Skip 1 | Add-Content -Path $temp -Encoding UTF8
+		}
+
+		$source = $temp.Replace("'", "''")
+		$target = $Path.Replace("'", "''")
+		Invoke-EprijavaDuckDb -Query "COPY (SELECT * FROM read_csv('$($source)', header = true, columns = {$($types -join ", ")})) TO '$($target)' (FORMAT PARQUET)"
+	}
+	finally {
+		Remove-Item -Path $temp -ErrorAction SilentlyContinue
+	}
+}
+
+# Ucitavanje rezultata iz Parquet fajla, u istom obliku kao Import-EprijavaResults
+function Import-EprijavaParquetResults {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$temp = [IO.Path]::GetTempFileName()
+	try {
+		$source = $Path.Replace("'", "''")
+		$target = $temp.Replace("'", "''")
+		Invoke-EprijavaDuckDb -Query "COPY (SELECT * FROM read_parquet('$($source)')) TO '$($target)' (HEADER, DELIMITER ',')"
+		Import-EprijavaResults -Path $temp
+	}
+	finally {
+		Remove-Item -Path $temp -ErrorAction SilentlyContinue
+	}
+}
+
+# Ucitavanje modula ImportExcel, koji se koristi za citanje i pisanje .xlsx fajlova
+function Import-EprijavaExcelModule {
+	if (-not (Get-Module -Name ImportExcel)) {
+		if (-not (Get-Module -ListAvailable -Name ImportExcel)) {
+			throw "Za Excel format potreban je modul ImportExcel (Install-Module ImportExcel)"
+		}
+		Import-Module ImportExcel
+	}
+}
+
+# Naziv radnog lista za kompaniju: bez znakova koje Excel ne dozvoljava i najvise 31 znak
+function Get-EprijavaWorksheetName {
+	param(
+		[Parameter(Mandatory)] [string]$Name
+	)
+
+	$sheet = [regex]::Replace($Name, '[\[\]:\*\?/\\]', '_').Trim("'")
+	if ($sheet.Length -gt 31) {
+		$sheet = $sheet.Substring(0, 31)
+	}
+
+	$sheet
+}
+
+# Upis svih rezultata u Excel fajl: list "Pregled" sa posljednjom godinom svake kompanije i po jedan list za svaku kompaniju
+# Novcani iznosi se formatiraju sa separatorom hiljada i dvije decimale, a ostali brojevi sa separatorom hiljada
+function Export-EprijavaExcel {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[switch]$StatAnnex
+	)
+
+	Import-EprijavaExcelModule
+	Remove-Item -Path $Path -ErrorAction SilentlyContinue
+
+	$columns = @(Get-EprijavaResultColumns -StatAnnex:$StatAnnex)
+	$groups = @($Results | Group-Object Name | Sort-Object Name)
+
+	$summary = foreach ($group in $groups) {
+		$latest = $group.Group | Where-Object { $_.Latest -ne 0 } | Sort-Object Year -Descending | Select-Object -First 1
+		$row = [ordered]@{}
+		foreach ($column in $columns) {
+			$row[$column.Header] = $latest.($column.Property)
+		}
+		[PSCustomObject]$row
+	}
+	if (-not $summary) {
+		$summary = [PSCustomObject]([ordered]@{ name = "" })
+	}
+	$package = $summary | Export-Excel -Path $Path -WorksheetName "Pregled" -AutoSize -BoldTopRow -FreezeTopRow -PassThru
+
+	foreach ($group in $groups) {
+		$rows = foreach ($result in ($group.Group | Sort-Object Year -Descending)) {
+			$row = [ordered]@{}
+			foreach ($column in $columns) {
+				$row[$column.Header] = $result.($column.Property)
+			}
+			[PSCustomObject]$row
+		}
+		$package = $rows | Export-Excel -ExcelPackage $package -WorksheetName (Get-EprijavaWorksheetName -Name $group.Name) -AutoSize -BoldTopRow -FreezeTopRow -PassThru
+	}
+
+	foreach ($worksheet in $package.Workbook.Worksheets) {
+		for ($i = 0; $i -lt $columns.Count; $i++) {
+			if ($columns[$i].Type -eq [int] -and $columns[$i].Property -notin @("Year", "Version", "Latest")) {
+				Set-ExcelColumn -Worksheet $worksheet -Column ($i + 1) -NumberFormat '#,##0'
+			}
+			elseif ($columns[$i].Type -eq [decimal]) {
+				Set-ExcelColumn -Worksheet $worksheet -Column ($i + 1) -NumberFormat '#,##0.00'
+			}
+		}
+	}
+
+	Close-ExcelPackage $package
+}
+
+# Ucitavanje rezultata iz listova kompanija Excel fajla, u istom obliku kao Import-EprijavaResults
+function Import-EprijavaExcelResults {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	Import-EprijavaExcelModule
+	$columns = @(Get-EprijavaResultColumns -StatAnnex)
+
+	foreach ($sheet in (Get-ExcelSheetInfo -Path $Path | Where-Object { $_.Name -ne "Pregled" })) {
+		foreach ($row in (Import-Excel -Path $Path -WorksheetName $sheet.Name)) {
+			$values = @{}
+			foreach ($column in $columns) {
+				$values[$column.Property] = $row.($column.Header)
+			}
+			$result = New-EprijavaModelObject -Type ParsedStatement -Values $values -Fields $columns
+			Rename-EprijavaCompanyAlias -Result $result
+		}
+	}
+}
+
+# Ucitavanje postojecih rezultata iz bilo kog podrzanog izvora: sqlite://<putanja>, .xlsx, .parquet, .json, .jsonl ili CSV
+function Import-EprijavaDataset {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if ($Path -match '^sqlite://(?<path>.+)$') {
+		return Import-EprijavaDatabaseResults -Path $Matches.path
+	}
+	switch -Regex ($Path) {
+		'\.xlsx$' { return Import-EprijavaExcelResults -Path $Path }
+		'\.parquet$' { return Import-EprijavaParquetResults -Path $Path }
+		'\.jsonl?$' { return Import-EprijavaJsonResults -Path $Path }
+		default { return Import-EprijavaResults -Path $Path }
+	}
+}
+
+# Upis rezultata u zadati format (csv, json, jsonl, parquet ili xlsx), ili u SQLite bazu kada je putanja u formatu sqlite://<putanja>
+# Fajl se upisuje preko privremenog fajla, pa postojeci izlaz ostaje netaknut ako upis ne uspije
+function Export-EprijavaDataset {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[string]$Format = "csv",
+		[switch]$StatAnnex
+	)
+
+	if ($Path -match '^sqlite://(?<path>.+)$') {
+		$database = $Matches.path
+		Initialize-EprijavaDatabase -Path $database
+		foreach ($result in $Results) {
+			Write-EprijavaDatabaseResult -Path $database -Result $result
+		}
+		return
+	}
+
+	$temporary = Open-EprijavaResultFile -Path $Path
+	switch ($Format) {
+		"parquet" { Export-EprijavaParquet -Path $temporary -Results $Results -StatAnnex:$StatAnnex }
+		"xlsx" { Export-EprijavaExcel -Path $temporary -Results $Results -StatAnnex:$StatAnnex }
+		{ $_ -in @("json", "jsonl") } { Export-EprijavaJson -Path $temporary -Results $Results -Lines:($Format -eq "jsonl") -StatAnnex:$StatAnnex }
+		default {
+			Set-EprijavaResultFile -Path $temporary -StatAnnex:$StatAnnex
+			foreach ($result in $Results) {
+				Add-EprijavaResultRow -Path $temporary -Result $result -StatAnnex:$StatAnnex
+			}
+		}
+	}
+	Complete-EprijavaResultFile -Path $Path
+}
+
+# Svojstva rezultata koja nisu pokazatelji, pa u sirokom formatu ne dobijaju kolone po godinama
+$script:PivotExcludedProperties = @("Name", "Pib", "Year", "Version", "Latest")
+
+# Pokazatelji sirokog formata u obliku kolona rezultata (Header je naziv pokazatelja u koloni <POKAZATELJ>_<GODINA>):
+# brojcane kolone izabrane sa -Columns (Set-EprijavaResultColumnSelection), a bez izbora pokazatelji rang liste (revenue, profit,
+# employees, averagePay) i realni iznosi kada je ucitana tabela indeksa (Import-EprijavaCpiTable)
+function Get-EprijavaPivotMetrics {
+	if ($script:ResultColumnSelection) {
+		$metrics = @($script:ResultColumnSelection | Where-Object { $_.Type -in @([decimal], [int]) -and $_.Property -notin $script:PivotExcludedProperties })
+		if ($metrics.Count -eq 0) {
+			throw "Izabrane kolone ($($script:ResultColumnSelection.Header -join ", ")) ne sadrze nijedan brojcani pokazatelj za siroki format"
+		}
+		return $metrics
+	}
+
+	foreach ($metric in $script:RankMetrics.GetEnumerator()) {
+		@{ Header = $metric.Key; Property = $metric.Value; Type = [decimal] }
+	}
+	if (Test-EprijavaCpiTableLoaded) {
+		$script:RealColumns
+	}
+}
+
+# Siroki format (jedan red po kompaniji): kolone name i pib, zatim kolona <POKAZATELJ>_<GODINA> za svaki pokazatelj (Get-EprijavaPivotMetrics)
+# i svaku godinu skupa podataka, npr. revenue_2021, revenue_2022, employees_2021
+# Koriste se samo posljednje verzije izvjestaja, a kompanije su u redosljedu prvog pojavljivanja u rezultatima
+function ConvertTo-EprijavaPivot {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[object[]]$Metrics = @(Get-EprijavaPivotMetrics)
+	)
+
+	$latest = Get-EprijavaLatestByYear -Results $Results
+	$years = @($latest.Values | ForEach-Object { [int]$_.Year } | Sort-Object -Unique)
+	$columns = @(
+		@{ Header = "name"; Property = "Name"; Type = [string] }
+		@{ Header = "pib"; Property = "Pib"; Type = [string] }
+		foreach ($metric in $Metrics) {
+			foreach ($year in $years) {
+				@{ Header = "$($metric.Header)_$($year)"; Property = "$($metric.Header)_$($year)"; Type = $metric.Type }
+			}
+		}
+	)
+
+	$companies = [ordered]@{}
+	foreach ($result in $Results) {
+		$key = Get-EprijavaCompanyKey -Result $result
+		if ($companies.Contains($key)) {
+			continue
+		}
+		$row = [ordered]@{ Name = $result.Name; Pib = $result.Pib }
+		foreach ($metric in $Metrics) {
+			foreach ($year in $years) {
+				$statement = $latest["$($key)|$($year)"]
+				$row["$($metric.Header)_$($year)"] = if (-not $statement) { $null } elseif ($metric.Expression) { & $metric.Expression $statement } else { $statement.($metric.Property) }
+			}
+		}
+		$companies[$key] = [PSCustomObject]$row
+	}
+
+	[PSCustomObject]@{
+		Columns = $columns
+		Rows    = @($companies.Values)
+	}
+}
+
+# Upis rezultata u sirokom formatu (ConvertTo-EprijavaPivot) u CSV fajl, preko privremenog fajla; vraca broj kompanija
+# Siroki format se upisuje samo u CSV (ostali formati izvoza imaju ugnijezdjene godine kompanije)
+function Export-EprijavaPivot {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	$pivot = ConvertTo-EprijavaPivot -Results $Results
+	$temporary = Open-EprijavaResultFile -Path $Path
+	Set-EprijavaResultFile -Path $temporary -Columns $pivot.Columns
+	if ($pivot.Rows.Count -gt 0) {
+		Add-Content -Path $temporary -Value @($pivot.Rows | ForEach-Object { ConvertTo-EprijavaResultLine -Result $_ -Columns $pivot.Columns })
+	}
+	Complete-EprijavaResultFile -Path $Path
+	$pivot.Rows.Count
+}
+
+# Istorija svake kompanije u posebnom folderu: <FOLDER>/<KOMPANIJA>/history.csv i history.json (sve godine i verzije izvjestaja)
+# Nazivi foldera su bezbjedni na Windows-u, a kompanije sa istim nazivom se razlikuju po PIB-u (Get-EprijavaCompanyFolderNames)
+# Vraca broj kompanija
+function Export-EprijavaCompanyHistory {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[switch]$StatAnnex
+	)
+
+	$count = 0
+	$folders = Get-EprijavaCompanyFolderNames -Results $Results
+	foreach ($company in ($Results | Group-Object { Get-EprijavaCompanyKey -Result $_ })) {
+		$history = @($company.Group | Sort-Object { [int]$_.Year }, { [int]$_.Version })
+		$folder = Join-Path $Path $folders[$company.Name]
+		New-Item -ItemType Directory -Force -Path $folder | Out-Null
+
+		Export-EprijavaDataset -Path (Join-Path $folder "history.csv") -Results $history -Format csv -StatAnnex:$StatAnnex
+		Export-EprijavaJson -Path (Join-Path $folder "history.json") -Results $history -StatAnnex:$StatAnnex
+		$count++
+	}
+
+	$count
+}
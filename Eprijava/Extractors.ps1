@@ -0,0 +1,147 @@
+# Dodatni podaci iz izvjestaja kao nezavisni ekstraktori, bez izmjena parsera
+# Svaki ekstraktor ima naziv, kolone koje dodaje rezultatu, blok za izdvajanje i (opciono) blok za provjeru vrijednosti
+# Ekstraktori se pokrecu redom registracije nakon podataka iz pravila (ConvertFrom-EprijavaStatementRows): prvo ugradjeni ekstraktori
+# iz foldera Extractors (bilans stanja i statisticki aneks), a zatim ekstraktori registrovani nakon ucitavanja modula
+
+# Registrovani ekstraktori, po nazivu
+$script:FieldExtractors = [ordered]@{}
+
+# Registracija ekstraktora (ekstraktor sa istim nazivom se zamjenjuje)
+# Columns su kolone u formatu kolona rezultata (@{ Header; Property; Type }), a Unitless = $true oznacava iznos koji se ne preracunava u eure
+# Extract dobija kontekst izvjestaja (Rows, Layout, Unit, Content, AnySection, IncomeStatement, BalanceSheet, StatAnnex, Fields)
+# i vraca hashtable sa vrijednostima po Property kolone, a iznosi su u jedinici izvjestaja
+# Validate dobija izdvojene vrijednosti (u eurima) i kontekst izvjestaja, i vraca nazive podataka koji nedostaju ili nisu ispravni,
+# koji se navode u MissingFields
+# Builtin oznacava ugradjeni ekstraktor cije su kolone vec kolone rezultata (Model.ps1), pa se ne dodaju kao kolone ekstraktora
+function Register-EprijavaFieldExtractor {
+	param(
+		[Parameter(Mandatory)] [string]$Name,
+		[Parameter(Mandatory)] [object[]]$Columns,
+		[Parameter(Mandatory)] [scriptblock]$Extract,
+		[scriptblock]$Validate = $null,
+		[switch]$Builtin
+	)
+
+	if ($Builtin) {
+		$model = @($script:ResultColumns + $script:StatAnnexColumns).Property
+		foreach ($column in $Columns) {
+			if ($model -notcontains $column.Property) {
+				throw "Kolona $($column.Property) ugradjenog ekstraktora $($Name) nije kolona rezultata"
+			}
+		}
+
+		$script:FieldExtractors[$Name] = [PSCustomObject]@{
+			PSTypeName = "Eprijava.FieldExtractor"
+			Name       = $Name
+			Columns    = $Columns
+			Extract    = $Extract
+			Validate   = $Validate
+			Builtin    = $true
+		}
+		return
+	}
+
+	$taken = @{}
+	foreach ($column in $script:ResultColumns + $script:StatAnnexColumns + $script:RegistryColumns + $script:VatColumns + $script:RealColumns + $script:SelectableColumns) {
+		$taken[$column.Property] = "kolona rezultata"
+	}
+	foreach ($field in $script:StatementFields) {
+		$taken[$field.Name] = "pravilo za izdvajanje"
+	}
+	foreach ($extractor in $script:FieldExtractors.Values) {
+		if ($extractor.Name -ne $Name -and -not $extractor.Builtin) {
+			foreach ($column in $extractor.Columns) {
+				$taken[$column.Property] = "ekstraktor $($extractor.Name)"
+			}
+		}
+	}
+
+	foreach ($column in $Columns) {
+		if (-not $column.Property -or -not $column.Header) {
+			throw "Kolona ekstraktora $($Name) mora imati Property i Header"
+		}
+		if ($taken.ContainsKey($column.Property)) {
+			throw "Kolona $($column.Property) ekstraktora $($Name) vec postoji ($($taken[$column.Property]))"
+		}
+		if (-not $column.Type) {
+			$column.Type = [decimal]
+		}
+		$taken[$column.Property] = "ekstraktor $($Name)"
+	}
+
+	$script:FieldExtractors[$Name] = [PSCustomObject]@{
+		PSTypeName = "Eprijava.FieldExtractor"
+		Name       = $Name
+		Columns    = $Columns
+		Extract    = $Extract
+		Validate   = $Validate
+		Builtin    = $false
+	}
+}
+
+# Uklanjanje ekstraktora iz registra
+function Unregister-EprijavaFieldExtractor {
+	param(
+		[Parameter(Mandatory)] [string]$Name
+	)
+
+	$script:FieldExtractors.Remove($Name)
+}
+
+# Registrovani ekstraktori redom pokretanja
+function Get-EprijavaFieldExtractors {
+	$script:FieldExtractors.Values
+}
+
+# Kolone registrovanih ekstraktora (bez ugradjenih, cije su kolone vec kolone rezultata), za kolone rezultata (Get-EprijavaResultColumns)
+function Get-EprijavaExtractorColumns {
+	foreach ($extractor in ($script:FieldExtractors.Values | Where-Object { -not $_.Builtin })) {
+		foreach ($column in $extractor.Columns) {
+			@{ Header = $column.Header; Property = $column.Property; Type = $column.Type }
+		}
+	}
+}
+
+# Pokretanje svih ekstraktora nad jednim izvjestajem
+# Vraca vrijednosti svih kolona ekstraktora (u eurima, osim kolona sa Unitless) i nazive podataka koji nedostaju
+# Greska u jednom ekstraktoru ne prekida parsiranje: biljezi se u logu, a kolone tog ekstraktora se navode kao podaci koji nedostaju
+function Invoke-EprijavaFieldExtractors {
+	param(
+		[Parameter(Mandatory)] $Context
+	)
+
+	$values = @{}
+	$missing = @()
+	foreach ($extractor in $script:FieldExtractors.Values) {
+		$extracted = $null
+		try {
+			$extracted = & $extractor.Extract $Context
+		} catch {
+			Write-EprijavaLog -Message "Ekstraktor $($extractor.Name) nije uspio: $($_.Exception.Message)" -Stage parse -Outcome failed -Level warning -Data @{ extractor = $extractor.Name }
+			$missing += @($extractor.Columns.Property)
+			foreach ($column in $extractor.Columns) {
+				$values[$column.Property] = $null
+			}
+			continue
+		}
+
+		$own = @{}
+		foreach ($column in $extractor.Columns) {
+			$value = if ($extracted -is [System.Collections.IDictionary]) { $extracted[$column.Property] } else { $null }
+			if ($column.Type -eq [decimal] -and -not $column.Unitless) {
+				$value = ConvertTo-EprijavaEuro -Value $value -Factor $Context.Unit.Factor
+			}
+			$own[$column.Property] = $value
+			$values[$column.Property] = $value
+		}
+
+		if ($extractor.Validate) {
+			$missing += @(& $extractor.Validate $own $Context | Where-Object { $_ })
+		}
+	}
+
+	[PSCustomObject]@{
+		Values  = $values
+		Missing = $missing
+	}
+}
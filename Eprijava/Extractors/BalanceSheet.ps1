@@ -0,0 +1,70 @@
+# Ugradjeni ekstraktor bilansa stanja: ukupna aktiva, kapital, obaveze, gotovina i potrazivanja
+
+# Pozicije bilansa stanja se prepoznaju po nazivu, jer se AOP oznake bilansa stanja ponavljaju u drugim tabelama
+# Oznaka na pocetku naziva (npr. "A.", "I", "1.") se preskace, a \S* pokriva slova sa dijakritikom u razlicitim kodiranjima
+$script:BalanceSheetFields = [ordered]@{
+	TotalAssets               = '(?i)ukupna\s+aktiva'
+	Equity                    = '(?i)^(?:[A-Z0-9]{1,4}[.)]\s*)*kapital\b'
+	Liabilities               = '(?i)ukupne\s+obaveze'
+	TotalLiabilitiesAndEquity = '(?i)ukupna\s+pasiva'
+	Cash                      = '(?i)gotovin'
+	Receivables               = '(?i)^(?:[A-Z0-9]{1,4}[.)]\s*)*potra\S*ivanja'
+}
+
+# Bilans stanja: ukupna aktiva, kapital, obaveze, gotovina i potrazivanja ($null za pozicije koje nisu pronadjene)
+# Ukoliko red sa ukupnim obavezama ne postoji, obaveze se racunaju kao ukupna pasiva umanjena za kapital
+# Uz AnySection se pozicije traze u svim redovima (format izvjestaja bez naslova dijelova)
+function Get-EprijavaBalanceSheet {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Rows,
+		[switch]$AnySection
+	)
+
+	$items = @(Get-EprijavaAopItems -Rows @($Rows | Where-Object { $AnySection -or $_.Section -eq "BalanceSheet" }))
+
+	$values = @{}
+	foreach ($field in $script:BalanceSheetFields.GetEnumerator()) {
+		$item = $items | Where-Object { (ConvertTo-EprijavaLabelKey -Text $_.Description) -match $field.Value -and $null -ne $_.Value } | Select-Object -First 1
+		$values[$field.Key] = if ($item) { $item.Value } else { $null }
+	}
+
+	if ($null -eq $values.Liabilities -and $null -ne $values.TotalLiabilitiesAndEquity -and $null -ne $values.Equity) {
+		$values.Liabilities = $values.TotalLiabilitiesAndEquity - $values.Equity
+	}
+
+	[PSCustomObject]@{
+		PSTypeName  = "Eprijava.BalanceSheet"
+		TotalAssets = $values.TotalAssets
+		Equity      = $values.Equity
+		Liabilities = $values.Liabilities
+		Cash        = $values.Cash
+		Receivables = $values.Receivables
+		Items       = $items
+	}
+}
+
+# Kolone bilansa stanja su kolone rezultata (Model.ps1), a pozicije bilansa se upisuju u kontekst za zbirne pozicije i naredne ekstraktore
+# Pozicije bilansa stanja nedostaju samo u izvjestaju sa bilansom stanja: dio sa naslovom bilansa (v2), odnosno bar jedna pronadjena pozicija
+# bilansa kada se pozicije traze u svim tabelama; izvjestaj u formatu legacy i izvjestaj samo sa bilansom uspjeha nemaju bilans stanja
+Register-EprijavaFieldExtractor -Name "BalanceSheet" -Builtin -Columns @($script:ResultColumns | Where-Object { $_.Property -in @("TotalAssets", "Equity", "Liabilities", "Cash", "Receivables") }) -Extract {
+	param($Context)
+
+	$balanceSheet = Get-EprijavaBalanceSheet -Rows $Context.Rows -AnySection:$Context.AnySection
+	$Context.BalanceSheet = $balanceSheet
+
+	# Vrijednosti se vracaju u jedinici izvjestaja, a bilans u kontekstu se odmah preracunava u eure
+	$values = @{}
+	foreach ($property in @("TotalAssets", "Equity", "Liabilities", "Cash", "Receivables")) {
+		$values[$property] = $balanceSheet.$property
+		$balanceSheet.$property = ConvertTo-EprijavaEuro -Value $balanceSheet.$property -Factor $Context.Unit.Factor
+	}
+	$values
+} -Validate {
+	param($Values, $Context)
+
+	$properties = @("TotalAssets", "Equity", "Liabilities", "Cash", "Receivables")
+	$hasBalanceSheet = if ($Context.AnySection) { @($properties | Where-Object { $null -ne $Values[$_] }).Count -gt 0 } else { @($Context.BalanceSheet.Items).Count -gt 0 }
+	if ($hasBalanceSheet) {
+		$properties | Where-Object { $null -eq $Values[$_] }
+	}
+}
@@ -0,0 +1,48 @@
+# Ugradjeni ekstraktor statistickog aneksa: broj vlasnika, struktura vlasnistva i prosjecan broj zaposlenih po kvartalima
+
+# Pozicije statistickog aneksa koje se prepoznaju po nazivu
+$script:StatAnnexFields = @{
+	Shareholders = '(?i)broj\s+(akcionara|vlasnika|osniva\S*|\S*lanova)'
+	Quarter      = '(?i)zaposlen.*(kvartal|tromjese)'
+	Ownership    = '(?i)(dr\S*avn|dru\S*tven|zadru\S*n|privatn|stran|mje\S*ovit)\S*\s+(kapital|svojin)'
+}
+
+# Statisticki aneks: broj vlasnika, struktura vlasnistva i prosjecan broj zaposlenih po kvartalima
+# Struktura vlasnistva je tekst u formatu "<naziv>: <vrijednost>; ...", a kvartali su poredani redom pojavljivanja u aneksu
+function Get-EprijavaStatAnnex {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Rows,
+		[switch]$AnySection
+	)
+
+	$items = @(Get-EprijavaAopItems -Rows @($Rows | Where-Object { $AnySection -or $_.Section -eq "StatAnnex" }))
+
+	$shareholders = $items | Where-Object { (ConvertTo-EprijavaLabelKey -Text $_.Description) -match $script:StatAnnexFields.Shareholders -and $null -ne $_.Value } | Select-Object -First 1
+	$quarters = @($items | Where-Object { (ConvertTo-EprijavaLabelKey -Text $_.Description) -match $script:StatAnnexFields.Quarter } | Select-Object -First 4 | ForEach-Object { $_.Value })
+	$ownership = @($items | Where-Object { (ConvertTo-EprijavaLabelKey -Text $_.Description) -match $script:StatAnnexFields.Ownership -and $null -ne $_.Value } | ForEach-Object { "$($_.Description): $($_.Value)" })
+
+	[PSCustomObject]@{
+		PSTypeName          = "Eprijava.StatAnnex"
+		Shareholders        = if ($shareholders) { $shareholders.Value } else { $null }
+		EmployeesPerQuarter = $quarters
+		Ownership           = $ownership -join "; "
+		Items               = $items
+	}
+}
+
+# Kolone statistickog aneksa su opcione kolone rezultata (Model.ps1, -StatAnnex), a aneks se upisuje u kontekst za naredne ekstraktore
+Register-EprijavaFieldExtractor -Name "StatAnnex" -Builtin -Columns $script:StatAnnexColumns -Extract {
+	param($Context)
+
+	$statAnnex = Get-EprijavaStatAnnex -Rows $Context.Rows -AnySection:$Context.AnySection
+	$Context.StatAnnex = $statAnnex
+
+	@{
+		Shareholders = $statAnnex.Shareholders
+		EmployeesQ1  = $statAnnex.EmployeesPerQuarter[0]
+		EmployeesQ2  = $statAnnex.EmployeesPerQuarter[1]
+		EmployeesQ3  = $statAnnex.EmployeesPerQuarter[2]
+		EmployeesQ4  = $statAnnex.EmployeesPerQuarter[3]
+		Ownership    = $statAnnex.Ownership
+	}
+}
@@ -0,0 +1,102 @@
+# Zapis svih zahtjeva prema portalu i odgovora (-DebugHttp), za tacnu reprodukciju nakon izmjene API-ja portala
+# Svaki zahtjev se upisuje u poseban numerisan fajl: metod i URL, zaglavlja zahtjeva, HTTP status, zaglavlja i tijelo odgovora
+# Vrijednosti cookie-ja (sesija) se ne upisuju, pa se fajlovi mogu priloziti uz prijavu greske
+
+# Zaglavlja cije se vrijednosti zamjenjuju sa <redacted>
+$script:RedactedHeaders = @("Cookie", "Set-Cookie", "Authorization", "Proxy-Authorization")
+
+# Otvaranje foldera za zapis zahtjeva, brojanje se nastavlja nakon postojecih fajlova u folderu
+function Open-EprijavaHttpDump {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	New-Item -ItemType Directory -Force -Path $Path | Out-Null
+	$existing = @(Get-ChildItem -Path $Path -Filter "*.txt" -File | Where-Object { $_.Name -match '^\d+-' } | ForEach-Object { [int]($_.Name -split '-')[0] })
+	$last = if ($existing.Count -gt 0) { ($existing | Measure-Object -Maximum).Maximum } else { 0 }
+
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.HttpDump"
+		Path       = $Path
+		Count      = [int]$last
+	}
+}
+
+# Zaglavlje u obliku "<naziv>: <vrijednost>", uz skrivenu vrijednost cookie-ja i podataka za prijavu
+function ConvertTo-EprijavaDumpHeader {
+	param(
+		[Parameter(Mandatory)] [string]$Name,
+		$Value
+	)
+
+	# Nazivi cookie-ja ostaju vidljivi (npr. taxisSession=<redacted>), kako bi se vidjelo koji se cookie salje ili postavlja
+	foreach ($item in @($Value)) {
+		if ($Name -eq "Cookie") {
+			$item = @("$item" -split ';\s*' | ForEach-Object { "$(($_ -split '=', 2)[0])=<redacted>" }) -join "; "
+		}
+		elseif ($Name -eq "Set-Cookie") {
+			$item = "$(("$item" -split '=', 2)[0])=<redacted>"
+		}
+		elseif ($script:RedactedHeaders -contains $Name) {
+			$item = "<redacted>"
+		}
+		"$($Name): $($item)"
+	}
+}
+
+# Upis jednog zahtjeva i odgovora u fajl <BROJ>-<METOD>-<PUTANJA>.txt, vraca putanju fajla
+# Body je tekst odgovora, JSON objekat (upisuje se kao JSON) ili niz bajtova (upisuje se samo velicina, npr. za PDF)
+function Write-EprijavaHttpDump {
+	param(
+		[Parameter(Mandatory)] $Dump,
+		[Parameter(Mandatory)] [string]$Method,
+		[Parameter(Mandatory)] [string]$Url,
+		$WebSession = $null,
+		[System.Collections.IDictionary]$RequestHeaders = @{},
+		$Status = $null,
+		[System.Collections.IDictionary]$ResponseHeaders = @{},
+		$Body = $null,
+		[string]$ErrorMessage = ""
+	)
+
+	$Dump.Count++
+	$relative = ([uri]$Url).PathAndQuery -replace '^/TaxisPortal/?', ''
+	$name = "{0:D4}-{1}-{2}.txt" -f $Dump.Count, $Method, (ConvertTo-EprijavaSafeFileName -Name ($relative -replace '[/?&=]', '_'))
+	$path = Join-Path $Dump.Path $name
+
+	$lines = [System.Collections.Generic.List[string]]::new()
+	$lines.Add("$($Method) $($Url)")
+	if ($WebSession) {
+		foreach ($cookie in $WebSession.Cookies.GetCookies([uri]$Url)) {
+			$lines.Add("Cookie: $($cookie.Name)=<redacted>")
+		}
+	}
+	foreach ($header in $RequestHeaders.Keys) {
+		foreach ($line in (ConvertTo-EprijavaDumpHeader -Name $header -Value $RequestHeaders[$header])) {
+			$lines.Add($line)
+		}
+	}
+	$lines.Add("")
+	$lines.Add("HTTP $(if ($null -ne $Status) { $Status } else { "?" })")
+	foreach ($header in $ResponseHeaders.Keys) {
+		foreach ($line in (ConvertTo-EprijavaDumpHeader -Name $header -Value $ResponseHeaders[$header])) {
+			$lines.Add($line)
+		}
+	}
+	if ($ErrorMessage) {
+		$lines.Add("X-Eprijava-Error: $($ErrorMessage -replace '\s+', ' ')")
+	}
+	$lines.Add("")
+	if ($Body -is [byte[]]) {
+		$lines.Add("<$($Body.Length) bajtova>")
+	}
+	elseif ($Body -is [string]) {
+		$lines.Add($Body)
+	}
+	elseif ($null -ne $Body) {
+		$lines.Add((ConvertTo-Json -InputObject $Body -Depth 20))
+	}
+
+	[IO.File]::WriteAllText($path, ($lines -join "`n"), [System.Text.UTF8Encoding]::new($false))
+	$path
+}
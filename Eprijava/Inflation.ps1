@@ -0,0 +1,86 @@
+# Realni iznosi (u stalnim eurima) na osnovu tabele indeksa potrosackih cijena (CPI), kako bi kretanje kroz vise godina bilo uporedivo
+# Tabela se ucitava iz CSV fajla (npr. godisnji prosjek indeksa iz MONSTAT-a), a rezultati dobijaju kolone realTotalIncome i realAveragePay
+
+# Indeks cijena po godini i bazna godina (iznosi u cijenama te godine), $null = bez realnih iznosa
+$script:CpiTable = $null
+$script:CpiBaseYear = $null
+
+# Ucitavanje tabele indeksa iz CSV fajla sa kolonama year i index (indeks moze imati bilo koju baznu godinu, npr. 2015 = 100)
+# Bazna godina realnih iznosa je BaseYear, odnosno posljednja godina tabele; vraca broj godina u tabeli
+function Import-EprijavaCpiTable {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[int]$BaseYear = 0
+	)
+
+	if (-not (Test-Path -Path $Path -PathType Leaf)) {
+		throw "Tabela indeksa potrosackih cijena nije pronadjena: $($Path)"
+	}
+
+	$invariant = [cultureinfo]::InvariantCulture
+	$table = @{}
+	$line = 1
+	foreach ($row in (Import-Csv -Path $Path -Encoding UTF8)) {
+		$line++
+		$year = 0
+		$index = [decimal]0
+		if (-not [int]::TryParse("$($row.year)".Trim(), [ref]$year) -or -not [decimal]::TryParse("$($row.index)".Trim(), [System.Globalization.NumberStyles]::Number, $invariant, [ref]$index) -or $index -le 0) {
+			throw "Neispravan red $($line) tabele indeksa $($Path) (ocekivano godina i pozitivan indeks, npr. 2023,132.5)"
+		}
+		$table[$year] = $index
+	}
+	if ($table.Count -eq 0) {
+		throw "Tabela indeksa potrosackih cijena je prazna: $($Path)"
+	}
+
+	if ($BaseYear -eq 0) {
+		$BaseYear = ($table.Keys | Measure-Object -Maximum).Maximum
+	}
+	if (-not $table.ContainsKey($BaseYear)) {
+		throw "Bazna godina $($BaseYear) nije u tabeli indeksa $($Path)"
+	}
+
+	$script:CpiTable = $table
+	$script:CpiBaseYear = $BaseYear
+	$table.Count
+}
+
+# Uklanjanje ucitane tabele indeksa (rezultati vise ne dobijaju realne iznose)
+function Clear-EprijavaCpiTable {
+	$script:CpiTable = $null
+	$script:CpiBaseYear = $null
+}
+
+# Da li je tabela indeksa ucitana
+function Test-EprijavaCpiTableLoaded {
+	$null -ne $script:CpiTable
+}
+
+# Iznos godine izvjestaja u cijenama bazne godine, $null ukoliko iznos nedostaje ili godine nema u tabeli
+function ConvertTo-EprijavaRealValue {
+	param(
+		$Value,
+		[Parameter(Mandatory)] [int]$Year
+	)
+
+	if ($null -eq $Value -or -not (Test-EprijavaCpiTableLoaded) -or -not $script:CpiTable.ContainsKey($Year)) {
+		return $null
+	}
+
+	ConvertTo-EprijavaMoney -Value ([decimal]$Value * $script:CpiTable[$script:CpiBaseYear] / $script:CpiTable[$Year])
+}
+
+# Dodavanje realnih iznosa rezultatu, rezultat se vraca neizmijenjen kada tabela nije ucitana
+function Add-EprijavaRealValues {
+	param(
+		[Parameter(Mandatory, ValueFromPipeline)] $Result
+	)
+
+	process {
+		if (Test-EprijavaCpiTableLoaded) {
+			$Result | Add-Member -NotePropertyName RealTotalIncome -NotePropertyValue (ConvertTo-EprijavaRealValue -Value $Result.TotalIncome -Year $Result.Year) -Force
+			$Result | Add-Member -NotePropertyName RealAveragePay -NotePropertyValue (ConvertTo-EprijavaRealValue -Value $Result.AveragePay -Year $Result.Year) -Force
+		}
+		$Result
+	}
+}
@@ -0,0 +1,108 @@
+# Prekid prikupljanja sa Ctrl+C ili signalom SIGTERM: tekuci izvjestaj se zavrsava i upisuje, a zatim se prikupljanje zaustavlja
+# uz upis izlaza, stanja prikupljanja i izvjestaja o greskama (umjesto naglog prekida usred upisa reda)
+
+$script:StopRequested = $false
+$script:TreatControlCAsInput = $null
+
+# SIGTERM (npr. kill, systemctl stop, docker stop) stize na posebnoj niti bez PowerShell runspace-a, pa ga obradjuje kompajliran handler
+# koji samo postavlja oznaku prekida; PosixSignalRegistration postoji od PowerShell 7.2, a u ranijim verzijama SIGTERM prekida proces odmah
+$script:StopSignalSource = @'
+using System;
+using System.Runtime.InteropServices;
+
+namespace Eprijava {
+	public static class StopSignal {
+		private static volatile bool requested;
+		private static PosixSignalRegistration registration;
+
+		public static bool Requested { get { return requested; } }
+
+		public static void Enable() {
+			requested = false;
+			if (registration == null) {
+				try {
+					registration = PosixSignalRegistration.Create(PosixSignal.SIGTERM, context => {
+						context.Cancel = true;
+						requested = true;
+					});
+				}
+				catch (PlatformNotSupportedException) {
+				}
+			}
+		}
+
+		public static void Disable() {
+			if (registration != null) {
+				registration.Dispose();
+				registration = null;
+			}
+		}
+	}
+}
+'@
+
+# Da li PowerShell podrzava obradu SIGTERM (handler se kompajlira pri prvom pozivu)
+function Test-EprijavaStopSignalSupported {
+	if ("Eprijava.StopSignal" -as [type]) {
+		return $true
+	}
+	if (-not ("System.Runtime.InteropServices.PosixSignalRegistration" -as [type])) {
+		return $false
+	}
+
+	Add-Type -TypeDefinition $script:StopSignalSource
+	$true
+}
+
+# Ukljucivanje prekida: SIGTERM postavlja oznaku prekida umjesto zaustavljanja procesa, i kada ulaz nije konzola (cron, CI, systemd),
+# a uz konzolu se Ctrl+C cita kao unos (bez konzole Ctrl+C prekida proces odmah, jer ga PowerShell konzola sama obradjuje)
+# Poziva se uz try/finally sa Disable-EprijavaStopRequest, kako greska ne bi ostavila konzolu bez uobicajenog ponasanja Ctrl+C
+function Enable-EprijavaStopRequest {
+	$script:StopRequested = $false
+	if (Test-EprijavaStopSignalSupported) {
+		[Eprijava.StopSignal]::Enable()
+	}
+	if ([Console]::IsInputRedirected) {
+		return
+	}
+
+	$script:TreatControlCAsInput = [Console]::TreatControlCAsInput
+	[Console]::TreatControlCAsInput = $true
+}
+
+# Vracanje uobicajenog ponasanja Ctrl+C i SIGTERM
+function Disable-EprijavaStopRequest {
+	if ("Eprijava.StopSignal" -as [type]) {
+		[Eprijava.StopSignal]::Disable()
+	}
+	if ($null -ne $script:TreatControlCAsInput) {
+		[Console]::TreatControlCAsInput = $script:TreatControlCAsInput
+		$script:TreatControlCAsInput = $null
+	}
+}
+
+# Da li je zatrazen prekid (Ctrl+C ili SIGTERM od ukljucivanja), ostali pritisnuti tasteri se zanemaruju
+function Test-EprijavaStopRequested {
+	if ($script:StopRequested) {
+		return $true
+	}
+
+	$signal = $null
+	if (("Eprijava.StopSignal" -as [type]) -and [Eprijava.StopSignal]::Requested) {
+		$signal = "SIGTERM"
+	}
+	elseif ($null -ne $script:TreatControlCAsInput) {
+		while ([Console]::KeyAvailable) {
+			$key = [Console]::ReadKey($true)
+			if ($key.Key -eq [ConsoleKey]::C -and ($key.Modifiers -band [ConsoleModifiers]::Control)) {
+				$signal = "Ctrl+C"
+			}
+		}
+	}
+
+	if ($signal) {
+		$script:StopRequested = $true
+		Write-EprijavaLog -Message "Prekid je zatrazen ($($signal)), prikupljanje se zaustavlja nakon tekuceg izvjestaja" -Level warning -Stage run -Outcome interrupted
+	}
+	$script:StopRequested
+}
@@ -0,0 +1,134 @@
+# Jezik izlaza: zaglavlja CSV fajlova i natpisi izvjestaja i grafikona
+# Bez zadatog jezika zaglavlja su na engleskom (nazivi polja), a natpisi izvjestaja na crnogorskom, kao u ranijim verzijama
+
+# Zadati jezik (me ili en), $null = podrazumijevano ponasanje
+$script:Locale = $null
+
+# Zaglavlja CSV fajlova na crnogorskom (me), po nazivu kolone na engleskom
+$script:MontenegrinHeaders = @{
+	name                         = "naziv"
+	pib                          = "pib"
+	Year                         = "godina"
+	totalIncome                  = "ukupniPrihodi"
+	profit                       = "netoRezultat"
+	employeeCount                = "brojZaposlenih"
+	netPayCosts                  = "netoTroskoviZarada"
+	averagePay                   = "prosjecnaZarada"
+	averageGrossPay              = "prosjecnaBrutoZarada"
+	totalAssets                  = "ukupnaAktiva"
+	equity                       = "kapital"
+	liabilities                  = "obaveze"
+	cash                         = "gotovina"
+	receivables                  = "potrazivanja"
+	version                      = "verzija"
+	latest                       = "posljednja"
+	parseStatus                  = "statusParsiranja"
+	unit                         = "jedinica"
+	shareholders                 = "brojVlasnika"
+	employeesQ1                  = "zaposleniK1"
+	employeesQ2                  = "zaposleniK2"
+	employeesQ3                  = "zaposleniK3"
+	employeesQ4                  = "zaposleniK4"
+	ownership                    = "vlasnickaStruktura"
+	foundedAt                    = "datumOsnivanja"
+	activityCode                 = "sifraDjelatnosti"
+	activity                     = "djelatnost"
+	address                      = "adresa"
+	owners                       = "osnivaci"
+	vatRegistered                = "pdvRegistrovan"
+	vatActive                    = "pdvAktivan"
+	realTotalIncome              = "realniUkupniPrihodi"
+	realAveragePay               = "realnaProsjecnaZarada"
+	netMargin                    = "netoMarza"
+	revenuePerEmployee           = "prihodPoZaposlenom"
+	revenueGrowth                = "rastPrihoda"
+	employeeGrowth               = "rastZaposlenih"
+	averagePayPercentile         = "percentilProsjecneZarade"
+	revenuePerEmployeePercentile = "percentilPrihodaPoZaposlenom"
+	netMarginPercentile          = "percentilNetoMarze"
+	companies                    = "kompanije"
+	medianTotalIncome            = "medijanaUkupnihPrihoda"
+	medianAveragePay             = "medijanaProsjecneZarade"
+	medianEmployeeCount          = "medijanaBrojaZaposlenih"
+	metric                       = "pokazatelj"
+	rank                         = "mjesto"
+	previousRank                 = "prethodnoMjesto"
+	rankChange                   = "promjenaMjesta"
+	value                        = "vrijednost"
+	previousValue                = "prethodnaVrijednost"
+	kind                         = "vrsta"
+	field                        = "polje"
+	oldValue                     = "staraVrijednost"
+	newValue                     = "novaVrijednost"
+	check                        = "provjera"
+	limit                        = "granica"
+	message                      = "poruka"
+	tag                          = "oznaka"
+}
+
+# Natpisi izvjestaja i grafikona na engleskom (en), po natpisu na crnogorskom
+$script:EnglishLabels = @{
+	"Finansijski izvjestaji kompanija" = "Company financial statements"
+	"Pregled sektora"                  = "Sector overview"
+	"Pregled po oznakama"              = "Overview by tag"
+	"Sektor"                           = "Sector"
+	"Godina"                           = "Year"
+	"Kompanije"                        = "Companies"
+	"Ukupni prihodi"                   = "Total income"
+	"Neto rezultat"                    = "Net result"
+	"Zaposleni"                        = "Employees"
+	"Broj zaposlenih"                  = "Number of employees"
+	"Prosjecna zarada"                 = "Average pay"
+	"Medijana prosjecne zarade"        = "Median average pay"
+	"PIB"                              = "Tax ID"
+}
+
+# Izbor jezika izlaza (me ili en), prazno vraca podrazumijevano ponasanje
+function Set-EprijavaLocale {
+	param(
+		[AllowEmptyString()] [ValidateSet("", "me", "en")] [string]$Locale = ""
+	)
+
+	$script:Locale = if ($Locale) { $Locale } else { $null }
+}
+
+# Zadati jezik izlaza, $null ukoliko nije zadat
+function Get-EprijavaLocale {
+	$script:Locale
+}
+
+# Zaglavlje kolone na zadatom jeziku (kolone bez prevoda, npr. iz pravila za izdvajanje, ostaju nepromijenjene)
+function ConvertTo-EprijavaLocalHeader {
+	param(
+		[Parameter(Mandatory)] [string]$Header
+	)
+
+	if ($script:Locale -eq "me" -and $script:MontenegrinHeaders.ContainsKey($Header)) {
+		return $script:MontenegrinHeaders[$Header]
+	}
+	$Header
+}
+
+# Svi nazivi pod kojima kolona moze biti u postojecem fajlu (engleski i crnogorski), za ucitavanje nezavisno od jezika
+function Get-EprijavaHeaderNames {
+	param(
+		[Parameter(Mandatory)] [string]$Header
+	)
+
+	$Header
+	if ($script:MontenegrinHeaders.ContainsKey($Header)) {
+		$script:MontenegrinHeaders[$Header]
+	}
+}
+
+# Natpis izvjestaja ili grafikona na zadatom jeziku
+function ConvertTo-EprijavaLocalLabel {
+	param(
+		[Parameter(Mandatory)] [string]$Text
+	)
+
+	if ($script:Locale -eq "en" -and $script:EnglishLabels.ContainsKey($Text)) {
+		return $script:EnglishLabels[$Text]
+	}
+	$Text
+}
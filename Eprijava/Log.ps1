@@ -0,0 +1,56 @@
+# Poruke o toku prikupljanja: tekst za konzolu ili JSON dogadjaji (jedan po redu) za CI/cron pokretanja i obradu logova
+
+# Format poruka (text ili json)
+$script:LogFormat = "text"
+
+# Izbor formata poruka
+function Set-EprijavaLogFormat {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("text", "json")] [string]$Format
+	)
+
+	$script:LogFormat = $Format
+}
+
+# Da li se poruke ispisuju kao JSON dogadjaji
+function Test-EprijavaJsonLog {
+	$script:LogFormat -eq "json"
+}
+
+# Jedna poruka: u tekstualnom formatu se ispisuje kao do sada (upozorenja preko Write-Warning),
+# a u JSON formatu kao objekat sa vremenom, nivoom, fazom (Stage), ishodom (Outcome), kompanijom, godinom, dodatnim podacima (Data) i tekstom poruke
+function Write-EprijavaLog {
+	param(
+		[Parameter(Mandatory)] [string]$Message,
+		[string]$Stage = "run",
+		[string]$Outcome = "info",
+		[ValidateSet("info", "warning")] [string]$Level = "info",
+		[string]$Pib,
+		[string]$Name,
+		[string]$Year,
+		[string]$Number,
+		[System.Collections.IDictionary]$Data = @{}
+	)
+
+	if ($script:LogFormat -ne "json") {
+		if ($Level -eq "warning") { Write-Warning $Message } else { Write-Host $Message }
+		return
+	}
+
+	$entry = [ordered]@{
+		time    = (Get-Date).ToUniversalTime().ToString("o")
+		level   = $Level
+		stage   = $Stage
+		outcome = $Outcome
+	}
+	if ($Pib) { $entry.pib = $Pib }
+	if ($Name) { $entry.company = $Name }
+	if ($Year) { $entry.year = [int]$Year }
+	if ($Number) { $entry.number = $Number }
+	foreach ($key in $Data.Keys) {
+		$entry[$key] = $Data[$key]
+	}
+	$entry.message = $Message.Trim()
+
+	[Console]::Out.WriteLine((ConvertTo-Json -InputObject $entry -Compress))
+}
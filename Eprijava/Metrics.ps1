@@ -0,0 +1,81 @@
+# Izvedeni pokazatelji koji se racunaju nakon parsiranja svih izvjestaja kompanije:
+# neto marza, prihod po zaposlenom i godisnji rast prihoda i broja zaposlenih
+
+# Udio vrijednosti u osnovici zaokruzen na cetiri decimale, $null ukoliko neka vrijednost nedostaje ili osnovica nije pozitivna
+function Get-EprijavaRatio {
+	param(
+		$Value,
+		$Base
+	)
+
+	if ($null -eq $Value -or $null -eq $Base -or $Base -le 0) {
+		return $null
+	}
+
+	[math]::Round([decimal]$Value / [decimal]$Base, 4, [MidpointRounding]::AwayFromZero)
+}
+
+# Prihod po zaposlenom, $null ukoliko prihod ili broj zaposlenih nedostaje
+function Get-EprijavaRevenuePerEmployee {
+	param(
+		[Parameter(Mandatory)] $Result
+	)
+
+	if ($null -eq $Result.TotalIncome -or -not ($Result.EmployeeCount -gt 0)) {
+		return $null
+	}
+
+	ConvertTo-EprijavaMoney -Value ([decimal]$Result.TotalIncome / [decimal]$Result.EmployeeCount)
+}
+
+# Pokazatelji za svaku kompaniju i godinu (samo posljednje verzije izvjestaja)
+# Rast se racuna u odnosu na prethodnu godinu iste kompanije, a izostaje ukoliko izvjestaja za prethodnu godinu nema
+# Percentili (0-100) su polozaj kompanije medju svim kompanijama iste godine (Get-EprijavaPercentileRank)
+function Get-EprijavaDerivedMetrics {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	$latest = @($Results | Where-Object { $_.Latest -ne 0 })
+	$benchmarks = Get-EprijavaBenchmarkValues -Results $latest
+	foreach ($company in ($latest | Group-Object { if ($_.Pib) { $_.Pib } else { $_.Name } } | Sort-Object Name)) {
+		$byYear = @{}
+		foreach ($result in $company.Group) {
+			$byYear[[int]$result.Year] = $result
+		}
+
+		foreach ($result in ($company.Group | Sort-Object { [int]$_.Year })) {
+			$previous = $byYear[[int]$result.Year - 1]
+			$percentiles = @{}
+			foreach ($metric in $script:BenchmarkMetrics.GetEnumerator()) {
+				$value = & $metric.Value $result
+				$percentiles[$metric.Key] = if ($null -ne $value) { Get-EprijavaPercentileRank -Sorted $benchmarks["$([int]$result.Year)|$($metric.Key)"] -Value $value } else { $null }
+			}
+			New-EprijavaModelObject -Type Metrics -Values @{
+				Name                         = $result.Name
+				Pib                          = $result.Pib
+				Year                         = [int]$result.Year
+				NetMargin                    = Get-EprijavaRatio -Value $result.Profit -Base $result.TotalIncome
+				RevenuePerEmployee           = Get-EprijavaRevenuePerEmployee -Result $result
+				RevenueGrowth                = if ($previous -and $null -ne $result.TotalIncome) { Get-EprijavaRatio -Value ($result.TotalIncome - $previous.TotalIncome) -Base $previous.TotalIncome } else { $null }
+				EmployeeGrowth               = if ($previous -and $null -ne $result.EmployeeCount) { Get-EprijavaRatio -Value ($result.EmployeeCount - $previous.EmployeeCount) -Base $previous.EmployeeCount } else { $null }
+				AveragePayPercentile         = $percentiles.averagePay
+				RevenuePerEmployeePercentile = $percentiles.revenuePerEmployee
+				NetMarginPercentile          = $percentiles.netMargin
+			}
+		}
+	}
+}
+
+# Upis pokazatelja u CSV fajl
+function Export-EprijavaMetrics {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Metrics
+	)
+
+	Set-EprijavaResultFile -Path $Path -Columns $script:MetricColumns
+	if ($Metrics.Count -gt 0) {
+		Add-Content -Path $Path -Value @($Metrics | ForEach-Object { ConvertTo-EprijavaResultLine -Result $_ -Columns $script:MetricColumns })
+	}
+}
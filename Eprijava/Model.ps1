@@ -0,0 +1,267 @@
+# Model podataka: kolone rezultata, novcani iznosi i tipovi objekata modula (kompanija, izvjestaj na portalu, obradjeni izvjestaj, pokazatelji)
+# Iznosi se cuvaju kao [decimal] (bez gresaka zaokruzivanja koje ima [double]) i zaokruzuju na centove
+
+# Kolone rezultata (CSV fajl i ostali formati): naziv u zaglavlju, svojstvo rezultata i tip vrijednosti
+# Novcani iznosi su [decimal] sa najvise dvije decimale, a brojevi (godina, zaposleni, verzija) su [int]
+$script:ResultColumns = @(
+	@{ Header = "name"; Property = "Name"; Type = [string] }
+	@{ Header = "Year"; Property = "Year"; Type = [int] }
+	@{ Header = "totalIncome"; Property = "TotalIncome"; Type = [decimal] }
+	@{ Header = "profit"; Property = "Profit"; Type = [decimal] }
+	@{ Header = "employeeCount"; Property = "EmployeeCount"; Type = [int] }
+	@{ Header = "netPayCosts"; Property = "NetPayCosts"; Type = [decimal] }
+	@{ Header = "averagePay"; Property = "AveragePay"; Type = [decimal] }
+	@{ Header = "averageGrossPay"; Property = "AverageGrossPay"; Type = [decimal] }
+	@{ Header = "totalAssets"; Property = "TotalAssets"; Type = [decimal] }
+	@{ Header = "equity"; Property = "Equity"; Type = [decimal] }
+	@{ Header = "liabilities"; Property = "Liabilities"; Type = [decimal] }
+	@{ Header = "cash"; Property = "Cash"; Type = [decimal] }
+	@{ Header = "receivables"; Property = "Receivables"; Type = [decimal] }
+	@{ Header = "version"; Property = "Version"; Type = [int] }
+	@{ Header = "latest"; Property = "Latest"; Type = [int] }
+	@{ Header = "parseStatus"; Property = "ParseStatus"; Type = [string] }
+	@{ Header = "unit"; Property = "Unit"; Type = [string] }
+)
+
+# Opcione kolone iz statistickog aneksa
+$script:StatAnnexColumns = @(
+	@{ Header = "shareholders"; Property = "Shareholders"; Type = [int] }
+	@{ Header = "employeesQ1"; Property = "EmployeesQ1"; Type = [int] }
+	@{ Header = "employeesQ2"; Property = "EmployeesQ2"; Type = [int] }
+	@{ Header = "employeesQ3"; Property = "EmployeesQ3"; Type = [int] }
+	@{ Header = "employeesQ4"; Property = "EmployeesQ4"; Type = [int] }
+	@{ Header = "ownership"; Property = "Ownership"; Type = [string] }
+)
+
+# Opcione kolone iz registra privrednih subjekata (Set-EprijavaRegistry)
+$script:RegistryColumns = @(
+	@{ Header = "foundedAt"; Property = "FoundedAt"; Type = [string] }
+	@{ Header = "activityCode"; Property = "ActivityCode"; Type = [string] }
+	@{ Header = "activity"; Property = "Activity"; Type = [string] }
+	@{ Header = "address"; Property = "Address"; Type = [string] }
+	@{ Header = "owners"; Property = "Owners"; Type = [string] }
+)
+
+# Opcione kolone iz liste obveznika PDV-a (Import-EprijavaVatList)
+$script:VatColumns = @(
+	@{ Header = "vatRegistered"; Property = "VatRegistered"; Type = [int] }
+	@{ Header = "vatActive"; Property = "VatActive"; Type = [int] }
+)
+
+# Opcione kolone sa realnim iznosima u cijenama bazne godine (Import-EprijavaCpiTable)
+$script:RealColumns = @(
+	@{ Header = "realTotalIncome"; Property = "RealTotalIncome"; Type = [decimal] }
+	@{ Header = "realAveragePay"; Property = "RealAveragePay"; Type = [decimal] }
+)
+
+# Kolone koje se upisuju samo kada su izabrane (Set-EprijavaResultColumnSelection): PIB i pokazatelji koji se racunaju iz istog reda
+# Pokazatelji rasta zahtijevaju prethodnu godinu, pa su samo u Metrics.csv
+$script:SelectableColumns = @(
+	@{ Header = "pib"; Property = "Pib"; Type = [string] }
+	@{ Header = "netMargin"; Property = "NetMargin"; Type = [decimal]; Expression = { param($Result) Get-EprijavaRatio -Value $Result.Profit -Base $Result.TotalIncome } }
+	@{ Header = "revenuePerEmployee"; Property = "RevenuePerEmployee"; Type = [decimal]; Expression = { param($Result) Get-EprijavaRevenuePerEmployee -Result $Result } }
+)
+
+# Kolone izvedenih pokazatelja (Metrics.csv): neto marza i godisnji rast su udjeli (0.15 = 15%)
+$script:MetricColumns = @(
+	@{ Header = "name"; Property = "Name"; Type = [string] }
+	@{ Header = "pib"; Property = "Pib"; Type = [string] }
+	@{ Header = "Year"; Property = "Year"; Type = [int] }
+	@{ Header = "netMargin"; Property = "NetMargin"; Type = [decimal] }
+	@{ Header = "revenuePerEmployee"; Property = "RevenuePerEmployee"; Type = [decimal] }
+	@{ Header = "revenueGrowth"; Property = "RevenueGrowth"; Type = [decimal] }
+	@{ Header = "employeeGrowth"; Property = "EmployeeGrowth"; Type = [decimal] }
+	@{ Header = "averagePayPercentile"; Property = "AveragePayPercentile"; Type = [decimal] }
+	@{ Header = "revenuePerEmployeePercentile"; Property = "RevenuePerEmployeePercentile"; Type = [decimal] }
+	@{ Header = "netMarginPercentile"; Property = "NetMarginPercentile"; Type = [decimal] }
+)
+
+# Kolone zbirnog pregleda sektora po godinama (Summary.csv)
+$script:SummaryColumns = @(
+	@{ Header = "Year"; Property = "Year"; Type = [int] }
+	@{ Header = "companies"; Property = "Companies"; Type = [int] }
+	@{ Header = "totalIncome"; Property = "TotalIncome"; Type = [decimal] }
+	@{ Header = "profit"; Property = "Profit"; Type = [decimal] }
+	@{ Header = "employeeCount"; Property = "EmployeeCount"; Type = [int] }
+	@{ Header = "medianTotalIncome"; Property = "MedianTotalIncome"; Type = [decimal] }
+	@{ Header = "medianAveragePay"; Property = "MedianAveragePay"; Type = [decimal] }
+	@{ Header = "medianEmployeeCount"; Property = "MedianEmployeeCount"; Type = [decimal] }
+)
+
+# Kolone percentilnih raspona sektora po godinama (Benchmarks.csv): pokazatelj, broj kompanija sa podatkom i 25., 50. i 75. percentil
+$script:BenchmarkColumns = @(
+	@{ Header = "Year"; Property = "Year"; Type = [int] }
+	@{ Header = "metric"; Property = "Metric"; Type = [string] }
+	@{ Header = "companies"; Property = "Companies"; Type = [int] }
+	@{ Header = "p25"; Property = "P25"; Type = [decimal] }
+	@{ Header = "p50"; Property = "P50"; Type = [decimal] }
+	@{ Header = "p75"; Property = "P75"; Type = [decimal] }
+)
+
+# Kolone rang liste (komanda rank): pokazatelj, mjesto, mjesto u prethodnoj godini i promjena mjesta
+$script:RankColumns = @(
+	@{ Header = "metric"; Property = "Metric"; Type = [string] }
+	@{ Header = "rank"; Property = "Rank"; Type = [int] }
+	@{ Header = "previousRank"; Property = "PreviousRank"; Type = [int] }
+	@{ Header = "rankChange"; Property = "RankChange"; Type = [int] }
+	@{ Header = "name"; Property = "Name"; Type = [string] }
+	@{ Header = "pib"; Property = "Pib"; Type = [string] }
+	@{ Header = "Year"; Property = "Year"; Type = [int] }
+	@{ Header = "value"; Property = "Value"; Type = [decimal] }
+	@{ Header = "previousValue"; Property = "PreviousValue"; Type = [decimal] }
+)
+
+# Kolone razlika izmedju dva skupa rezultata (komanda diff): vrsta razlike, kompanija, godina, kolona i vrijednosti
+$script:DiffColumns = @(
+	@{ Header = "kind"; Property = "Kind"; Type = [string] }
+	@{ Header = "name"; Property = "Name"; Type = [string] }
+	@{ Header = "pib"; Property = "Pib"; Type = [string] }
+	@{ Header = "Year"; Property = "Year"; Type = [int] }
+	@{ Header = "field"; Property = "Field"; Type = [string] }
+	@{ Header = "oldValue"; Property = "OldValue"; Type = [string] }
+	@{ Header = "newValue"; Property = "NewValue"; Type = [string] }
+)
+
+# Kolone anomalija (anomalies.csv): kompanija, godina i verzija izvjestaja, provjera, vrijednost, granica i opis
+$script:AnomalyColumns = @(
+	@{ Header = "name"; Property = "Name"; Type = [string] }
+	@{ Header = "pib"; Property = "Pib"; Type = [string] }
+	@{ Header = "Year"; Property = "Year"; Type = [int] }
+	@{ Header = "version"; Property = "Version"; Type = [int] }
+	@{ Header = "check"; Property = "Check"; Type = [string] }
+	@{ Header = "value"; Property = "Value"; Type = [decimal] }
+	@{ Header = "limit"; Property = "Limit"; Type = [decimal] }
+	@{ Header = "message"; Property = "Message"; Type = [string] }
+)
+
+# Novcani iznos zaokruzen na dvije decimale, $null ostaje $null
+function ConvertTo-EprijavaMoney {
+	param(
+		$Value
+	)
+
+	if ($null -eq $Value) {
+		return $null
+	}
+
+	[math]::Round([decimal]$Value, 2, [MidpointRounding]::AwayFromZero)
+}
+
+# Tipovi objekata modula: kompanija (lista kompanija, pretraga), izvjestaj sa liste izvjestaja kompanije na portalu,
+# obradjeni izvjestaj (rezultat, New-EprijavaScrapeResult i ucitavanje postojecih rezultata) i izvedeni pokazatelji
+$script:ModelTypeNames = @{
+	Company         = "Eprijava.Company"
+	StatementRef    = "Eprijava.FinancialStatement"
+	ParsedStatement = "Eprijava.ScrapeResult"
+	Metrics         = "Eprijava.Metrics"
+}
+
+# Polja kompanije: PIB, naziv i raniji nazivi
+$script:CompanyFields = @(
+	@{ Property = "Pib"; Type = [string] }
+	@{ Property = "Name"; Type = [string] }
+	@{ Property = "Aliases"; Type = [string[]] }
+)
+
+# Polja izvjestaja sa liste izvjestaja: PIB, redni broj, godina, verzija izvjestaja za godinu i oznaka posljednje verzije
+$script:StatementRefFields = @(
+	@{ Property = "Pib"; Type = [string] }
+	@{ Property = "Number"; Type = [string] }
+	@{ Property = "Year"; Type = [int] }
+	@{ Property = "Version"; Type = [int] }
+	@{ Property = "Latest"; Type = [bool] }
+)
+
+# Polja tipa modela; obradjeni izvjestaj ima PIB, redni broj i sve kolone rezultata (ukljucujuci kolone iz pravila, ekstraktora i aneksa)
+function Get-EprijavaModelFields {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("Company", "StatementRef", "ParsedStatement", "Metrics")] [string]$Type
+	)
+
+	switch ($Type) {
+		"Company" { $script:CompanyFields }
+		"StatementRef" { $script:StatementRefFields }
+		"Metrics" { $script:MetricColumns }
+		"ParsedStatement" {
+			@{ Property = "Pib"; Type = [string] }
+			@{ Property = "Number"; Type = [string] }
+			Get-EprijavaResultColumns -StatAnnex
+			$script:SelectableColumns | Where-Object { $_.Property -ne "Pib" }
+		}
+	}
+}
+
+# Vrijednost polja zadatog tipa: $null, DBNull i prazan tekst postaju $null, a tekst "true"/"false" (npr. iz CSV fajla) je [bool]
+function ConvertTo-EprijavaModelValue {
+	param(
+		$Value,
+		[Parameter(Mandatory)] [type]$Type
+	)
+
+	if ($null -eq $Value -or $Value -is [System.DBNull]) {
+		return $null
+	}
+	if ($Value -is [string]) {
+		$Value = $Value.Trim()
+		if ($Value -eq "") {
+			return $null
+		}
+		if ($Type -eq [bool]) {
+			return $Value -in @("true", "1")
+		}
+	}
+	if ($Type.IsArray) {
+		return , ($Value -as $Type)
+	}
+
+	$Value -as $Type
+}
+
+# Objekat tipa modela iz vrijednosti po nazivu polja (hashtable ili objekat), polja bez vrijednosti su $null
+# Uz Fields se koriste zadata polja (npr. samo kolone koje postoje u ucitanom fajlu)
+function New-EprijavaModelObject {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("Company", "StatementRef", "ParsedStatement", "Metrics")] [string]$Type,
+		[Parameter(Mandatory)] $Values,
+		[object[]]$Fields = @(Get-EprijavaModelFields -Type $Type)
+	)
+
+	$record = [ordered]@{ PSTypeName = $script:ModelTypeNames[$Type] }
+	foreach ($field in $Fields) {
+		$value = if ($Values -is [System.Collections.IDictionary]) { $Values[$field.Property] } else { $Values.($field.Property) }
+		$record[$field.Property] = ConvertTo-EprijavaModelValue -Value $value -Type $field.Type
+	}
+
+	[PSCustomObject]$record
+}
+
+# JSON niz objekata tipa modela (samo polja modela, bez ugnijezdjenih podataka kao sto je bilans uspjeha)
+function ConvertTo-EprijavaModelJson {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("Company", "StatementRef", "ParsedStatement", "Metrics")] [string]$Type,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$InputObject
+	)
+
+	$fields = @(Get-EprijavaModelFields -Type $Type)
+	$records = foreach ($item in $InputObject) {
+		$record = [ordered]@{}
+		foreach ($field in $fields) {
+			$record[$field.Property] = $item.($field.Property)
+		}
+		$record
+	}
+
+	ConvertTo-Json -InputObject @($records) -Depth 3 -Compress
+}
+
+# Objekti tipa modela iz JSON niza (ConvertTo-EprijavaModelJson), sa istim tipovima vrijednosti
+function ConvertFrom-EprijavaModelJson {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("Company", "StatementRef", "ParsedStatement", "Metrics")] [string]$Type,
+		[Parameter(Mandatory)] [string]$Json
+	)
+
+	foreach ($item in @($Json | ConvertFrom-Json | ForEach-Object { $_ })) {
+		New-EprijavaModelObject -Type $Type -Values $item
+	}
+}
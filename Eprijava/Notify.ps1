@@ -0,0 +1,119 @@
+# Obavjestenja o prikupljanju: webhook o novim izvjestajima (Slack/Discord kompatibilan JSON) i e-mail sa izvjestajem pokretanja
+
+# Tekst obavjestenja sa osnovnim podacima svakog novog izvjestaja
+function Get-EprijavaFilingSummary {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Filings
+	)
+
+	$invariant = [cultureinfo]::InvariantCulture
+	$lines = foreach ($filing in ($Filings | Sort-Object Name, { [int]$_.Year })) {
+		$figures = @(
+			if ($null -ne $filing.TotalIncome) { "prihodi $(([decimal]$filing.TotalIncome).ToString('N0', $invariant)) EUR" }
+			if ($null -ne $filing.Profit) { "neto rezultat $(([decimal]$filing.Profit).ToString('N0', $invariant)) EUR" }
+			if ($null -ne $filing.EmployeeCount) { "zaposleni $($filing.EmployeeCount)" }
+		)
+		"- $($filing.Name) ($($filing.Year)): $($figures -join ', ')"
+	}
+
+	"Novi finansijski izvjestaji ($(@($Filings).Count)):`n$($lines -join "`n")"
+}
+
+# Slanje obavjestenja o novim izvjestajima na webhook URL
+# Tekst je u poljima text (Slack) i content (Discord), a polje filings sadrzi podatke za automatsku obradu
+function Send-EprijavaWebhook {
+	param(
+		[Parameter(Mandatory)] [string]$Url,
+		[Parameter(Mandatory)] [object[]]$Filings
+	)
+
+	$text = Get-EprijavaFilingSummary -Filings $Filings
+	# Discord ogranicava poruku na 2000 karaktera
+	$content = if ($text.Length -gt 2000) { $text.Substring(0, 1997) + "..." } else { $text }
+	$payload = [ordered]@{
+		text    = $text
+		content = $content
+		filings = @(foreach ($filing in $Filings) {
+			[ordered]@{
+				company       = $filing.Name
+				pib           = $filing.Pib
+				year          = [int]$filing.Year
+				totalIncome   = $filing.TotalIncome
+				profit        = $filing.Profit
+				employeeCount = $filing.EmployeeCount
+				averagePay    = $filing.AveragePay
+			}
+		})
+	}
+
+	$body = [System.Text.Encoding]::UTF8.GetBytes((ConvertTo-Json -InputObject $payload -Depth 4))
+	Invoke-RestMethod -Uri $Url -Method 'POST' -ContentType "application/json; charset=utf-8" -Body $body -ErrorAction Stop | Out-Null
+}
+
+# Podesavanja za slanje e-maila iz konfiguracije (kljucevi SmtpServer, SmtpPort, SmtpUseSsl, SmtpUser, SmtpPassword, MailFrom i MailTo)
+# Lozinka se moze zadati i promjenljivom EPRIJAVA_SMTP_PASSWORD ili u skladistu kljuceva (SmtpPassword), kako ne bi bila u fajlu; vraca $null kada server ili primalac nisu zadati
+function Get-EprijavaMailSettings {
+	param(
+		[Parameter(Mandatory)] [hashtable]$Config,
+		[string]$To
+	)
+
+	$recipient = if ($To) { $To } else { $Config.MailTo }
+	if (-not $Config.SmtpServer -or -not $recipient) {
+		return $null
+	}
+
+	$password = if ($env:EPRIJAVA_SMTP_PASSWORD) { $env:EPRIJAVA_SMTP_PASSWORD } else { Get-EprijavaSecret -Name SmtpPassword }
+	if (-not $password) {
+		$password = $Config.SmtpPassword
+	}
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.MailSettings"
+		Server     = $Config.SmtpServer
+		Port       = if ($Config.SmtpPort) { [int]$Config.SmtpPort } else { 587 }
+		UseSsl     = if ($null -ne $Config.SmtpUseSsl) { [bool]$Config.SmtpUseSsl } else { $true }
+		User       = $Config.SmtpUser
+		Password   = $password
+		From       = if ($Config.MailFrom) { $Config.MailFrom } else { $Config.SmtpUser }
+		To         = $recipient
+	}
+}
+
+# Slanje e-maila sa izvjestajem pokretanja i fajlovima u prilogu (fajlovi koji ne postoje se preskacu)
+function Send-EprijavaMailReport {
+	param(
+		[Parameter(Mandatory)] $Settings,
+		[Parameter(Mandatory)] [string]$Subject,
+		[Parameter(Mandatory)] [string]$Body,
+		[string[]]$Attachments = @()
+	)
+
+	$message = [System.Net.Mail.MailMessage]::new()
+	$client = [System.Net.Mail.SmtpClient]::new($Settings.Server, $Settings.Port)
+	try {
+		$message.From = $Settings.From
+		foreach ($recipient in ($Settings.To -split '[,;]')) {
+			if ($recipient.Trim()) {
+				$message.To.Add($recipient.Trim())
+			}
+		}
+		$message.Subject = $Subject
+		$message.Body = $Body
+		$message.BodyEncoding = [System.Text.Encoding]::UTF8
+		foreach ($attachment in $Attachments) {
+			if ($attachment -and (Test-Path -Path $attachment -PathType Leaf)) {
+				$message.Attachments.Add([System.Net.Mail.Attachment]::new((Resolve-Path -Path $attachment).Path))
+			}
+		}
+
+		$client.EnableSsl = $Settings.UseSsl
+		if ($Settings.User) {
+			$client.Credentials = [System.Net.NetworkCredential]::new($Settings.User, $Settings.Password)
+		}
+		$client.Send($message)
+	}
+	finally {
+		$message.Dispose()
+		$client.Dispose()
+	}
+}
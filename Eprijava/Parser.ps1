@@ -0,0 +1,511 @@
+# Parsiranje HTML-a finansijskih izvjestaja
+# Redovi tabela se prepoznaju po AOP oznaci u celiji, nezavisno od stilova i razmaka u HTML-u,
+# a regularni izrazi za stari format HTML-a se koriste samo ukoliko red nije pronadjen
+# Format izvjestaja se prepoznaje prije parsiranja (Get-EprijavaReportLayout) i biljezi uz rezultat
+
+# Podaci koji se izdvajaju iz izvjestaja, prema pravilima iz rules.psd1 (ili fajla zadatog sa Import-EprijavaRules)
+$script:StatementFields = @()
+
+# Ucitavanje pravila za izdvajanje podataka (PowerShell data fajl sa nizom Fields)
+# Svako pravilo mora imati naziv i trocifrenu AOP oznaku, a kolona u izlazu se izvodi iz naziva ukoliko nije zadata
+# Podaci sa Unitless = $true (npr. broj zaposlenih) nisu novcani iznosi i ne preracunavaju se u eure
+function Import-EprijavaRules {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if (-not (Test-Path -Path $Path -PathType Leaf)) {
+		throw "Fajl sa pravilima nije pronadjen: $($Path)"
+	}
+
+	$names = @{}
+	$fields = foreach ($rule in (Import-PowerShellDataFile -Path $Path).Fields) {
+		if (-not $rule.Name -or $rule.Name -notmatch '^[A-Za-z][A-Za-z0-9]*$') {
+			throw "Pravilo bez ispravnog naziva u fajlu $($Path) (ocekivano npr. TotalIncome)"
+		}
+		if ("$($rule.Aop)" -notmatch '^\d{3}$') {
+			throw "Neispravna AOP oznaka '$($rule.Aop)' za $($rule.Name) u fajlu $($Path) (ocekivano 3 cifre)"
+		}
+		if ($names.ContainsKey($rule.Name)) {
+			throw "Pravilo $($rule.Name) se ponavlja u fajlu $($Path)"
+		}
+		$names[$rule.Name] = $true
+
+		@{
+			Name     = $rule.Name
+			Header   = if ($rule.Header) { $rule.Header } else { $rule.Name.Substring(0, 1).ToLowerInvariant() + $rule.Name.Substring(1) }
+			Aop      = "$($rule.Aop)"
+			Label    = $rule.Label
+			Pattern  = $rule.Pattern
+			Unitless = [bool]$rule.Unitless
+		}
+	}
+
+	$script:StatementFields = @($fields)
+	$script:RulesPath = (Resolve-Path -Path $Path).Path
+}
+
+# Trenutna pravila za izdvajanje podataka
+function Get-EprijavaRules {
+	$script:StatementFields
+}
+
+Import-EprijavaRules -Path "$PSScriptRoot/rules.psd1"
+
+# Naslovi dijelova izvjestaja, svaki red tabele pripada dijelu ciji naslov mu posljednji prethodi
+$script:StatementSections = [ordered]@{
+	BalanceSheet    = '(?i)bilans\s+stanja'
+	IncomeStatement = '(?i)bilans\s+uspjeha'
+	StatAnnex       = '(?i)statisti\S*\s+aneks'
+}
+
+# Jedinice iznosa prema napomeni u zaglavlju izvjestaja (npr. "u EUR", "u 000 EUR", "u hiljadama eura", "u DEM")
+# Factor pretvara iznos u eure, a njemacke marke se preracunavaju po fiksnom kursu 1 EUR = 1.95583 DEM
+$script:ReportUnits = @(
+	@{ Unit = "000 EUR"; Pattern = '(?i)\bu\s+(000|hiljadama)\s*(eur|\u20AC)'; Factor = [decimal]1000 }
+	@{ Unit = "000 DEM"; Pattern = '(?i)\bu\s+(000|hiljadama)\s*(dem\b|njema\S*\s+mar)'; Factor = [decimal]1000 / [decimal]1.95583 }
+	@{ Unit = "DEM"; Pattern = '(?i)\bu\s+(dem\b|njema\S*\s+mar)'; Factor = [decimal]1 / [decimal]1.95583 }
+	@{ Unit = "EUR"; Pattern = '(?i)\bu\s+(eur|\u20AC)'; Factor = [decimal]1 }
+)
+
+# Jedinica iznosa iz zaglavlja izvjestaja (tekst prije prve tabele), EUR ukoliko napomena nije pronadjena
+function Get-EprijavaReportUnit {
+	param(
+		[Parameter(Mandatory)] [string]$Content
+	)
+
+	$tableIndex = $Content.IndexOf("<table", [StringComparison]::OrdinalIgnoreCase)
+	$header = if ($tableIndex -ge 0) { $Content.Substring(0, $tableIndex) } else { $Content }
+	$header = ConvertTo-EprijavaLabelKey -Text ([regex]::Replace($header, '<[^>]+>', ' '))
+
+	foreach ($unit in $script:ReportUnits) {
+		if ($header -match $unit.Pattern) {
+			return [PSCustomObject]@{ Unit = $unit.Unit; Factor = $unit.Factor; Detected = $true }
+		}
+	}
+
+	[PSCustomObject]@{ Unit = "EUR"; Factor = [decimal]1; Detected = $false }
+}
+
+# Iznos u eurima (zaokruzen na cente) za iznos u jedinici izvjestaja, $null ostaje $null
+function ConvertTo-EprijavaEuro {
+	param(
+		$Value,
+		[Parameter(Mandatory)] [decimal]$Factor
+	)
+
+	if ($null -eq $Value -or $Factor -eq 1) {
+		return $Value
+	}
+
+	ConvertTo-EprijavaMoney -Value ([decimal]$Value * $Factor)
+}
+
+# HTML5 entiteti za slova sa dijakritikom koje WebUtility.HtmlDecode ne prepoznaje
+$script:LetterEntities = @{
+	"&ccaron;" = [string][char]0x010D
+	"&Ccaron;" = [string][char]0x010C
+	"&cacute;" = [string][char]0x0107
+	"&Cacute;" = [string][char]0x0106
+	"&scaron;" = [string][char]0x0161
+	"&Scaron;" = [string][char]0x0160
+	"&zcaron;" = [string][char]0x017E
+	"&Zcaron;" = [string][char]0x017D
+	"&dstrok;" = [string][char]0x0111
+	"&Dstrok;" = [string][char]0x0110
+}
+
+# Tekst iz HTML-a sa dekodiranim entitetima (i HTML5 entitetima za c, s, z sa dijakritikom) u Unicode obliku NFC
+# UTF-8 tekst koji je procitan kao Latin-1 (npr. "Prosje" + "\u00C4\u008D" + "an") se vraca u ispravna slova
+function ConvertFrom-EprijavaHtmlText {
+	param(
+		[AllowEmptyString()] [string]$Text
+	)
+
+	foreach ($entity in $script:LetterEntities.GetEnumerator()) {
+		$Text = $Text.Replace($entity.Key, $entity.Value)
+	}
+	$Text = [System.Net.WebUtility]::HtmlDecode($Text)
+
+	if ($Text -match '[\u00C2-\u00C5][\u0080-\u00BF]') {
+		$repaired = [System.Text.Encoding]::UTF8.GetString([System.Text.Encoding]::GetEncoding(28591).GetBytes($Text))
+		if ($repaired -notmatch '\uFFFD') {
+			$Text = $repaired
+		}
+	}
+
+	$Text.Normalize([System.Text.NormalizationForm]::FormC)
+}
+
+# Tekst za poredjenje naziva pozicija: bez dijakritike (c, s, z, dj), sa jednim razmakom izmedju rijeci
+# Ovako se "Prosje&ccaron;an", "Prosje\u010Dan" i "Prosjecan" prepoznaju istim pravilom
+function ConvertTo-EprijavaLabelKey {
+	param(
+		[AllowEmptyString()] [string]$Text
+	)
+
+	$decomposed = (ConvertFrom-EprijavaHtmlText -Text $Text).Normalize([System.Text.NormalizationForm]::FormD)
+	$builder = [System.Text.StringBuilder]::new($decomposed.Length)
+	foreach ($char in $decomposed.ToCharArray()) {
+		if ([System.Globalization.CharUnicodeInfo]::GetUnicodeCategory($char) -ne [System.Globalization.UnicodeCategory]::NonSpacingMark) {
+			[void]$builder.Append($char)
+		}
+	}
+
+	$key = $builder.ToString().Replace([string][char]0x0111, "dj").Replace([string][char]0x0110, "Dj")
+	[regex]::Replace($key, '\s+', ' ').Trim()
+}
+
+# Pretvaranje teksta celije u broj ([decimal]), $null ukoliko celija nije broj
+# Negativne vrijednosti (gubitak) mogu biti napisane sa minusom ili u zagradi, npr. -12345 ili (12345),
+# hiljade mogu biti odvojene tackom, razmakom, apostrofom ili zarezom (1.234.567, 1 234 567, 1'234'567, 1,234,567),
+# a centi su odvojeni zarezom ili tackom sa jednom ili dvije decimale (1.234,56, 1234567,89 ili 1234.56)
+# Oznaka valute uz iznos (EUR, \u20AC) se zanemaruje
+function ConvertFrom-EprijavaNumber {
+	param(
+		[string]$Text
+	)
+
+	$value = [regex]::Replace($Text, '[\s\u00A0]', '') -replace '[\u2212\u2013]', '-'
+	$value = $value -replace '(?i)^(eur|\u20AC)|(eur|\u20AC)$', ''
+	if ($value -match "^[-+(]?\d{1,3}('\d{3})+") {
+		$value = $value.Replace("'", "")
+	}
+
+	$negative = $false
+	if ($value -match '^\((?<inner>.*)\)$') {
+		$negative = $true
+		$value = $Matches.inner
+	}
+	if ($value -match '^(?<sign>[-+])(?<digits>.*)$') {
+		$negative = $negative -or $Matches.sign -eq '-'
+		$value = $Matches.digits
+	}
+	$cents = ""
+	if ($value -match '^(?<whole>[\d.]+),(?<cents>\d{1,2})$' -or $value -match '^(?<whole>[\d,]+)\.(?<cents>\d{1,2})$') {
+		$value = $Matches.whole
+		$cents = $Matches.cents
+	}
+	if ($value -match '^\d{1,3}(\.\d{3})+$' -or $value -match '^\d{1,3}(,\d{3})+$') {
+		$value = $value -replace '[.,]', ''
+	}
+
+	if ($value -notmatch '^\d+$') {
+		return $null
+	}
+
+	$number = [decimal]::Parse($(if ($cents) { "$($value).$($cents)" } else { $value }), [cultureinfo]::InvariantCulture)
+	if ($negative) { -$number } else { $number }
+}
+
+# Izdvajanje redova svih tabela iz HTML-a, tekst svake celije je bez tagova, entiteta i suvisnih razmaka
+# Key je tekst reda bez dijakritike (ConvertTo-EprijavaLabelKey), za poredjenje sa nazivima iz pravila
+# Svaki red nosi i naziv dijela izvjestaja (BalanceSheet, IncomeStatement, StatAnnex) kojem pripada
+function Get-EprijavaTableRows {
+	param(
+		[Parameter(Mandatory)] [string]$Content
+	)
+
+	$headings = @(foreach ($section in $script:StatementSections.GetEnumerator()) {
+		foreach ($heading in [regex]::Matches($Content, $section.Value)) {
+			[PSCustomObject]@{ Index = $heading.Index; Section = $section.Key }
+		}
+	}) | Sort-Object Index
+
+	foreach ($row in [regex]::Matches($Content, '(?is)<tr\b[^>]*>(.*?)</tr>')) {
+		$cells = @(foreach ($cell in [regex]::Matches($row.Groups[1].Value, '(?is)<t[dh]\b[^>]*>(.*?)</t[dh]>')) {
+			$text = [regex]::Replace($cell.Groups[1].Value, '<[^>]+>', ' ')
+			$text = ConvertFrom-EprijavaHtmlText -Text $text
+			[regex]::Replace($text, '\s+', ' ').Trim()
+		})
+
+		if ($cells.Count -gt 0) {
+			$section = $headings | Where-Object { $_.Index -lt $row.Index } | Select-Object -Last 1
+
+			[PSCustomObject]@{
+				Section = $section.Section
+				Cells   = $cells
+				Text    = $cells -join " "
+				Key     = ConvertTo-EprijavaLabelKey -Text ($cells -join " ")
+			}
+		}
+	}
+}
+
+# Vrijednost tekuce godine iz reda sa zadatom AOP oznakom, $null ukoliko red ili vrijednost nisu pronadjeni
+# Kolone u redu su: naziv pozicije, AOP oznaka, napomena, tekuca godina i (opciono) prethodna godina
+function Get-EprijavaAopValue {
+	param(
+		[Parameter(Mandatory)] [object[]]$Rows,
+		[Parameter(Mandatory)] [string]$Aop,
+		[string]$Label
+	)
+
+	if ($Label) {
+		$Label = ConvertTo-EprijavaLabelKey -Text $Label
+	}
+
+	foreach ($row in $Rows) {
+		$aopIndex = [array]::IndexOf($row.Cells, $Aop)
+		if ($aopIndex -lt 0) {
+			continue
+		}
+		if ($Label -and $row.Key -notmatch $Label) {
+			continue
+		}
+
+		$valueIndex = [math]::Min($aopIndex + 2, $row.Cells.Count - 1)
+		$value = ConvertFrom-EprijavaNumber -Text $row.Cells[$valueIndex]
+		if ($null -ne $value) {
+			return $value
+		}
+	}
+
+	$null
+}
+
+# Sve pozicije (AOP oznaka, opis, tekuca i prethodna godina) iz redova jednog dijela izvjestaja
+function Get-EprijavaAopItems {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Rows
+	)
+
+	foreach ($row in $Rows) {
+		$aopIndex = -1
+		for ($i = 1; $i -lt $row.Cells.Count; $i++) {
+			if ($row.Cells[$i] -match '^\d{3}$') {
+				$aopIndex = $i
+				break
+			}
+		}
+		if ($aopIndex -lt 0) {
+			continue
+		}
+
+		[PSCustomObject]@{
+			Aop           = $row.Cells[$aopIndex]
+			Description   = ($row.Cells[0..($aopIndex - 1)] -join " ").Trim()
+			Value         = ConvertFrom-EprijavaNumber -Text $row.Cells[$aopIndex + 2]
+			PreviousValue = ConvertFrom-EprijavaNumber -Text $row.Cells[$aopIndex + 3]
+		}
+	}
+}
+
+# Bilans uspjeha sa svim pozicijama iz izvjestaja
+# Ukoliko naslov bilansa nije pronadjen, uzimaju se redovi sa AOP oznakama 201-299
+function Get-EprijavaIncomeStatement {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Rows
+	)
+
+	$sectionRows = @($Rows | Where-Object { $_.Section -eq "IncomeStatement" })
+	$items = @(Get-EprijavaAopItems -Rows $sectionRows)
+	if ($items.Count -eq 0) {
+		$items = @(Get-EprijavaAopItems -Rows $Rows | Where-Object { [int]$_.Aop -ge 201 -and [int]$_.Aop -le 299 })
+	}
+
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.IncomeStatement"
+		Items      = $items
+	}
+}
+
+# Provjera zbirnih pozicija: pozicija ciji naziv sadrzi formulu, npr. "Neto sveobuhvatni rezultat (248+259)" ili "(202 do 206)",
+# mora biti jednaka zbiru navedenih pozicija (dozvoljena razlika zbog zaokruzivanja je 1)
+# Formula sa pozicijom koje nema u izvjestaju se preskace, a u opsegu "do" se pozicije koje nedostaju racunaju kao 0
+function Test-EprijavaStatementTotals {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Items,
+		[decimal]$Tolerance = 1
+	)
+
+	$byAop = @{}
+	foreach ($item in $Items) {
+		if (-not $byAop.ContainsKey($item.Aop)) {
+			$byAop[$item.Aop] = $item
+		}
+	}
+
+	foreach ($item in $Items) {
+		if ($null -eq $item.Value) {
+			continue
+		}
+
+		$expected = [decimal]0
+		if ($item.Description -match '\((od\s+)?(?<from>\d{3})\s+do\s+(?<to>\d{3})\)') {
+			foreach ($aop in ([int]$Matches.from)..([int]$Matches.to)) {
+				$part = $byAop["{0:D3}" -f $aop]
+				if ($part -and $null -ne $part.Value) {
+					$expected += $part.Value
+				}
+			}
+		}
+		elseif ($item.Description -match '\((?<formula>\d{3}(\s*[+-]\s*\d{3})+)\)') {
+			$complete = $true
+			foreach ($term in [regex]::Matches($Matches.formula, '(?<sign>^|[+-])\s*(?<aop>\d{3})')) {
+				$part = $byAop[$term.Groups['aop'].Value]
+				if (-not $part) {
+					$complete = $false
+					break
+				}
+				$value = if ($null -eq $part.Value) { [decimal]0 } else { [decimal]$part.Value }
+				$expected += if ($term.Groups['sign'].Value -eq "-") { -$value } else { $value }
+			}
+			if (-not $complete) {
+				continue
+			}
+		}
+		else {
+			continue
+		}
+
+		if ([math]::Abs($item.Value - $expected) -gt $Tolerance) {
+			[PSCustomObject]@{
+				PSTypeName  = "Eprijava.TotalMismatch"
+				Aop         = $item.Aop
+				Description = $item.Description
+				Value       = $item.Value
+				Expected    = $expected
+			}
+		}
+	}
+}
+
+# Pretraga pojedinacnog podatka regularnim izrazom, $null ukoliko podatak nije pronadjen
+function Get-EprijavaHtmlValue {
+	param(
+		[string]$Content,
+		[string]$Pattern
+	)
+
+	$result = [regex]::Matches($Content, $Pattern)
+	if ($result.Count -gt 0) {
+		return ConvertFrom-EprijavaNumber -Text ([System.Net.WebUtility]::HtmlDecode($result[0].Groups['value'].Value))
+	}
+
+	$null
+}
+
+# Prepoznavanje formata izvjestaja na osnovu redova tabela:
+# v2 - tabele sa AOP oznakama i naslovima dijelova (bilans stanja, bilans uspjeha, statisticki aneks)
+# v1 - tabele sa AOP oznakama bez naslova dijelova, pozicije se traze u svim tabelama
+# legacy - bez prepoznatljivih AOP redova, podaci se izdvajaju samo regularnim izrazima iz pravila
+function Get-EprijavaReportLayout {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Rows
+	)
+
+	if (@(Get-EprijavaAopItems -Rows $Rows).Count -eq 0) {
+		return "legacy"
+	}
+	if (@($Rows | Where-Object { $_.Section }).Count -gt 0) {
+		return "v2"
+	}
+	"v1"
+}
+
+# Izdvajanje podataka iz HTML-a finansijskog izvjestaja
+function ConvertFrom-EprijavaStatementHtml {
+	param(
+		[Parameter(Mandatory)] [string]$Content
+	)
+
+	$rows = @(Get-EprijavaTableRows -Content $Content)
+	$layout = Get-EprijavaReportLayout -Rows $rows
+	$unit = Get-EprijavaReportUnit -Content $Content
+
+	ConvertFrom-EprijavaStatementRows -Rows $rows -Layout $layout -Unit $unit -Content $Content -AnySection:($layout -eq "v1")
+}
+
+# Izdvajanje podataka iz redova izvjestaja (tabele HTML-a ili redovi teksta PDF-a), Layout se biljezi uz rezultat
+# Uz Content (HTML) se podaci koji nisu pronadjeni po AOP oznaci traze i regularnim izrazima iz pravila, a u formatu legacy samo tako
+# Novcani iznosi se pretvaraju u eure prema jedinici iz zaglavlja izvjestaja, koja se biljezi u Unit
+# Zbirne pozicije koje nisu jednake zbiru svojih dijelova se navode u TotalMismatches
+# Podatak koji nije pronadjen ostaje $null (razlikuje se od stvarne nule) i navodi se u MissingFields,
+# a ParseStatus je "ok" ili "missing: <polja>"
+function ConvertFrom-EprijavaStatementRows {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Rows,
+		[Parameter(Mandatory)] [string]$Layout,
+		[Parameter(Mandatory)] $Unit,
+		[string]$Content = "",
+		[switch]$AnySection
+	)
+
+	$values = @{}
+	$contentKey = $null
+	foreach ($field in $script:StatementFields) {
+		$value = $null
+		if ($Layout -ne "legacy") {
+			$value = Get-EprijavaAopValue -Rows $Rows -Aop $field.Aop -Label $field.Label
+		}
+		if ($null -eq $value -and $field.Pattern -and $Content) {
+			$value = Get-EprijavaHtmlValue -Content $Content -Pattern $field.Pattern
+		}
+		# Regex iz pravila se ponovo primjenjuje na HTML bez dijakritike i entiteta (npr. "trosk" umjesto "tro&scaron;k")
+		if ($null -eq $value -and $field.Pattern -and $Content) {
+			if ($null -eq $contentKey) {
+				$contentKey = ConvertTo-EprijavaLabelKey -Text $Content
+			}
+			$value = Get-EprijavaHtmlValue -Content $contentKey -Pattern (ConvertTo-EprijavaLabelKey -Text $field.Pattern)
+		}
+		$values[$field.Name] = if ($field.Unitless) { $value } else { ConvertTo-EprijavaEuro -Value $value -Factor $Unit.Factor }
+	}
+
+	# Kalkulacija averagePay (prosjecna mjesecna neto zarada) i averageGrossPay prema modelu zarade (Set-EprijavaSalaryModel)
+	$averagePay = Get-EprijavaAveragePay -NetPayCosts $values.NetPayCosts -EmployeeCount $values.EmployeeCount
+
+	$incomeStatement = Get-EprijavaIncomeStatement -Rows $Rows
+	$missing = @($script:StatementFields.Name | Where-Object { $null -eq $values[$_] })
+
+	# Ostali podaci se izdvajaju registrovanim ekstraktorima (Register-EprijavaFieldExtractor): ugradjeni ekstraktori bilansa stanja
+	# i statistickog aneksa (folder Extractors) upisuju svoje dijelove izvjestaja u kontekst, za zbirne pozicije i naredne ekstraktore
+	$context = [PSCustomObject]@{
+		Rows            = $Rows
+		Layout          = $Layout
+		Unit            = $Unit
+		Content         = $Content
+		AnySection      = [bool]$AnySection
+		IncomeStatement = $incomeStatement
+		BalanceSheet    = $null
+		StatAnnex       = $null
+		Fields          = $values
+	}
+	$extracted = Invoke-EprijavaFieldExtractors -Context $context
+	foreach ($entry in $extracted.Values.GetEnumerator()) {
+		$values[$entry.Key] = $entry.Value
+	}
+	$missing = @($missing + $extracted.Missing)
+	$balanceSheet = $context.BalanceSheet
+	$statAnnex = $context.StatAnnex
+
+	# Zbirne pozicije se provjeravaju prije preracunavanja u eure, sa iznosima kako su upisani u izvjestaju
+	$balanceItems = @(if ($balanceSheet) { $balanceSheet.Items })
+	$mismatches = @(@(Test-EprijavaStatementTotals -Items $balanceItems) + @(Test-EprijavaStatementTotals -Items @($incomeStatement.Items)))
+
+	# Pozicije bilansa u eurima (statisticki aneks sadrzi uglavnom brojeve osoba, pa se ne preracunava)
+	if ($Unit.Factor -ne 1) {
+		foreach ($item in $balanceItems + @($incomeStatement.Items)) {
+			$item.Value = ConvertTo-EprijavaEuro -Value $item.Value -Factor $Unit.Factor
+			$item.PreviousValue = ConvertTo-EprijavaEuro -Value $item.PreviousValue -Factor $Unit.Factor
+		}
+	}
+
+	[PSCustomObject]@{
+		TotalIncome     = $values.TotalIncome
+		Profit          = $values.Profit
+		EmployeeCount   = $values.EmployeeCount
+		NetPayCosts     = $values.NetPayCosts
+		AveragePay      = $averagePay.Net
+		AverageGrossPay = $averagePay.Gross
+		IncomeStatement = $incomeStatement
+		BalanceSheet    = $balanceSheet
+		StatAnnex       = $statAnnex
+		Fields          = $values
+		Layout          = $Layout
+		Unit            = $Unit.Unit
+		MissingFields   = $missing
+		TotalMismatches = $mismatches
+		ParseStatus     = if ($missing.Count -eq 0) { "ok" } else { "missing: $($missing -join ", ")" }
+	}
+}
@@ -0,0 +1,74 @@
+# Nazivi fajlova i foldera izvedeni iz naziva kompanija, ispravni na Windows-u i na ostalim sistemima
+# Pravila Windows-a se primjenjuju uvijek, pa isti izlaz moze da se kopira izmedju sistema
+
+# Znakovi koji nisu dozvoljeni u imenu fajla na Windows-u (uz kontrolne znakove)
+$script:InvalidFileNamePattern = '[<>:"/\\|?*\x00-\x1F]'
+
+# Imena uredjaja koja Windows ne dozvoljava kao ime fajla, ni sa ekstenzijom (npr. CON.txt)
+$script:ReservedFileNames = @("CON", "PRN", "AUX", "NUL") + @(1..9 | ForEach-Object { "COM$($_)"; "LPT$($_)" })
+
+# Najveca duzina naziva, da putanja fajla u folderu ostane ispod ogranicenja od 260 znakova
+$script:MaxFileNameLength = 100
+
+# Naziv bez nedozvoljenih znakova (zamjenjuju se sa _), bez tacaka i razmaka na kraju i bez imena uredjaja Windows-a
+# Prazan naziv (npr. samo tacke) postaje _
+function ConvertTo-EprijavaSafeFileName {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Name
+	)
+
+	$safe = ($Name -replace $script:InvalidFileNamePattern, "_") -replace '\s+', " "
+	if ($safe.Length -gt $script:MaxFileNameLength) {
+		$safe = $safe.Substring(0, $script:MaxFileNameLength)
+	}
+	$safe = $safe.Trim(" .")
+	if ($safe -eq "") {
+		return "_"
+	}
+	if ($script:ReservedFileNames -contains ($safe -split '\.')[0]) {
+		$safe = "_$($safe)"
+	}
+
+	$safe
+}
+
+# Naziv foldera jedne kompanije: naziv kompanije (PIB za redove bez naziva) kao bezbjedan naziv fajla
+function Get-EprijavaCompanyFolderName {
+	param(
+		[Parameter(Mandatory)] $Result
+	)
+
+	$name = if ($Result.Name) { "$($Result.Name)" } else { "$($Result.Pib)" }
+	ConvertTo-EprijavaSafeFileName -Name $name
+}
+
+# Nazivi foldera svih kompanija, po kljucu kompanije (Get-EprijavaCompanyKey)
+# Kompanije ciji se nazivi poklapaju (bez razlike izmedju velikih i malih slova, kao na Windows-u) dobijaju PIB na kraju naziva
+function Get-EprijavaCompanyFolderNames {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	$names = @{}
+	foreach ($company in ($Results | Group-Object { Get-EprijavaCompanyKey -Result $_ })) {
+		$latest = $company.Group | Sort-Object { [int]$_.Year }, { [int]$_.Version } | Select-Object -Last 1
+		$names[$company.Name] = [PSCustomObject]@{
+			Folder = Get-EprijavaCompanyFolderName -Result $latest
+			Pib    = $latest.Pib
+		}
+	}
+
+	$folders = @{}
+	foreach ($collision in ($names.GetEnumerator() | Group-Object { $_.Value.Folder.ToLowerInvariant() })) {
+		foreach ($entry in $collision.Group) {
+			$folder = $entry.Value.Folder
+			if ($collision.Count -gt 1 -and $entry.Value.Pib) {
+				$suffix = "-$($entry.Value.Pib)"
+				$folder = "$($folder.Substring(0, [math]::Min($folder.Length, $script:MaxFileNameLength - $suffix.Length)).TrimEnd(" ."))$($suffix)"
+			}
+			$folders[$entry.Key] = $folder
+		}
+	}
+
+	$folders
+}
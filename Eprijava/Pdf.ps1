@@ -0,0 +1,278 @@
+# Izdvajanje podataka iz PDF verzije finansijskog izvjestaja (sacuvane sa -Pdf), kao rezerva kada se HTML ne moze parsirati
+# Tekst se izdvaja alatom pdftotext (poppler) ukoliko je dostupan, a inace ugradjenim citanjem tekstualnih operatora PDF-a
+
+# Kodiranje teksta standardnih fontova (WinAnsiEncoding), ISO-8859-1 ukoliko kodna strana 1252 nije dostupna
+$script:PdfTextEncoding = try { [System.Text.Encoding]::GetEncoding(1252) } catch { [System.Text.Encoding]::GetEncoding(28591) }
+
+# Tekst PDF fajla, red po red (kolone u istom redu su odvojene sa najmanje dva razmaka)
+function Get-EprijavaPdfText {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$pdftotext = Get-Command -Name pdftotext -CommandType Application -ErrorAction SilentlyContinue | Select-Object -First 1
+	if ($pdftotext) {
+		$textPath = [IO.Path]::GetTempFileName()
+		try {
+			& $pdftotext.Source -layout -enc UTF-8 $Path $textPath 2>$null
+			if ($LASTEXITCODE -eq 0) {
+				return [IO.File]::ReadAllText($textPath, [System.Text.Encoding]::UTF8)
+			}
+		}
+		finally {
+			Remove-Item -Path $textPath -Force -ErrorAction SilentlyContinue
+		}
+	}
+
+	ConvertFrom-EprijavaPdfContent -Bytes ([IO.File]::ReadAllBytes($Path))
+}
+
+# Tekst iz sadrzaja stranica PDF-a (operatori Tj, TJ, ' i "), nekomprimovanih ili komprimovanih sa FlateDecode
+# Tekst na istoj visini stranice je jedan red, a pomjeranje unutar reda (Td, Tm) odvaja kolone sa dva razmaka
+# Fontovi sa sopstvenim kodiranjem (npr. Identity-H bez ToUnicode tabele) ne daju citljiv tekst, za njih je potreban pdftotext
+function ConvertFrom-EprijavaPdfContent {
+	param(
+		[Parameter(Mandatory)] [byte[]]$Bytes
+	)
+
+	$latin1 = [System.Text.Encoding]::GetEncoding(28591)
+	$raw = $latin1.GetString($Bytes)
+	$lines = New-Object System.Collections.Generic.List[string]
+	$line = New-Object System.Text.StringBuilder
+	$lineY = 0
+	$y = 0
+	$leading = 0
+	$moved = $false
+
+	foreach ($stream in [regex]::Matches($raw, '(?s)\bobj\s*(?<dict><<(?:(?!endobj).)*?>>)\s*stream\r?\n(?<data>.*?)\r?\n?endstream')) {
+		$dict = $stream.Groups["dict"].Value
+		if ($dict -match '/(Subtype\s*/Image|Type\s*/(XObject|XRef|ObjStm|Metadata)|Length1)\b') {
+			continue
+		}
+
+		# Duzina iz recnika (kada nije referenca na drugi objekat) odredjuje kraj sadrzaja koji se zavrsava bajtom \r ili \n
+		$content = $stream.Groups["data"].Value
+		$dataIndex = $stream.Groups["data"].Index
+		if ($dict -match '/Length\s+(?<length>\d+)\b(?!\s+\d+\s+R)' -and $dataIndex + [int]$Matches.length -le $raw.Length) {
+			$content = $raw.Substring($dataIndex, [int]$Matches.length)
+		}
+		if ($dict -match '/FlateDecode') {
+			$data = $latin1.GetBytes($content)
+			try {
+				# Prva dva bajta su zlib zaglavlje koje DeflateStream ne ocekuje
+				$compressed = New-Object IO.MemoryStream(, $data)
+				$compressed.Position = 2
+				$deflate = New-Object IO.Compression.DeflateStream($compressed, [IO.Compression.CompressionMode]::Decompress)
+				$decompressed = New-Object IO.MemoryStream
+				$deflate.CopyTo($decompressed)
+				$content = $latin1.GetString($decompressed.ToArray())
+			}
+			catch {
+				continue
+			}
+		}
+		elseif ($dict -match '/Filter') {
+			continue
+		}
+		if ($content -notmatch '\bBT\b') {
+			continue
+		}
+
+		$operands = New-Object System.Collections.Generic.List[decimal]
+		$text = New-Object System.Text.StringBuilder
+		$inArray = $false
+		$tokens = '(?s)\((?<str>(?:\\.|[^\\)])*)\)|<(?<hex>[0-9A-Fa-f\s]*)>|(?<![\w/])(?<op>T[jJ*dDmL]|BT|''|")(?!\w)|(?<![\w/])(?<num>-?(\d+\.?\d*|\.\d+))|(?<open>\[)|(?<close>\])'
+		foreach ($token in [regex]::Matches($content, $tokens)) {
+			if ($token.Groups["str"].Success) {
+				$null = $text.Append((ConvertFrom-EprijavaPdfString -Bytes $latin1.GetBytes((ConvertFrom-EprijavaPdfEscapes -Text $token.Groups["str"].Value))))
+			}
+			elseif ($token.Groups["hex"].Success) {
+				$hex = $token.Groups["hex"].Value -replace '\s', ''
+				if ($hex.Length % 2) {
+					$hex += "0"
+				}
+				$hexBytes = [byte[]]@(for ($i = 0; $i -lt $hex.Length; $i += 2) { [Convert]::ToByte($hex.Substring($i, 2), 16) })
+				$null = $text.Append((ConvertFrom-EprijavaPdfString -Bytes $hexBytes))
+			}
+			elseif ($token.Groups["num"].Success) {
+				$number = [decimal]::Parse($token.Groups["num"].Value, [cultureinfo]::InvariantCulture)
+				# Veliki razmak izmedju dijelova teksta u nizu operatora TJ je razmak izmedju rijeci
+				if ($inArray -and $number -lt -200) {
+					$null = $text.Append(" ")
+				}
+				elseif (-not $inArray) {
+					$operands.Add($number)
+				}
+			}
+			elseif ($token.Groups["open"].Success) {
+				$inArray = $true
+			}
+			elseif ($token.Groups["close"].Success) {
+				$inArray = $false
+			}
+			else {
+				$op = $token.Groups["op"].Value
+				switch -CaseSensitive ($op) {
+					"BT" { $y = 0; $moved = $true }
+					"TL" { if ($operands.Count -ge 1) { $leading = $operands[$operands.Count - 1] } }
+					"Td" { if ($operands.Count -ge 2) { $y += $operands[$operands.Count - 1] }; $moved = $true }
+					"TD" { if ($operands.Count -ge 2) { $leading = -$operands[$operands.Count - 1]; $y += $operands[$operands.Count - 1] }; $moved = $true }
+					"Tm" { if ($operands.Count -ge 6) { $y = $operands[$operands.Count - 1] }; $moved = $true }
+					"T*" { $y -= $(if ($leading) { $leading } else { 12 }); $moved = $true }
+				}
+				if ($op -in @("'", '"')) {
+					$y -= $(if ($leading) { $leading } else { 12 })
+					$moved = $true
+				}
+
+				if ($op -in @("Tj", "TJ", "'", '"') -and $text.Length -gt 0) {
+					if ($line.Length -gt 0 -and [math]::Abs($y - $lineY) -gt 1) {
+						$lines.Add($line.ToString())
+						$null = $line.Clear()
+					}
+					elseif ($line.Length -gt 0 -and $moved) {
+						$null = $line.Append("  ")
+					}
+					if ($line.Length -eq 0) {
+						$lineY = $y
+					}
+					$null = $line.Append($text.ToString())
+					$moved = $false
+				}
+				$null = $text.Clear()
+				$operands.Clear()
+			}
+		}
+	}
+	if ($line.Length -gt 0) {
+		$lines.Add($line.ToString())
+	}
+
+	$lines -join "`n"
+}
+
+# Niz PDF-a bez escape sekvenci (\n, \(, \\, oktalni kodovi \ddd i nastavak u sljedecem redu)
+function ConvertFrom-EprijavaPdfEscapes {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Text
+	)
+
+	[regex]::Replace($Text, '\\([nrtbf()\\]|[0-7]{1,3}|\r?\n)', [System.Text.RegularExpressions.MatchEvaluator] {
+		param($match)
+		$escape = $match.Groups[1].Value
+		switch -CaseSensitive -Regex ($escape) {
+			'^n$' { "`n"; break }
+			'^r$' { "`r"; break }
+			'^t$' { "`t"; break }
+			'^b$' { "`b"; break }
+			'^f$' { "`f"; break }
+			'^[0-7]+$' { [string][char][Convert]::ToInt32($escape, 8); break }
+			'^\r?\n$' { ""; break }
+			default { $escape }
+		}
+	})
+}
+
+# Tekst niza PDF-a: UTF-16BE sa oznakom FE FF ili kodiranje standardnih fontova
+function ConvertFrom-EprijavaPdfString {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [byte[]]$Bytes
+	)
+
+	if ($Bytes.Length -ge 2 -and $Bytes[0] -eq 0xFE -and $Bytes[1] -eq 0xFF) {
+		return [System.Text.Encoding]::BigEndianUnicode.GetString($Bytes, 2, $Bytes.Length - 2)
+	}
+
+	$script:PdfTextEncoding.GetString($Bytes)
+}
+
+# Redovi izvjestaja iz teksta PDF-a, u istom obliku kao redovi tabela HTML-a (Get-EprijavaTableRows)
+# AOP oznaka je prvi trocifreni broj iza kojeg slijede samo iznosi (napomena, tekuca i prethodna godina)
+# Kolone se razdvajaju sa dva ili vise razmaka kada ih tekst sadrzi, a inace svakim razmakom
+# Naslovi dijelova izvjestaja (bilans stanja, bilans uspjeha, statisticki aneks) odredjuju Section narednih redova
+function ConvertTo-EprijavaPdfRows {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Text
+	)
+
+	$section = $null
+	foreach ($line in ($Text -split '\r?\n')) {
+		$line = $line.Trim()
+		if (-not $line) {
+			continue
+		}
+
+		$columns = if ($line -match '\S\s{2,}\S') { @($line -split '\s{2,}') } else { @($line -split '\s+') }
+		$aopIndex = -1
+		for ($i = 1; $i -lt $columns.Count -and $aopIndex -lt 0; $i++) {
+			if ($columns[$i] -notmatch '^\d{3}$' -or $columns.Count - $i - 1 -gt 3) {
+				continue
+			}
+			$amounts = @($columns | Select-Object -Skip ($i + 1) | Where-Object { $_ -ne "-" -and $null -eq (ConvertFrom-EprijavaNumber -Text $_) })
+			if ($amounts.Count -eq 0) {
+				$aopIndex = $i
+			}
+		}
+
+		if ($aopIndex -lt 0) {
+			$key = ConvertTo-EprijavaLabelKey -Text $line
+			foreach ($heading in $script:StatementSections.GetEnumerator()) {
+				if ($key -match $heading.Value) {
+					$section = $heading.Key
+				}
+			}
+			continue
+		}
+
+		$label = ($columns[0..($aopIndex - 1)] -join " ").Trim()
+		$values = @($columns | Select-Object -Skip ($aopIndex + 1))
+		if ($values.Count -lt 3) {
+			$values = @("") + $values
+		}
+		$cells = @($label, $columns[$aopIndex]) + $values
+
+		[PSCustomObject]@{
+			Section = $section
+			Cells   = $cells
+			Text    = $cells -join " "
+			Key     = ConvertTo-EprijavaLabelKey -Text ($cells -join " ")
+		}
+	}
+}
+
+# Izdvajanje podataka iz PDF verzije finansijskog izvjestaja, sa istim svojstvima kao ConvertFrom-EprijavaStatementHtml
+# Layout je "pdf", a jedinica iznosa se trazi u tekstu izvjestaja
+function ConvertFrom-EprijavaStatementPdf {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$text = Get-EprijavaPdfText -Path $Path
+	$rows = @(ConvertTo-EprijavaPdfRows -Text $text)
+	$unit = Get-EprijavaReportUnit -Content $text
+
+	ConvertFrom-EprijavaStatementRows -Rows $rows -Layout "pdf" -Unit $unit -AnySection:((Get-EprijavaReportLayout -Rows $rows) -ne "v2")
+}
+
+# Podaci izvjestaja iz PDF-a umjesto HTML-a kada u HTML-u nisu pronadjeni svi podaci, a PDF (sacuvan pored HTML-a) ih sadrzi vise
+# PDF koji se ne moze procitati ne mijenja rezultat HTML-a
+function Select-EprijavaStatementValues {
+	param(
+		[Parameter(Mandatory)] $Values,
+		[string]$PdfPath
+	)
+
+	if ($Values.MissingFields.Count -eq 0 -or -not $PdfPath -or -not (Test-Path -LiteralPath $PdfPath -PathType Leaf)) {
+		return $Values
+	}
+
+	try {
+		$pdfValues = ConvertFrom-EprijavaStatementPdf -Path $PdfPath
+	}
+	catch {
+		Write-Verbose "PDF $($PdfPath) nije procitan: $($_.Exception.Message)"
+		return $Values
+	}
+
+	if ($pdfValues.MissingFields.Count -lt $Values.MissingFields.Count) { $pdfValues } else { $Values }
+}
@@ -0,0 +1,34 @@
+# Provjera PIB-a (poreski identifikacioni broj) pravnih lica u Crnoj Gori
+# PIB ima 8 cifara, a posljednja cifra je kontrolna (modul 11 sa tezinama 8 do 2 za prvih 7 cifara)
+
+# Kontrolna cifra za prvih 7 cifara PIB-a
+function Get-EprijavaPibCheckDigit {
+	param(
+		[Parameter(Mandatory)] [ValidatePattern('^\d{7}$')] [string]$Digits
+	)
+
+	$sum = 0
+	for ($i = 0; $i -lt 7; $i++) {
+		$sum += [int]::Parse("$($Digits[$i])") * (8 - $i)
+	}
+
+	$check = 11 - ($sum % 11)
+	if ($check -ge 10) {
+		$check = 0
+	}
+
+	$check
+}
+
+# Provjera da PIB ima 8 cifara i ispravnu kontrolnu cifru
+function Test-EprijavaPib {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Pib
+	)
+
+	if ($Pib -notmatch '^\d{8}$') {
+		return $false
+	}
+
+	(Get-EprijavaPibCheckDigit -Digits $Pib.Substring(0, 7)) -eq [int]::Parse("$($Pib[7])")
+}
@@ -0,0 +1,168 @@
+# Pristojno prikupljanje: prepoznatljiv User-Agent sa adresom projekta i kontaktom, i postovanje robots.txt portala
+# Crawl-delay iz robots.txt povecava razmak izmedju zahtjeva, a zabranjene putanje portala se navode u upozorenju
+
+# Naziv programa u User-Agent zaglavlju, po kojem se program prepoznaje i u robots.txt (User-agent: eprijava-scraper)
+$script:UserAgentProduct = "eprijava-scraper"
+
+# Adresa projekta u User-Agent zaglavlju
+$script:ProjectUrl = "https://github.com/stevyhacker/scraping-eprijava"
+
+# User-Agent zaglavlje: zadato zaglavlje, ili naziv programa sa adresom projekta i kontaktom (npr. e-mail adresa)
+function Get-EprijavaUserAgent {
+	param(
+		[string]$UserAgent,
+		[string]$Contact
+	)
+
+	if ($UserAgent) {
+		return $UserAgent
+	}
+
+	$details = @("+$($script:ProjectUrl)")
+	if ($Contact) {
+		$details += $Contact
+	}
+	"$($script:UserAgentProduct) ($($details -join "; "))"
+}
+
+# Pravila iz robots.txt za ovaj program: grupa ciji se User-agent nalazi u nazivu programa, odnosno grupa "*"
+# Vraca Crawl-delay (sekunde, $null ukoliko nije zadat) i putanje iz Allow i Disallow
+function ConvertFrom-EprijavaRobotsTxt {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyString()] [string]$Content,
+		[string]$UserAgent = $script:UserAgentProduct
+	)
+
+	$groups = [System.Collections.Generic.List[object]]::new()
+	$current = $null
+	$readingAgents = $false
+	foreach ($line in ($Content -split '\r?\n')) {
+		$line = ($line -replace '#.*$', '').Trim()
+		if ($line -notmatch '^(?<key>[A-Za-z-]+)\s*:\s*(?<value>.*)$') {
+			continue
+		}
+		$key = $Matches.key.ToLowerInvariant()
+		$value = $Matches.value.Trim()
+
+		if ($key -eq "user-agent") {
+			if (-not $readingAgents) {
+				$current = @{ Agents = @(); CrawlDelay = $null; Allow = @(); Disallow = @() }
+				$groups.Add($current)
+			}
+			$current.Agents += $value.ToLowerInvariant()
+			$readingAgents = $true
+			continue
+		}
+
+		$readingAgents = $false
+		if (-not $current) {
+			continue
+		}
+		$delay = 0.0
+		switch ($key) {
+			"crawl-delay" {
+				if ([double]::TryParse($value, [System.Globalization.NumberStyles]::Float, [cultureinfo]::InvariantCulture, [ref]$delay)) {
+					$current.CrawlDelay = $delay
+				}
+			}
+			"allow" {
+				if ($value) { $current.Allow += $value }
+			}
+			"disallow" {
+				if ($value) { $current.Disallow += $value }
+			}
+		}
+	}
+
+	$agent = $UserAgent.ToLowerInvariant()
+	$selected = @($groups | Where-Object { @($_.Agents | Where-Object { $_ -ne "*" -and $agent.Contains($_) }).Count -gt 0 })
+	if ($selected.Count -eq 0) {
+		$selected = @($groups | Where-Object { $_.Agents -contains "*" })
+	}
+
+	$delays = @($selected | Where-Object { $null -ne $_.CrawlDelay } | ForEach-Object { $_.CrawlDelay })
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.RobotsRules"
+		CrawlDelay = if ($delays.Count -gt 0) { ($delays | Measure-Object -Maximum).Maximum } else { $null }
+		Allow      = @($selected | ForEach-Object { $_.Allow })
+		Disallow   = @($selected | ForEach-Object { $_.Disallow })
+	}
+}
+
+# Provjera da li robots.txt dozvoljava adresu: vazi najduze pravilo koje se poklapa sa putanjom (uz * i $), Allow ima prednost uz istu duzinu
+function Test-EprijavaRobotsAllowed {
+	param(
+		[Parameter(Mandatory)] $Rules,
+		[Parameter(Mandatory)] [string]$Url
+	)
+
+	$path = ([uri]$Url).PathAndQuery
+	$best = $null
+	$allowed = $true
+	foreach ($rule in @(@($Rules.Disallow | ForEach-Object { @{ Path = $_; Allow = $false } }) + @($Rules.Allow | ForEach-Object { @{ Path = $_; Allow = $true } }))) {
+		$pattern = "^" + ([regex]::Escape($rule.Path) -replace '\\\*', '.*' -replace '\\\$$', '$')
+		if ($path -notmatch $pattern) {
+			continue
+		}
+		if ($null -eq $best -or $rule.Path.Length -gt $best -or ($rule.Path.Length -eq $best -and $rule.Allow)) {
+			$best = $rule.Path.Length
+			$allowed = $rule.Allow
+		}
+	}
+
+	$allowed
+}
+
+# Preuzimanje robots.txt sa adrese portala (korijen servera), $null ukoliko ne postoji ili nije dostupan
+function Get-EprijavaRobotsRules {
+	param(
+		[Parameter(Mandatory)] $Client
+	)
+
+	$portal = [uri](Get-EprijavaPortalUrl)
+	$url = "$($portal.Scheme)://$($portal.Authority)/robots.txt"
+	$options = @{}
+	if ($Client.RequestOptions) {
+		$options += $Client.RequestOptions
+	}
+	$options += Get-EprijavaTimeoutOptions -Timeouts $Client.Timeouts -Json
+
+	try {
+		Wait-EprijavaRateLimit -RateLimiter $Client.RateLimiter
+		$response = Invoke-WebRequest $url -Method 'GET' -UseBasicParsing @options -ErrorAction Stop
+	}
+	catch {
+		Write-EprijavaLog -Message "robots.txt nije dostupan ($($url)), koriste se zadata ogranicenja" -Stage robots -Outcome skipped -Data @{ url = $url; status = Get-EprijavaErrorStatus -ErrorRecord $_ }
+		return $null
+	}
+
+	$content = if ($response.Content -is [byte[]]) { [System.Text.Encoding]::UTF8.GetString($response.Content) } else { "$($response.Content)" }
+	ConvertFrom-EprijavaRobotsTxt -Content $content -UserAgent (Get-EprijavaUserAgent -UserAgent $Client.RequestOptions.UserAgent)
+}
+
+# Primjena robots.txt na klijenta: Crawl-delay veci od razmaka limitera postaje novi razmak izmedju zahtjeva,
+# a za endpoint-e portala koje robots.txt zabranjuje se upisuje upozorenje; vraca procitana pravila ($null bez robots.txt)
+function Set-EprijavaRobotsPolicy {
+	param(
+		[Parameter(Mandatory)] $Client
+	)
+
+	$rules = Get-EprijavaRobotsRules -Client $Client
+	if (-not $rules) {
+		return $null
+	}
+
+	if ($null -ne $rules.CrawlDelay -and $rules.CrawlDelay -gt $Client.RateLimiter.Interval) {
+		$Client.RateLimiter.Interval = [double]$rules.CrawlDelay
+		Write-EprijavaLog -Message "robots.txt: Crawl-delay $($rules.CrawlDelay)s, razmak izmedju zahtjeva je povecan" -Stage robots -Outcome ok -Data @{ crawlDelay = $rules.CrawlDelay }
+	}
+
+	foreach ($endpoint in @("Home", "TaxPayerSearch", "CompanyDetails", "StatementList", "StatementDetails", "StatementPdf")) {
+		$url = Get-EprijavaEndpointUrl -Path (Get-EprijavaEndpoint -Name $endpoint)
+		if (-not (Test-EprijavaRobotsAllowed -Rules $rules -Url $url)) {
+			Write-EprijavaLog -Message "robots.txt ne dozvoljava pristup putanji $(([uri]$url).AbsolutePath)" -Level warning -Stage robots -Outcome disallowed -Data @{ endpoint = $endpoint; url = $url }
+		}
+	}
+
+	$rules
+}
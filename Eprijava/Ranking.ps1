@@ -0,0 +1,83 @@
+# Rang lista kompanija za jednu godinu (prihodi, dobit, broj zaposlenih, prosjecna zarada) uz promjenu mjesta u odnosu na prethodnu godinu
+
+# Pokazatelji po kojima se rangira: naziv pokazatelja i svojstvo rezultata
+$script:RankMetrics = [ordered]@{
+	revenue    = "TotalIncome"
+	profit     = "Profit"
+	employees  = "EmployeeCount"
+	averagePay = "AveragePay"
+}
+
+# Mjesta kompanija za godinu po jednom svojstvu (od najvece vrijednosti), iste vrijednosti dijele mjesto (1, 1, 3)
+# Kljuc je PIB, odnosno naziv za redove bez PIB-a; kompanije bez podatka se ne rangiraju
+function Get-EprijavaRankPositions {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[Parameter(Mandatory)] [int]$Year,
+		[Parameter(Mandatory)] [string]$Property
+	)
+
+	$ranked = @($Results |
+		Where-Object { $_.Latest -ne 0 -and [int]$_.Year -eq $Year -and $null -ne $_.$Property } |
+		Sort-Object -Property @{ Expression = { [decimal]$_.$Property }; Descending = $true }, Name)
+
+	$positions = [ordered]@{}
+	for ($i = 0; $i -lt $ranked.Count; $i++) {
+		$rank = $i + 1
+		if ($i -gt 0 -and [decimal]$ranked[$i].$Property -eq [decimal]$ranked[$i - 1].$Property) {
+			$rank = $positions[$positions.Count - 1].Rank
+		}
+		$key = if ($ranked[$i].Pib) { $ranked[$i].Pib } else { $ranked[$i].Name }
+		$positions[$key] = [PSCustomObject]@{
+			Rank   = $rank
+			Result = $ranked[$i]
+		}
+	}
+
+	$positions
+}
+
+# Rang lista za godinu po pokazatelju (revenue, profit, employees ili averagePay), samo posljednje verzije izvjestaja
+# RankChange je pozitivan kada je kompanija napredovala, a izostaje ukoliko nije rangirana u prethodnoj godini
+function Get-EprijavaRanking {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[Parameter(Mandatory)] [int]$Year,
+		[Parameter(Mandatory)] [ValidateSet("revenue", "profit", "employees", "averagePay")] [string]$By
+	)
+
+	$property = $script:RankMetrics[$By]
+	$current = Get-EprijavaRankPositions -Results $Results -Year $Year -Property $property
+	$previous = Get-EprijavaRankPositions -Results $Results -Year ($Year - 1) -Property $property
+
+	foreach ($key in $current.Keys) {
+		$position = $current[$key]
+		$before = $previous[$key]
+
+		[PSCustomObject]@{
+			PSTypeName    = "Eprijava.Rank"
+			Metric        = $By
+			Rank          = $position.Rank
+			PreviousRank  = if ($before) { $before.Rank } else { $null }
+			RankChange    = if ($before) { $before.Rank - $position.Rank } else { $null }
+			Name          = $position.Result.Name
+			Pib           = $position.Result.Pib
+			Year          = $Year
+			Value         = $position.Result.$property
+			PreviousValue = if ($before) { $before.Result.$property } else { $null }
+		}
+	}
+}
+
+# Upis rang lista svih pokazatelja u CSV fajl
+function Export-EprijavaRanking {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Ranking
+	)
+
+	Set-EprijavaResultFile -Path $Path -Columns $script:RankColumns
+	if ($Ranking.Count -gt 0) {
+		Add-Content -Path $Path -Value @($Ranking | ForEach-Object { ConvertTo-EprijavaResultLine -Result $_ -Columns $script:RankColumns })
+	}
+}
@@ -0,0 +1,36 @@
+# Ogranicavanje broja zahtjeva prema portalu
+
+# Limiter sa najvise RequestsPerSecond zahtjeva u sekundi i dodatnom nasumicnom pauzom do Jitter sekundi
+# RequestsPerSecond = 0 iskljucuje ogranicenje
+function New-EprijavaRateLimiter {
+	param(
+		[double]$RequestsPerSecond = 2,
+		[double]$Jitter = 0.5
+	)
+
+	[PSCustomObject]@{
+		PSTypeName  = "Eprijava.RateLimiter"
+		Interval    = if ($RequestsPerSecond -gt 0) { 1 / $RequestsPerSecond } else { 0 }
+		Jitter      = $Jitter
+		LastRequest = [datetime]::MinValue
+	}
+}
+
+# Cekanje dok od prethodnog zahtjeva ne protekne interval limitera (uz nasumicnu pauzu)
+function Wait-EprijavaRateLimit {
+	param(
+		[Parameter(Mandatory)] $RateLimiter
+	)
+
+	$wait = $RateLimiter.Interval
+	if ($RateLimiter.Interval -gt 0 -and $RateLimiter.Jitter -gt 0) {
+		$wait += Get-Random -Minimum 0.0 -Maximum $RateLimiter.Jitter
+	}
+
+	$elapsed = ((Get-Date) - $RateLimiter.LastRequest).TotalSeconds
+	if ($elapsed -lt $wait) {
+		Start-Sleep -Milliseconds ([int](($wait - $elapsed) * 1000))
+	}
+
+	$RateLimiter.LastRequest = Get-Date
+}
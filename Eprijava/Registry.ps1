@@ -0,0 +1,109 @@
+# Podaci iz Centralnog registra privrednih subjekata (CRPS): datum osnivanja, sifra djelatnosti, adresa i osnivaci
+# Registar nema javni API, pa se adresa stranice kompanije zadaje sablonom sa {pib} (Set-EprijavaRegistry),
+# a podaci se prepoznaju po nazivu reda u tabelama stranice, kao pozicije bilansa stanja
+
+# Sablon adrese stranice kompanije u registru, $null = bez podataka iz registra
+$script:RegistryUrl = $null
+
+# Nazivi redova stranice registra za svaki podatak (\S* pokriva slova sa dijakritikom u razlicitim kodiranjima)
+$script:RegistryFields = [ordered]@{
+	FoundedAt = '(?i)datum\s+(registracije|osnivanja|upisa)'
+	Activity  = '(?i)djelatnost'
+	Address   = '(?i)adresa|sjedi\S*te'
+	Owners    = '(?i)osniva\S*|vlasni\S*|\S*lan\S*\s+dru\S*tva'
+}
+
+# Ukljucivanje podataka iz registra: sablon adrese sa {pib}, npr. https://registar.example/Detalji?pib={pib}
+# Kolone registra se od tada dodaju kolonama rezultata (Get-EprijavaResultColumns)
+function Set-EprijavaRegistry {
+	param(
+		[Parameter(Mandatory)] [string]$Url
+	)
+
+	if ($Url -notmatch '\{pib\}') {
+		throw "Adresa registra '$($Url)' ne sadrzi {pib} (ocekivano npr. https://registar.example/Detalji?pib={pib})"
+	}
+	$script:RegistryUrl = $Url
+}
+
+# Da li su podaci iz registra ukljuceni
+function Test-EprijavaRegistryEnabled {
+	[bool]$script:RegistryUrl
+}
+
+# Podaci iz HTML stranice kompanije u registru: prvi red ciji naziv odgovara podatku, osim osnivaca koji se spajaju u "<osnivac>; <osnivac>"
+# Datum u formatu dd.MM.yyyy se pretvara u yyyy-MM-dd, a sifra djelatnosti (npr. 62.01) se izdvaja iz opisa djelatnosti
+function ConvertFrom-EprijavaRegistryHtml {
+	param(
+		[Parameter(Mandatory)] [string]$Content
+	)
+
+	$rows = @(Get-EprijavaTableRows -Content $Content | Where-Object { $_.Cells.Count -ge 2 -and $_.Cells[1] })
+	$values = @{}
+	foreach ($field in $script:RegistryFields.GetEnumerator()) {
+		$values[$field.Key] = @($rows | Where-Object { (ConvertTo-EprijavaLabelKey -Text $_.Cells[0]) -match $field.Value } | ForEach-Object { ($_.Cells[1..($_.Cells.Count - 1)] -join " ").Trim() })
+	}
+
+	$foundedAt = $values.FoundedAt | Select-Object -First 1
+	$date = [datetime]::MinValue
+	if ($foundedAt -and [datetime]::TryParseExact($foundedAt, @("d.M.yyyy", "d.M.yyyy."), [cultureinfo]::InvariantCulture, [System.Globalization.DateTimeStyles]::None, [ref]$date)) {
+		$foundedAt = $date.ToString("yyyy-MM-dd")
+	}
+
+	$activity = $values.Activity | Select-Object -First 1
+	$activityCode = $null
+	if ($activity -match '^(?<code>\d{2}\.\d{1,2})\s*[-:]?\s*(?<name>.*)$') {
+		$activityCode = $Matches.code
+		$activity = $Matches.name
+	}
+
+	[PSCustomObject]@{
+		PSTypeName   = "Eprijava.RegistryRecord"
+		FoundedAt    = $foundedAt
+		ActivityCode = $activityCode
+		Activity     = $activity
+		Address      = $values.Address | Select-Object -First 1
+		Owners       = $values.Owners -join "; "
+	}
+}
+
+# Preuzimanje stranice kompanije iz registra kroz limiter, ponavljanje i podesavanja veze klijenta (bez sesije portala ePrijava)
+function Get-EprijavaRegistryRecord {
+	param(
+		[Parameter(Mandatory)] $Client,
+		[Parameter(Mandatory)] [string]$Pib
+	)
+
+	if (-not $script:RegistryUrl) {
+		throw "Adresa registra nije zadata (Set-EprijavaRegistry)"
+	}
+
+	$url = $script:RegistryUrl.Replace("{pib}", [uri]::EscapeDataString($Pib))
+	$options = @{}
+	if ($Client.RequestOptions) {
+		$options += $Client.RequestOptions
+	}
+	$options += Get-EprijavaTimeoutOptions -Timeouts $Client.Timeouts
+	$content = Invoke-EprijavaWithRetry -RetryPolicy $Client.RetryPolicy -Url $url -ScriptBlock {
+		Wait-EprijavaRateLimit -RateLimiter $Client.RateLimiter
+		Invoke-RestMethod $url -Method 'GET' @options -ErrorAction Stop
+	}
+
+	ConvertFrom-EprijavaRegistryHtml -Content "$content"
+}
+
+# Dodavanje podataka iz registra rezultatu (prazne vrijednosti kada podaci nisu preuzeti)
+function Add-EprijavaRegistryRecord {
+	param(
+		[Parameter(Mandatory, ValueFromPipeline)] $Result,
+		$Record = $null
+	)
+
+	process {
+		foreach ($column in $script:RegistryColumns) {
+			$value = if ($Record) { $Record.($column.Property) } else { $null }
+			$Result | Add-Member -NotePropertyName $column.Property -NotePropertyValue $value -Force
+		}
+		$Result
+	}
+}
@@ -0,0 +1,213 @@
+# Izvjestaj za objavljivanje (Markdown ili staticki HTML): zbirni pregled sektora po godinama (i po oznakama, ukoliko su zadate)
+# i tabela sa godinama svake kompanije
+# Natpisi su na crnogorskom, odnosno na engleskom kada je zadat jezik en (Set-EprijavaLocale)
+
+# Kolone tabela izvjestaja: naslov kolone, svojstvo i format broja
+$script:ReportCompanyColumns = @(
+	@{ Title = "Godina"; Property = "Year"; Format = "0" }
+	@{ Title = "Ukupni prihodi"; Property = "TotalIncome"; Format = "N2" }
+	@{ Title = "Neto rezultat"; Property = "Profit"; Format = "N2" }
+	@{ Title = "Zaposleni"; Property = "EmployeeCount"; Format = "N0" }
+	@{ Title = "Prosjecna zarada"; Property = "AveragePay"; Format = "N2" }
+)
+$script:ReportSummaryColumns = @(
+	@{ Title = "Godina"; Property = "Year"; Format = "0" }
+	@{ Title = "Kompanije"; Property = "Companies"; Format = "N0" }
+	@{ Title = "Ukupni prihodi"; Property = "TotalIncome"; Format = "N2" }
+	@{ Title = "Neto rezultat"; Property = "Profit"; Format = "N2" }
+	@{ Title = "Zaposleni"; Property = "EmployeeCount"; Format = "N0" }
+	@{ Title = "Medijana prosjecne zarade"; Property = "MedianAveragePay"; Format = "N2" }
+)
+
+# Vrijednost celije tabele, prazna ukoliko podatak nedostaje
+function Format-EprijavaReportValue {
+	param(
+		$Value,
+		[Parameter(Mandatory)] [string]$Format
+	)
+
+	if ($null -eq $Value) {
+		return ""
+	}
+	([decimal]$Value).ToString($Format, [cultureinfo]::InvariantCulture)
+}
+
+# Redovi tabele (zaglavlje i vrijednosti) kao tekst, nezavisno od formata izvjestaja
+function Get-EprijavaReportTable {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Rows,
+		[Parameter(Mandatory)] [object[]]$Columns
+	)
+
+	[PSCustomObject]@{
+		Headers = @($Columns | ForEach-Object { ConvertTo-EprijavaLocalLabel -Text $_.Title })
+		Rows    = @(foreach ($row in $Rows) {
+			, @(foreach ($column in $Columns) { Format-EprijavaReportValue -Value $row.($column.Property) -Format $column.Format })
+		})
+	}
+}
+
+# Kompanije sa posljednjim verzijama izvjestaja, sortirane po nazivu
+function Get-EprijavaReportCompanies {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	$latest = @($Results | Where-Object { $_.Latest -ne 0 })
+	foreach ($company in ($latest | Group-Object { if ($_.Pib) { $_.Pib } else { $_.Name } })) {
+		$years = @($company.Group | Sort-Object { [int]$_.Year })
+		[PSCustomObject]@{
+			Name  = $years[-1].Name
+			Pib   = $years[-1].Pib
+			Years = $years
+		}
+	}
+}
+
+# Markdown tabela
+function ConvertTo-EprijavaMarkdownTable {
+	param(
+		[Parameter(Mandatory)] $Table
+	)
+
+	"| $($Table.Headers -join ' | ') |"
+	"|$((@($Table.Headers | ForEach-Object { '---' })) -join '|')|"
+	foreach ($row in $Table.Rows) {
+		"| $((@($row | ForEach-Object { $_.Replace('|', '\|') })) -join ' | ') |"
+	}
+}
+
+# Izvjestaj u Markdown formatu
+function ConvertTo-EprijavaMarkdownReport {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[string]$Title = ""
+	)
+
+	if (-not $Title) {
+		$Title = ConvertTo-EprijavaLocalLabel -Text "Finansijski izvjestaji kompanija"
+	}
+	$lines = [System.Collections.Generic.List[string]]::new()
+	$lines.Add("# $($Title)")
+	$lines.Add("")
+	$lines.Add("## $(ConvertTo-EprijavaLocalLabel -Text "Pregled sektora")")
+	$lines.Add("")
+	$summary = @(Get-EprijavaSectorSummary -Results $Results)
+	foreach ($line in (ConvertTo-EprijavaMarkdownTable -Table (Get-EprijavaReportTable -Rows $summary -Columns $script:ReportSummaryColumns))) {
+		$lines.Add($line)
+	}
+	$tagSummary = @(Get-EprijavaTagSummary -Results $Results)
+	if ($tagSummary.Count -gt 0) {
+		$lines.Add("")
+		$lines.Add("## $(ConvertTo-EprijavaLocalLabel -Text "Pregled po oznakama")")
+		foreach ($tag in ($tagSummary | Group-Object Tag)) {
+			$lines.Add("")
+			$lines.Add("### $($tag.Name)")
+			$lines.Add("")
+			foreach ($line in (ConvertTo-EprijavaMarkdownTable -Table (Get-EprijavaReportTable -Rows $tag.Group -Columns $script:ReportSummaryColumns))) {
+				$lines.Add($line)
+			}
+		}
+	}
+
+	foreach ($company in (Get-EprijavaReportCompanies -Results $Results | Sort-Object Name)) {
+		$lines.Add("")
+		$lines.Add("## $($company.Name)")
+		$lines.Add("")
+		if ($company.Pib) {
+			$lines.Add("$(ConvertTo-EprijavaLocalLabel -Text "PIB"): $($company.Pib)")
+			$lines.Add("")
+		}
+		foreach ($line in (ConvertTo-EprijavaMarkdownTable -Table (Get-EprijavaReportTable -Rows $company.Years -Columns $script:ReportCompanyColumns))) {
+			$lines.Add($line)
+		}
+	}
+
+	$lines -join "`n"
+}
+
+# HTML tabela
+function ConvertTo-EprijavaHtmlTable {
+	param(
+		[Parameter(Mandatory)] $Table
+	)
+
+	"<table>"
+	"<thead><tr>$((@($Table.Headers | ForEach-Object { "<th>$([System.Net.WebUtility]::HtmlEncode($_))</th>" })) -join '')</tr></thead>"
+	"<tbody>"
+	foreach ($row in $Table.Rows) {
+		"<tr>$((@($row | ForEach-Object { "<td>$([System.Net.WebUtility]::HtmlEncode($_))</td>" })) -join '')</tr>"
+	}
+	"</tbody>"
+	"</table>"
+}
+
+# Izvjestaj kao staticka HTML stranica (bez spoljnih stilova i skripti)
+function ConvertTo-EprijavaHtmlReport {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[string]$Title = ""
+	)
+
+	if (-not $Title) {
+		$Title = ConvertTo-EprijavaLocalLabel -Text "Finansijski izvjestaji kompanija"
+	}
+	$title = [System.Net.WebUtility]::HtmlEncode($Title)
+	$language = if ((Get-EprijavaLocale) -eq "en") { "en" } else { "sr-Latn-ME" }
+	$lines = [System.Collections.Generic.List[string]]::new()
+	$lines.Add("<!DOCTYPE html>")
+	$lines.Add("<html lang=""$($language)"">")
+	$lines.Add("<head>")
+	$lines.Add("<meta charset=""utf-8"">")
+	$lines.Add("<title>$($title)</title>")
+	$lines.Add("<style>body { font-family: sans-serif; margin: 2em; } table { border-collapse: collapse; margin-bottom: 1em; } th, td { border: 1px solid #ccc; padding: 4px 8px; } td { text-align: right; }</style>")
+	$lines.Add("</head>")
+	$lines.Add("<body>")
+	$lines.Add("<h1>$($title)</h1>")
+	$lines.Add("<h2>$(ConvertTo-EprijavaLocalLabel -Text "Pregled sektora")</h2>")
+	$summary = @(Get-EprijavaSectorSummary -Results $Results)
+	foreach ($line in (ConvertTo-EprijavaHtmlTable -Table (Get-EprijavaReportTable -Rows $summary -Columns $script:ReportSummaryColumns))) {
+		$lines.Add($line)
+	}
+	$tagSummary = @(Get-EprijavaTagSummary -Results $Results)
+	if ($tagSummary.Count -gt 0) {
+		$lines.Add("<h2>$(ConvertTo-EprijavaLocalLabel -Text "Pregled po oznakama")</h2>")
+		foreach ($tag in ($tagSummary | Group-Object Tag)) {
+			$lines.Add("<h3>$([System.Net.WebUtility]::HtmlEncode($tag.Name))</h3>")
+			foreach ($line in (ConvertTo-EprijavaHtmlTable -Table (Get-EprijavaReportTable -Rows $tag.Group -Columns $script:ReportSummaryColumns))) {
+				$lines.Add($line)
+			}
+		}
+	}
+
+	foreach ($company in (Get-EprijavaReportCompanies -Results $Results | Sort-Object Name)) {
+		$lines.Add("<h2>$([System.Net.WebUtility]::HtmlEncode($company.Name))</h2>")
+		if ($company.Pib) {
+			$lines.Add("<p>$(ConvertTo-EprijavaLocalLabel -Text "PIB"): $([System.Net.WebUtility]::HtmlEncode($company.Pib))</p>")
+		}
+		foreach ($line in (ConvertTo-EprijavaHtmlTable -Table (Get-EprijavaReportTable -Rows $company.Years -Columns $script:ReportCompanyColumns))) {
+			$lines.Add($line)
+		}
+	}
+
+	$lines.Add("</body>")
+	$lines.Add("</html>")
+	$lines -join "`n"
+}
+
+# Upis izvjestaja, format se odredjuje prema ekstenziji fajla (.html/.htm ili Markdown za ostale)
+function Export-EprijavaReport {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[string]$Title = ""
+	)
+
+	$content = if ($Path -match '\.html?$') {
+		ConvertTo-EprijavaHtmlReport -Results $Results -Title $Title
+	}
+	else {
+		ConvertTo-EprijavaMarkdownReport -Results $Results -Title $Title
+	}
+	Set-Content -Path $Path -Value $content -Encoding UTF8
+}
@@ -0,0 +1,255 @@
+# Upis i citanje CSV fajla sa rezultatima
+
+# Izabrane kolone CSV fajla sa rezultatima, $null = sve kolone (Set-EprijavaResultColumnSelection)
+$script:ResultColumnSelection = $null
+
+# Kolone koje se upisuju u CSV fajl
+# Podaci dodati u pravilima za izdvajanje (rules.psd1) dobijaju kolonu nakon osnovnih kolona, a kolone registra i liste PDV-a su na kraju
+# Uz Selected se vracaju izabrane kolone u zadatom redosljedu, ukoliko je izbor zadat
+function Get-EprijavaResultColumns {
+	param(
+		[switch]$StatAnnex,
+		[switch]$Selected
+	)
+
+	if ($Selected -and $script:ResultColumnSelection) {
+		return $script:ResultColumnSelection
+	}
+
+	$script:ResultColumns
+	foreach ($field in (Get-EprijavaRules)) {
+		if ($script:ResultColumns.Property -notcontains $field.Name) {
+			@{ Header = $field.Header; Property = $field.Name; Type = [decimal] }
+		}
+	}
+	Get-EprijavaExtractorColumns
+	if ($StatAnnex) {
+		$script:StatAnnexColumns
+	}
+	if (Test-EprijavaRegistryEnabled) {
+		$script:RegistryColumns
+	}
+	if (Test-EprijavaVatListLoaded) {
+		$script:VatColumns
+	}
+	if (Test-EprijavaCpiTableLoaded) {
+		$script:RealColumns
+	}
+}
+
+# Sve kolone koje se mogu izabrati: kolone rezultata sa svim opcionim kolonama i kolone koje se upisuju samo kada su izabrane
+function Get-EprijavaAvailableColumns {
+	$headers = @{}
+	foreach ($column in @(@(Get-EprijavaResultColumns -StatAnnex) + $script:RegistryColumns + $script:VatColumns + $script:RealColumns + $script:SelectableColumns)) {
+		if (-not $headers.ContainsKey($column.Header)) {
+			$headers[$column.Header] = $true
+			$column
+		}
+	}
+}
+
+# Izbor i redosljed kolona CSV fajla sa rezultatima po nazivima iz zaglavlja na bilo kom jeziku (npr. "name,Year,profit" ili niz naziva)
+# Prazan izbor vraca sve kolone; nepoznat naziv prekida pokretanje uz spisak dostupnih kolona
+function Set-EprijavaResultColumnSelection {
+	param(
+		[AllowEmptyCollection()] [string[]]$Headers = @()
+	)
+
+	$names = @($Headers -split ',' | ForEach-Object { $_.Trim() } | Where-Object { $_ })
+	if ($names.Count -eq 0) {
+		$script:ResultColumnSelection = $null
+		return
+	}
+
+	$available = @(Get-EprijavaAvailableColumns)
+	$script:ResultColumnSelection = @(foreach ($name in $names) {
+		$column = $available | Where-Object { (Get-EprijavaHeaderNames -Header $_.Header) -contains $name } | Select-Object -First 1
+		if (-not $column) {
+			throw "Nepoznata kolona '$($name)' (dostupne kolone: $($available.Header -join ", "))"
+		}
+		$column
+	})
+}
+
+# Formiranje CSV fajla za smjestanje rezultata (postojeci fajl se brise)
+# Uz Columns se koriste zadate kolone umjesto kolona rezultata (npr. za izvedene pokazatelje), a zaglavlja su na zadatom jeziku (Set-EprijavaLocale)
+# Ispred zaglavlja je komentar sa verzijom sheme kada je ukljucen (Set-EprijavaSchemaComment)
+function Set-EprijavaResultFile {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[switch]$StatAnnex,
+		[object[]]$Columns
+	)
+
+	if (-not $Columns) {
+		$Columns = @(Get-EprijavaResultColumns -StatAnnex:$StatAnnex -Selected)
+	}
+	$headers = foreach ($column in $Columns) {
+		"""$(ConvertTo-EprijavaLocalHeader -Header $column.Header)"""
+	}
+	Set-Content -Path $Path -Value @(@(Get-EprijavaSchemaComment) + ($headers -join ","))
+}
+
+# Privremeni fajl za upis izlaza (<putanja>.tmp), koji zamjenjuje izlaz tek kada je upis zavrsen (Complete-EprijavaResultFile)
+# Uz Keep se nastavlja postojeci izlaz: koristi se privremeni fajl prekinutog pokretanja ako postoji, inace kopija izlaza
+function Open-EprijavaResultFile {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[switch]$Keep
+	)
+
+	$temporary = "$($Path).tmp"
+	if (-not $Keep) {
+		Remove-Item -Path $temporary -ErrorAction SilentlyContinue
+	}
+	elseif (-not (Test-Path -Path $temporary -PathType Leaf) -and (Test-Path -Path $Path -PathType Leaf)) {
+		Copy-Item -Path $Path -Destination $temporary -Force
+	}
+
+	$temporary
+}
+
+# Zamjena izlaza privremenim fajlom (preimenovanje), pa citaoci izlaza nikada ne vide djelimicno upisan fajl
+function Complete-EprijavaResultFile {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$temporary = "$($Path).tmp"
+	if (Test-Path -Path $temporary -PathType Leaf) {
+		Move-Item -Path $temporary -Destination $Path -Force
+	}
+}
+
+# Red CSV fajla za jedan rezultat, tekstualne vrijednosti se pisu pod navodnicima
+# Kolona sa izrazom (Expression) se racuna iz ostalih vrijednosti rezultata, osim kada je vrijednost vec ucitana iz fajla
+function ConvertTo-EprijavaResultLine {
+	param(
+		[Parameter(Mandatory)] $Result,
+		[switch]$StatAnnex,
+		[object[]]$Columns
+	)
+
+	if (-not $Columns) {
+		$Columns = @(Get-EprijavaResultColumns -StatAnnex:$StatAnnex -Selected)
+	}
+	$values = foreach ($column in $Columns) {
+		$value = $Result.($column.Property)
+		if ($null -eq $value -and $column.Expression) {
+			$value = & $column.Expression $Result
+		}
+		if ($column.Type -eq [string]) {
+			"""$("$value".Replace('"', '""'))"""
+		}
+		else {
+			"$value"
+		}
+	}
+	$values -join ", "
+}
+
+# Upis jednog rezultata u CSV fajl
+function Add-EprijavaResultRow {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] $Result,
+		[switch]$StatAnnex
+	)
+
+	Add-Content -Path $Path -Value (ConvertTo-EprijavaResultLine -Result $Result -StatAnnex:$StatAnnex)
+}
+
+# Kljuc rezultata (naziv, godina, verzija izvjestaja), redovi bez verzije iz ranijih fajlova se smatraju prvom verzijom
+function Get-EprijavaResultKey {
+	param(
+		[Parameter(Mandatory)] $Result
+	)
+
+	$version = if ($Result.Version) { $Result.Version } else { 1 }
+	"$($Result.Name)|$($Result.Year)|$($version)"
+}
+
+# Redovi sortirani po nazivu ili PIB-u kompanije, zatim po godini i verziji izvjestaja (none = redosljed obrade)
+function Sort-EprijavaResults {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[ValidateSet("name", "pib", "none")] [string]$By = "none"
+	)
+
+	if ($By -eq "none") {
+		return $Results
+	}
+
+	$company = if ($By -eq "pib") { @({ "$($_.Pib)" }, { "$($_.Name)" }) } else { @({ "$($_.Name)" }, { "$($_.Pib)" }) }
+	$Results | Sort-Object -Property @($company + @({ [int]$_.Year }, { [int]$_.Version }))
+}
+
+# Indeks postojecih redova CSV fajla po kljucu (naziv, godina, verzija), za spajanje novih rezultata sa postojecim
+function New-EprijavaResultIndex {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[switch]$StatAnnex
+	)
+
+	$index = [ordered]@{}
+	if (Test-Path -Path $Path -PathType Leaf) {
+		foreach ($result in (Import-EprijavaResults -Path $Path)) {
+			$index[(Get-EprijavaResultKey -Result $result)] = ConvertTo-EprijavaResultLine -Result $result -StatAnnex:$StatAnnex
+		}
+	}
+
+	$index
+}
+
+# Spajanje rezultata sa postojecim CSV fajlom: novi red se dodaje, izmijenjeni red zamjenjuje postojeci (uz ponovni upis fajla)
+# Vraca Added, Updated ili Unchanged
+function Merge-EprijavaResultRow {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] $Result,
+		[Parameter(Mandatory)] [System.Collections.Specialized.OrderedDictionary]$Index,
+		[switch]$StatAnnex
+	)
+
+	$key = Get-EprijavaResultKey -Result $Result
+	$line = ConvertTo-EprijavaResultLine -Result $Result -StatAnnex:$StatAnnex
+
+	if (-not $Index.Contains($key)) {
+		$Index[$key] = $line
+		Add-Content -Path $Path -Value $line
+		return "Added"
+	}
+	if ($Index[$key] -eq $line) {
+		return "Unchanged"
+	}
+
+	$Index[$key] = $line
+	Set-EprijavaResultFile -Path $Path -StatAnnex:$StatAnnex
+	Add-Content -Path $Path -Value @($Index.Values)
+	"Updated"
+}
+
+# Ucitavanje rezultata iz postojeceg CSV fajla, kolone kojih nema u fajlu i prazne celije dobijaju vrijednost $null
+# Zaglavlja se prepoznaju na oba jezika, nezavisno od zadatog jezika izlaza, a komentar sa verzijom sheme u prvom redu se preskace
+function Import-EprijavaResults {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$columns = @(Get-EprijavaResultColumns -StatAnnex) + @($script:SelectableColumns | Where-Object { $_.Property -ne "Pib" })
+	$fields = @(@{ Property = "Pib"; Type = [string] }) + $columns
+	$rows = if ((Get-Content -Path $Path -TotalCount 1) -match '^#') { Get-Content -Path $Path | Select-Object -Skip 1 | ConvertFrom-Csv } else { Import-Csv -Path $Path }
+	foreach ($row in $rows) {
+		$values = @{ Pib = $row.pib }
+		foreach ($column in $columns) {
+			foreach ($header in (Get-EprijavaHeaderNames -Header $column.Header)) {
+				if ($null -ne $row.$header) {
+					$values[$column.Property] = $row.$header
+					break
+				}
+			}
+		}
+		$result = New-EprijavaModelObject -Type ParsedStatement -Values $values -Fields $fields
+		Rename-EprijavaCompanyAlias -Result $result
+	}
+}
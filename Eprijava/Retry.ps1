@@ -0,0 +1,62 @@
+# Ponavljanje zahtjeva nakon privremenih gresaka (istek vremena, 5xx odgovori)
+
+# Pravilo ponavljanja: najvise MaxAttempts pokusaja, pauza BaseDelay * 2^(pokusaj-1) sekundi
+# Ponavljaju se zahtjevi bez odgovora (istek vremena, prekinuta veza) i odgovori sa statusom iz RetryOnStatus
+function New-EprijavaRetryPolicy {
+	param(
+		[int]$MaxAttempts = 3,
+		[double]$BaseDelay = 2,
+		[int[]]$RetryOnStatus = @(408, 429, 500, 502, 503, 504)
+	)
+
+	[PSCustomObject]@{
+		PSTypeName    = "Eprijava.RetryPolicy"
+		MaxAttempts   = [math]::Max($MaxAttempts, 1)
+		BaseDelay     = $BaseDelay
+		RetryOnStatus = $RetryOnStatus
+	}
+}
+
+# HTTP status iz greske Invoke-RestMethod/Invoke-WebRequest, $null ukoliko odgovor nije primljen
+function Get-EprijavaErrorStatus {
+	param(
+		[Parameter(Mandatory)] [System.Management.Automation.ErrorRecord]$ErrorRecord
+	)
+
+	$response = $ErrorRecord.Exception.Response
+	if ($null -ne $response -and $null -ne $response.StatusCode) {
+		return [int]$response.StatusCode
+	}
+
+	$null
+}
+
+# Izvrsavanje zahtjeva uz ponavljanje prema pravilu; greska se prosljedjuje kada pokusaji isteknu ili status nije privremen
+function Invoke-EprijavaWithRetry {
+	param(
+		[Parameter(Mandatory)] $RetryPolicy,
+		[Parameter(Mandatory)] [scriptblock]$ScriptBlock,
+		[string]$Url
+	)
+
+	for ($attempt = 1; ; $attempt++) {
+		try {
+			return & $ScriptBlock
+		}
+		catch {
+			if (Test-EprijavaBudgetError -ErrorRecord $_) {
+				throw
+			}
+			$status = Get-EprijavaErrorStatus -ErrorRecord $_
+			$transient = $null -eq $status -or $RetryPolicy.RetryOnStatus -contains $status
+			if (-not $transient -or $attempt -ge $RetryPolicy.MaxAttempts) {
+				throw
+			}
+
+			$delay = $RetryPolicy.BaseDelay * [math]::Pow(2, $attempt - 1)
+			$reason = if ($null -eq $status) { $_.Exception.Message } else { "HTTP $($status)" }
+			Write-EprijavaLog -Message "Zahtjev $($Url) nije uspio ($($reason)), pokusaj $($attempt + 1)/$($RetryPolicy.MaxAttempts) za $($delay)s" -Level warning -Stage request -Outcome retry
+			Start-Sleep -Milliseconds ([int]($delay * 1000))
+		}
+	}
+}
@@ -0,0 +1,93 @@
+# Opis pokretanja prikupljanja (run.json): vrijeme, verzija programa, podesavanja, brojaci i SHA-256 izlaznih fajlova
+# Uz objavljeni skup podataka omogucava da se utvrdi kako je i iz kog pokretanja skup nastao
+
+# Verzija programa, povecava se uz izmjene koje mijenjaju izlaz
+$script:ToolVersion = "1.0.0"
+
+# Kljucevi podesavanja cije se vrijednosti ne upisuju (ID sesije, lozinke, webhook URL sa tokenom i tajna vrijednost pseudonima)
+$script:RedactedConfigKeys = @("Session", "*Password", "WebhookUrl", "*Salt")
+
+# Verzija programa i git commit foldera programa ($null ukoliko git nije dostupan ili folder nije git repozitorijum)
+function Get-EprijavaToolVersion {
+	$commit = $null
+	if (Get-Command -Name git -CommandType Application -ErrorAction SilentlyContinue) {
+		$commit = git -C $PSScriptRoot rev-parse HEAD 2>$null
+		if ($LASTEXITCODE -ne 0) {
+			$commit = $null
+		}
+	}
+
+	[ordered]@{
+		name       = $script:UserAgentProduct
+		version    = $script:ToolVersion
+		commit     = $commit
+		powershell = "$($PSVersionTable.PSVersion)"
+	}
+}
+
+# Podesavanja pokretanja bez tajnih podataka: vrijednosti kljuceva iz $script:RedactedConfigKeys se zamjenjuju sa "<redacted>",
+# a korisnicko ime i lozinka u URL-u proxy servera se uklanjaju
+function ConvertTo-EprijavaRedactedConfig {
+	param(
+		[Parameter(Mandatory)] [System.Collections.IDictionary]$Values
+	)
+
+	$redacted = [ordered]@{}
+	foreach ($key in $Values.Keys) {
+		$value = $Values[$key]
+		if (@($script:RedactedConfigKeys | Where-Object { $key -like $_ }).Count -gt 0) {
+			$value = if ("$($value)" -ne "") { "<redacted>" } else { $null }
+		}
+		elseif ($value -is [string] -and $value -match '^[a-z][a-z0-9+.-]*://[^/@]+@') {
+			$value = $value -replace '^([a-z][a-z0-9+.-]*://)[^/@]+@', '$1'
+		}
+		elseif ($value -is [switch]) {
+			$value = $value.IsPresent
+		}
+		$redacted[$key] = $value
+	}
+	$redacted
+}
+
+# Upis opisa pokretanja u JSON fajl
+# Outputs su putanje izlaznih fajlova; za postojece fajlove se upisuju velicina i SHA-256, a fajlovi koji ne postoje se izostavljaju
+function Export-EprijavaRunMetadata {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] $Stats,
+		[Parameter(Mandatory)] [string]$Command,
+		[System.Collections.IDictionary]$Config = @{},
+		[System.Collections.IDictionary]$Counts = @{},
+		[AllowEmptyCollection()] [string[]]$Outputs = @(),
+		[int]$ExitCode = 0
+	)
+
+	$finishedAt = Get-Date
+	$files = foreach ($output in ($Outputs | Where-Object { $_ } | Select-Object -Unique)) {
+		if (Test-Path -Path $output -PathType Leaf) {
+			$item = Get-Item -Path $output
+			[ordered]@{
+				path   = $output
+				bytes  = $item.Length
+				sha256 = Get-EprijavaFileSha256 -Path $item.FullName
+			}
+		}
+	}
+
+	$metadata = [ordered]@{
+		tool            = Get-EprijavaToolVersion
+		command         = $Command
+		startedAt       = $Stats.StartedAt.ToUniversalTime().ToString("o")
+		finishedAt      = $finishedAt.ToUniversalTime().ToString("o")
+		durationSeconds = [int]($finishedAt - $Stats.StartedAt).TotalSeconds
+		exitCode        = $ExitCode
+		schemaVersion   = $script:SchemaVersion
+		config          = ConvertTo-EprijavaRedactedConfig -Values $Config
+		counts          = $Counts
+		outputs         = @($files)
+	}
+
+	$temporary = "$($Path).tmp"
+	[IO.File]::WriteAllText($temporary, (ConvertTo-Json -InputObject $metadata -Depth 5), [System.Text.UTF8Encoding]::new($false))
+	Move-Item -Path $temporary -Destination $Path -Force
+}
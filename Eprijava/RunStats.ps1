@@ -0,0 +1,109 @@
+# Statistika pokretanja: napredak po kompanijama sa procjenom preostalog vremena i brojaci za pregled na kraju prikupljanja
+# Brojaci zahtjeva prema portalu se uvecavaju u klijentu kojem je statistika proslijedjena (New-EprijavaClient -Stats)
+
+# Prazna statistika za zadati broj kompanija
+function New-EprijavaRunStats {
+	param(
+		[Parameter(Mandatory)] [int]$Companies
+	)
+
+	[PSCustomObject]@{
+		PSTypeName      = "Eprijava.RunStats"
+		StartedAt       = Get-Date
+		Companies       = $Companies
+		Processed       = 0
+		Downloaded      = 0
+		CacheHits       = 0
+		ContentChanged  = 0
+		Requests        = 0
+		RequestFailures = 0
+		BytesDownloaded = [long]0
+		RequestSeconds  = [double]0
+	}
+}
+
+# Ukupni napredak prije obrade kompanije, procjena preostalog vremena se racuna iz prosjecnog trajanja obradjenih kompanija
+function Write-EprijavaRunProgress {
+	param(
+		[Parameter(Mandatory)] $Stats,
+		[Parameter(Mandatory)] [string]$Name
+	)
+
+	$progress = @{
+		Id              = 0
+		Activity        = "Prikupljanje finansijskih izvjestaja"
+		Status          = "$($Stats.Processed + 1) od $($Stats.Companies): $($Name)"
+		PercentComplete = if ($Stats.Companies -gt 0) { [math]::Min(100, [int](100 * $Stats.Processed / $Stats.Companies)) } else { 0 }
+	}
+	if ($Stats.Processed -gt 0) {
+		$elapsed = ((Get-Date) - $Stats.StartedAt).TotalSeconds
+		$progress.SecondsRemaining = [int]($elapsed / $Stats.Processed * ($Stats.Companies - $Stats.Processed))
+	}
+	Write-Progress @progress
+}
+
+# Napredak kroz izvjestaje jedne kompanije (ispod ukupnog napretka)
+function Write-EprijavaReportProgress {
+	param(
+		[Parameter(Mandatory)] [int]$Index,
+		[Parameter(Mandatory)] [int]$Count,
+		[Parameter(Mandatory)] [string]$Year
+	)
+
+	Write-Progress -Id 1 -ParentId 0 -Activity "Izvjestaji" -Status "$($Index + 1) od $($Count): godina $($Year)" -PercentComplete ([int](100 * $Index / [math]::Max(1, $Count)))
+}
+
+# Zavrsetak prikaza napretka
+function Complete-EprijavaRunProgress {
+	Write-Progress -Id 1 -Activity "Izvjestaji" -Completed
+	Write-Progress -Id 0 -Activity "Prikupljanje finansijskih izvjestaja" -Completed
+}
+
+# Upis statistike u tekstualni fajl u Prometheus formatu (za textfile collector node-exporter-a)
+# Fajl se upisuje preko privremenog fajla, kako collector nikada ne bi procitao nepotpun sadrzaj
+function Export-EprijavaPrometheusMetrics {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] $Stats,
+		$ErrorReport = $null
+	)
+
+	$invariant = [cultureinfo]::InvariantCulture
+	$lines = [System.Collections.Generic.List[string]]::new()
+	$counters = @(
+		@{ Name = "eprijava_companies_processed_total"; Help = "Obradjene kompanije"; Value = $Stats.Processed }
+		@{ Name = "eprijava_requests_total"; Help = "Zahtjevi prema portalu (ukljucujuci ponovljene)"; Value = $Stats.Requests }
+		@{ Name = "eprijava_request_failures_total"; Help = "Neuspjeli zahtjevi prema portalu"; Value = $Stats.RequestFailures }
+		@{ Name = "eprijava_downloaded_bytes_total"; Help = "Preuzeti bajtovi odgovora portala (HTML, JSON i PDF)"; Value = $Stats.BytesDownloaded }
+		@{ Name = "eprijava_request_duration_seconds_total"; Help = "Ukupno trajanje zahtjeva prema portalu"; Value = $Stats.RequestSeconds }
+		@{ Name = "eprijava_reports_downloaded_total"; Help = "Preuzeti izvjestaji"; Value = $Stats.Downloaded }
+		@{ Name = "eprijava_cache_hits_total"; Help = "Izvjestaji ucitani iz kesa"; Value = $Stats.CacheHits }
+		@{ Name = "eprijava_reports_changed_total"; Help = "Ponovo preuzeti izvjestaji sa izmijenjenim sadrzajem"; Value = $Stats.ContentChanged }
+	)
+	foreach ($counter in $counters) {
+		$lines.Add("# HELP $($counter.Name) $($counter.Help)")
+		$lines.Add("# TYPE $($counter.Name) counter")
+		$lines.Add("$($counter.Name) $(([double]$counter.Value).ToString('0.###', $invariant))")
+	}
+
+	if ($ErrorReport) {
+		$lines.Add("# HELP eprijava_errors_total Greske pokretanja po vrsti (SkippedCompany, DownloadFailed, ExtractionFailed, InconsistentTotals)")
+		$lines.Add("# TYPE eprijava_errors_total counter")
+		foreach ($kind in $script:ErrorKinds) {
+			$lines.Add("eprijava_errors_total{kind=""$($kind)""} $(@($ErrorReport.Entries | Where-Object { $_.Kind -eq $kind }).Count)")
+		}
+	}
+
+	$now = Get-Date
+	$lines.Add("# HELP eprijava_run_duration_seconds Trajanje posljednjeg pokretanja")
+	$lines.Add("# TYPE eprijava_run_duration_seconds gauge")
+	$lines.Add("eprijava_run_duration_seconds $((($now - $Stats.StartedAt).TotalSeconds).ToString('0.###', $invariant))")
+	$lines.Add("# HELP eprijava_last_run_timestamp_seconds Vrijeme zavrsetka posljednjeg pokretanja (Unix)")
+	$lines.Add("# TYPE eprijava_last_run_timestamp_seconds gauge")
+	$lines.Add("eprijava_last_run_timestamp_seconds $([DateTimeOffset]::new($now).ToUnixTimeSeconds())")
+
+	# Prometheus zahtijeva LF kraj reda i UTF-8 bez BOM-a
+	$temporary = "$($Path).tmp"
+	[IO.File]::WriteAllText($temporary, (($lines -join "`n") + "`n"), [System.Text.UTF8Encoding]::new($false))
+	Move-Item -Path $temporary -Destination $Path -Force
+}
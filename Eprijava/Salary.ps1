@@ -0,0 +1,73 @@
+# Model zarade: prosjecna mjesecna neto zarada iz neto troskova zarada i procjena bruto zarade
+# Broj isplata godisnje (npr. 13 uz trinaestu platu) i stope poreza i doprinosa se zadaju sa Set-EprijavaSalaryModel
+
+# Podrazumijevani model: 12 isplata, doprinosi zaposlenog 24% (PIO 15%, zdravstvo 8.5%, nezaposlenost 0.5%),
+# porez na dohodak 9% i prirez 13% poreza (Podgorica), tj. stope koje su vazile do 2022. godine
+$script:SalaryModel = [PSCustomObject]@{
+	PSTypeName       = "Eprijava.SalaryModel"
+	Divisor          = 12
+	ContributionRate = [decimal]0.24
+	TaxRate          = [decimal]0.09
+	SurtaxRate       = [decimal]0.13
+}
+
+# Izmjena modela zarade: broj isplata godisnje i stope (udio, npr. 0.24) doprinosa zaposlenog, poreza na dohodak i prireza na porez
+# Porez i doprinosi se racunaju na bruto zaradu, pa je neto = bruto * (1 - doprinosi - porez * (1 + prirez))
+function Set-EprijavaSalaryModel {
+	param(
+		[int]$Divisor = 12,
+		[decimal]$ContributionRate = 0.24,
+		[decimal]$TaxRate = 0.09,
+		[decimal]$SurtaxRate = 0.13
+	)
+
+	if ($Divisor -lt 1) {
+		throw "Broj isplata zarade godisnje mora biti veci od 0 (zadato $($Divisor))"
+	}
+	foreach ($rate in @($ContributionRate, $TaxRate, $SurtaxRate)) {
+		if ($rate -lt 0 -or $rate -ge 1) {
+			throw "Stopa $($rate) nije ispravna (ocekivan udio izmedju 0 i 1, npr. 0.24)"
+		}
+	}
+	if ($ContributionRate + $TaxRate * (1 + $SurtaxRate) -ge 1) {
+		throw "Zbir stopa doprinosa, poreza i prireza mora biti manji od 1"
+	}
+
+	$script:SalaryModel = [PSCustomObject]@{
+		PSTypeName       = "Eprijava.SalaryModel"
+		Divisor          = $Divisor
+		ContributionRate = $ContributionRate
+		TaxRate          = $TaxRate
+		SurtaxRate       = $SurtaxRate
+	}
+}
+
+# Trenutni model zarade
+function Get-EprijavaSalaryModel {
+	$script:SalaryModel
+}
+
+# Prosjecna mjesecna neto zarada (neto troskovi zarada / broj zaposlenih / broj isplata) i procijenjena bruto zarada,
+# $null ukoliko neki od podataka nije pronadjen, odnosno 0 za kompanije bez zaposlenih
+function Get-EprijavaAveragePay {
+	param(
+		$NetPayCosts,
+		$EmployeeCount
+	)
+
+	if ($null -eq $NetPayCosts -or $null -eq $EmployeeCount) {
+		return [PSCustomObject]@{ Net = $null; Gross = $null }
+	}
+	if ($EmployeeCount -le 0) {
+		return [PSCustomObject]@{ Net = [decimal]0; Gross = [decimal]0 }
+	}
+
+	$model = $script:SalaryModel
+	$net = [decimal]$NetPayCosts / [decimal]$EmployeeCount / $model.Divisor
+	$netShare = 1 - $model.ContributionRate - $model.TaxRate * (1 + $model.SurtaxRate)
+
+	[PSCustomObject]@{
+		Net   = ConvertTo-EprijavaMoney -Value $net
+		Gross = ConvertTo-EprijavaMoney -Value ($net / $netShare)
+	}
+}
@@ -0,0 +1,93 @@
+# Verzija sheme izlaza i opis izlaza kao JSON Schema, kako bi programi koji citaju rezultate prepoznali nekompatibilne izmjene
+# Verzija se povecava kada se kolona ukloni, preimenuje ili promijeni tip; nove kolone ne mijenjaju verziju
+
+# Verzija sheme izlaza
+$script:SchemaVersion = 1
+
+# Upis verzije sheme kao prvog reda CSV fajla (komentar "#schemaVersion: 1"), $false = CSV fajl pocinje zaglavljem
+$script:SchemaComment = $false
+
+# Verzija sheme izlaza
+function Get-EprijavaSchemaVersion {
+	$script:SchemaVersion
+}
+
+# Ukljucivanje komentara sa verzijom sheme u CSV fajlove (Set-EprijavaResultFile)
+function Set-EprijavaSchemaComment {
+	param(
+		[bool]$Enabled = $true
+	)
+
+	$script:SchemaComment = $Enabled
+}
+
+# Komentar sa verzijom sheme za prvi red CSV fajla, $null ukoliko nije ukljucen
+function Get-EprijavaSchemaComment {
+	if ($script:SchemaComment) {
+		"#schemaVersion: $($script:SchemaVersion)"
+	}
+}
+
+# JSON Schema tip kolone, vrijednost moze i da nedostaje (null)
+function ConvertTo-EprijavaSchemaType {
+	param(
+		[Parameter(Mandatory)] [type]$Type
+	)
+
+	$name = switch ($Type) {
+		([int]) { "integer" }
+		([decimal]) { "number" }
+		default { "string" }
+	}
+	@($name, "null")
+}
+
+# Opis izlaza kao JSON Schema (draft 2020-12): red CSV fajla (csv) ili kompanija sa nizom godisnjih izvjestaja (json, jsonl)
+# Obuhvata sve kolone koje izlaz moze imati, ukljucujuci opcione kolone i kolone iz pravila za izdvajanje
+function Get-EprijavaResultSchema {
+	param(
+		[ValidateSet("csv", "json", "jsonl")] [string]$Format = "csv"
+	)
+
+	# Izvjestaji u JSON formatima nemaju naziv kompanije ni kolone koje se upisuju samo u CSV fajl (-Columns)
+	$columns = @(Get-EprijavaAvailableColumns)
+	if ($Format -ne "csv") {
+		$columns = @($columns | Where-Object { $_.Property -ne "Name" -and $script:SelectableColumns.Header -notcontains $_.Header })
+	}
+	$properties = [ordered]@{}
+	foreach ($column in $columns) {
+		$properties[$column.Header] = [ordered]@{ type = @(ConvertTo-EprijavaSchemaType -Type $column.Type) }
+	}
+
+	$schema = [ordered]@{
+		'$schema'     = "https://json-schema.org/draft/2020-12/schema"
+		title         = "Eprijava rezultati"
+		schemaVersion = $script:SchemaVersion
+	}
+	if ($Format -eq "csv") {
+		$schema.description = "Red CSV fajla sa rezultatima (jedan izvjestaj kompanije za jednu godinu)"
+		$schema.type = "object"
+		$schema.properties = $properties
+		$schema.required = @("name", "Year")
+		return $schema
+	}
+
+	$properties["number"] = [ordered]@{ type = @("string", "null") }
+	$schema.description = "Kompanija sa godisnjim izvjestajima (element JSON niza, odnosno red JSON Lines fajla)"
+	$schema.type = "object"
+	$schema.properties = [ordered]@{
+		schemaVersion = [ordered]@{ const = $script:SchemaVersion }
+		name          = [ordered]@{ type = "string" }
+		pib           = [ordered]@{ type = @("string", "null") }
+		statements    = [ordered]@{
+			type  = "array"
+			items = [ordered]@{
+				type       = "object"
+				properties = $properties
+				required   = @("Year")
+			}
+		}
+	}
+	$schema.required = @("name", "statements")
+	$schema
+}
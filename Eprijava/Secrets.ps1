@@ -0,0 +1,72 @@
+# Tajni podaci (ID sesije, lozinke za proxy i SMTP server) u sistemskom skladistu kljuceva, umjesto u konfiguracionom fajlu
+# Skladiste se koristi preko modula Microsoft.PowerShell.SecretManagement sa registrovanim skladistem (Register-SecretVault),
+# npr. Windows Credential Manager, macOS Keychain, GNOME Keyring ili Microsoft.PowerShell.SecretStore
+
+# Naziv tajnog podatka u skladistu (eprijava-<naziv>)
+function Get-EprijavaSecretName {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("Session", "ProxyPassword", "SmtpPassword")] [string]$Name
+	)
+
+	"eprijava-$($Name)"
+}
+
+# Naziv podrazumijevanog skladista, $null ukoliko modul SecretManagement nije instaliran ili skladiste nije registrovano
+function Get-EprijavaSecretVault {
+	if (-not (Get-Command -Name Get-Secret -ErrorAction Ignore)) {
+		return $null
+	}
+	$vault = Get-SecretVault -ErrorAction Ignore | Where-Object IsDefault | Select-Object -First 1
+	if ($vault) { $vault.Name } else { $null }
+}
+
+# Dostupnost skladista
+function Test-EprijavaSecretStore {
+	[bool](Get-EprijavaSecretVault)
+}
+
+# Tajni podatak iz skladista kao tekst, $null ukoliko skladiste nije dostupno ili podatak nije sacuvan
+function Get-EprijavaSecret {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("Session", "ProxyPassword", "SmtpPassword")] [string]$Name
+	)
+
+	if (-not (Test-EprijavaSecretStore)) {
+		return $null
+	}
+	try {
+		Get-Secret -Name (Get-EprijavaSecretName -Name $Name) -AsPlainText -ErrorAction Stop
+	}
+	catch {
+		$null
+	}
+}
+
+# Cuvanje tajnog podatka u skladistu (postojeca vrijednost se zamjenjuje)
+function Set-EprijavaSecret {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("Session", "ProxyPassword", "SmtpPassword")] [string]$Name,
+		[Parameter(Mandatory)] [securestring]$Value
+	)
+
+	if (-not (Test-EprijavaSecretStore)) {
+		throw "Skladiste kljuceva nije dostupno: potreban je modul Microsoft.PowerShell.SecretManagement sa registrovanim skladistem (Register-SecretVault -DefaultVault)"
+	}
+	Set-Secret -Name (Get-EprijavaSecretName -Name $Name) -SecureStringSecret $Value -ErrorAction Stop
+}
+
+# Brisanje tajnog podatka iz skladista, $false ukoliko podatak nije bio sacuvan
+function Remove-EprijavaSecret {
+	param(
+		[Parameter(Mandatory)] [ValidateSet("Session", "ProxyPassword", "SmtpPassword")] [string]$Name
+	)
+
+	if (-not (Test-EprijavaSecretStore)) {
+		throw "Skladiste kljuceva nije dostupno: potreban je modul Microsoft.PowerShell.SecretManagement sa registrovanim skladistem (Register-SecretVault -DefaultVault)"
+	}
+	if ($null -eq (Get-EprijavaSecret -Name $Name)) {
+		return $false
+	}
+	Remove-Secret -Name (Get-EprijavaSecretName -Name $Name) -Vault (Get-EprijavaSecretVault) -ErrorAction Stop
+	$true
+}
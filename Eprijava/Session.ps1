@@ -0,0 +1,304 @@
+# Uspostavljanje sesije na portalu ePrijava
+
+# Odredjivanje ID-a sesije: parametar, zatim promjenljiva EPRIJAVA_SESSION, skladiste kljuceva (Set-EprijavaSecret),
+# pa kljuc Session u konfiguracionom fajlu
+# Ukoliko ID nije nigdje definisan, vraca se $null i sesija se uspostavlja automatski
+function Resolve-EprijavaSession {
+	param(
+		[string]$Session,
+		[string]$ConfigPath
+	)
+
+	if ($Session) {
+		return $Session
+	}
+	if ($env:EPRIJAVA_SESSION) {
+		return $env:EPRIJAVA_SESSION
+	}
+	$stored = Get-EprijavaSecret -Name Session
+	if ($stored) {
+		return $stored
+	}
+	$config = Import-EprijavaConfig -Path $ConfigPath
+	if ($config.Session) {
+		return $config.Session
+	}
+
+	$null
+}
+
+# Vremenska ogranicenja zahtjeva u sekundama (0 = podrazumijevano ogranicenje PowerShell-a): uspostavljanje veze,
+# JSON endpoint-i (liste izvjestaja i pretraga) i HTML stranice (izvjestaji i detalji), koje su na sporim vezama znatno duze
+# Odvojeno ogranicenje za uspostavljanje veze postoji od PowerShell 7.4, a u starijim verzijama se primjenjuje samo ukupno ogranicenje
+function New-EprijavaTimeouts {
+	param(
+		[int]$Connect = 0,
+		[int]$List = 0,
+		[int]$Report = 0
+	)
+
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.Timeouts"
+		Connect    = $Connect
+		List       = $List
+		Report     = $Report
+	}
+}
+
+# Parametri vremenskog ogranicenja za Invoke-RestMethod/Invoke-WebRequest prema verziji PowerShell-a
+function Get-EprijavaTimeoutOptions {
+	param(
+		$Timeouts,
+		[switch]$Json
+	)
+
+	$options = @{}
+	if (-not $Timeouts) {
+		return $options
+	}
+
+	$operation = if ($Json) { $Timeouts.List } else { $Timeouts.Report }
+	if ((Get-Command Invoke-RestMethod).Parameters.ContainsKey("OperationTimeoutSeconds")) {
+		if ($Timeouts.Connect -gt 0) { $options.ConnectionTimeoutSeconds = $Timeouts.Connect }
+		if ($operation -gt 0) { $options.OperationTimeoutSeconds = $operation }
+	}
+	elseif ($operation -gt 0) {
+		$options.TimeoutSec = $operation
+	}
+
+	$options
+}
+
+# Najveci broj istovremenih veza prema jednom serveru (Windows PowerShell 5.1, 0 = bez izmjene)
+function Set-EprijavaConnectionSettings {
+	param(
+		[int]$ConnectionLimit = 0
+	)
+
+	if ($ConnectionLimit -gt 0) {
+		[System.Net.ServicePointManager]::DefaultConnectionLimit = $ConnectionLimit
+	}
+}
+
+# Dodatni parametri za sve zahtjeve prema portalu (proxy, User-Agent), u obliku pogodnom za prosljedjivanje Invoke-RestMethod/Invoke-WebRequest
+# User-Agent se salje uvijek: zadato zaglavlje ili naziv programa sa adresom projekta i kontaktom (Get-EprijavaUserAgent)
+# Proxy moze biti HTTP(S) ili, u PowerShell 7, SOCKS (socks5://host:port); uz CaBundle se prihvataju i sertifikati izdati od CA iz PEM fajla
+function New-EprijavaRequestOptions {
+	param(
+		[string]$Proxy,
+		[pscredential]$ProxyCredential,
+		[string]$UserAgent,
+		[string]$Contact,
+		[string]$CaBundle,
+		[switch]$DisableKeepAlive
+	)
+
+	$options = @{}
+	if ($DisableKeepAlive) {
+		$options.DisableKeepAlive = $true
+	}
+	if ($Proxy) {
+		$options.Proxy = $Proxy
+		if ($ProxyCredential) {
+			$options.ProxyCredential = $ProxyCredential
+		}
+	}
+	$options.UserAgent = Get-EprijavaUserAgent -UserAgent $UserAgent -Contact $Contact
+	if ($CaBundle) {
+		Register-EprijavaCaBundle -Path $CaBundle
+	}
+
+	$options
+}
+
+# Sertifikati iz PEM fajla (jedan ili vise blokova BEGIN CERTIFICATE)
+function Import-EprijavaCaBundle {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	$content = Get-Content -Path $Path -Raw
+	$certificates = foreach ($match in [regex]::Matches($content, '-----BEGIN CERTIFICATE-----(?<body>[\s\S]+?)-----END CERTIFICATE-----')) {
+		[System.Security.Cryptography.X509Certificates.X509Certificate2]::new([Convert]::FromBase64String(($match.Groups["body"].Value -replace '\s', '')))
+	}
+	if (@($certificates).Count -eq 0) {
+		throw "Fajl $($Path) ne sadrzi nijedan sertifikat u PEM formatu"
+	}
+
+	@($certificates)
+}
+
+# Prihvatanje sertifikata servera koji su izdati od CA iz PEM fajla (npr. za proxy koji presrece HTTPS saobracaj)
+# Sertifikati koje sistem vec prihvata se i dalje prihvataju; podrzano je u Windows PowerShell 5.1, jer PowerShell 7 ne koristi ServicePointManager
+function Register-EprijavaCaBundle {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if ($PSVersionTable.PSVersion.Major -ge 6) {
+		throw "Parametar -CaBundle je podrzan samo u Windows PowerShell 5.1. U PowerShell 7 dodajte CA sertifikat u sistemsko skladiste sertifikata."
+	}
+
+	$script:CaBundle = Import-EprijavaCaBundle -Path $Path
+	[System.Net.ServicePointManager]::ServerCertificateValidationCallback = {
+		param($sender, $certificate, $chain, $sslPolicyErrors)
+
+		if ($sslPolicyErrors -eq [System.Net.Security.SslPolicyErrors]::None) {
+			return $true
+		}
+		if ($sslPolicyErrors -ne [System.Net.Security.SslPolicyErrors]::RemoteCertificateChainErrors) {
+			return $false
+		}
+
+		$customChain = [System.Security.Cryptography.X509Certificates.X509Chain]::new()
+		$customChain.ChainPolicy.RevocationMode = [System.Security.Cryptography.X509Certificates.X509RevocationMode]::NoCheck
+		$customChain.ChainPolicy.VerificationFlags = [System.Security.Cryptography.X509Certificates.X509VerificationFlags]::AllowUnknownCertificateAuthority
+		$customChain.ChainPolicy.ExtraStore.AddRange([System.Security.Cryptography.X509Certificates.X509Certificate2[]]$script:CaBundle)
+		if (-not $customChain.Build([System.Security.Cryptography.X509Certificates.X509Certificate2]::new($certificate))) {
+			return $false
+		}
+		$root = $customChain.ChainElements[$customChain.ChainElements.Count - 1].Certificate
+		@($script:CaBundle | Where-Object { $_.Thumbprint -eq $root.Thumbprint }).Count -gt 0
+	}
+}
+
+# Otvaranje pocetne stranice portala kako bi portal dodijelio novi taxisSession cookie
+# Cookie se cuva u novom WebSession objektu, koji klijent salje uz sve naredne zahtjeve
+# Zahtjev prolazi kroz limiter, ogranicenje pokretanja, statistiku i zapis zahtjeva klijenta (Invoke-EprijavaClientRequest), kao i ostali zahtjevi
+function Connect-EprijavaSession {
+	param(
+		[Parameter(Mandatory)] $Client
+	)
+
+	$options = @{}
+	if ($Client.RequestOptions) {
+		$options += $Client.RequestOptions
+	}
+	$options += Get-EprijavaTimeoutOptions -Timeouts $Client.Timeouts
+	$webSession = New-Object Microsoft.PowerShell.Commands.WebRequestSession
+
+	Write-EprijavaLog -Message "Uspostavljanje nove sesije na portalu ePrijava" -Stage session -Outcome started
+	Invoke-EprijavaClientRequest -Client $Client -Url (Get-EprijavaEndpointUrl -Path (Get-EprijavaEndpoint -Name Home)) -Method GET -Options $options -WebSession $webSession | Out-Null
+
+	$cookie = $webSession.Cookies.GetCookies([uri](Get-EprijavaPortalUrl)) | Where-Object { $_.Name -eq "taxisSession" }
+	if (-not $cookie) {
+		throw "Portal nije dodijelio taxisSession cookie. Proslijedite ID sesije rucno parametrom -Session ili promjenljivom EPRIJAVA_SESSION."
+	}
+
+	$webSession
+}
+
+# WebSession sa zadatim ID-em sesije (taxisSession cookie za adresu portala)
+function New-EprijavaWebSession {
+	param(
+		[Parameter(Mandatory)] [string]$Session
+	)
+
+	$webSession = New-Object Microsoft.PowerShell.Commands.WebRequestSession
+	$webSession.Cookies.Add([System.Net.Cookie]::new("taxisSession", $Session, "/", ([uri](Get-EprijavaPortalUrl)).Host))
+	$webSession
+}
+
+# Kreiranje klijenta: sa zadatim ID-em sesije ili, ukoliko nije zadat, sa automatski uspostavljenom sesijom
+# Svi zahtjevi klijenta prolaze kroz zajednicki limiter (podrazumijevano 2 zahtjeva u sekundi uz do 0.5s nasumicne pauze)
+# i ponavljaju se nakon privremenih gresaka (podrazumijevano do 3 pokusaja); uz Stats se broje zahtjevi, greske, bajtovi i trajanje
+# Uz kasetu (Open-EprijavaCassette) se odgovori snimaju, odnosno u rezimu replay reprodukuju bez uspostavljanja sesije
+# PageSize je broj izvjestaja po stranici liste finansijskih izvjestaja
+# Uz HttpDump (Open-EprijavaHttpDump) se svaki zahtjev i odgovor upisuju u numerisan fajl
+# Uz Budget (New-EprijavaBudget) se zahtjevi nakon dostignutog ogranicenja zahtjeva ili bajtova ne salju
+# SessionRefresh odredjuje sta se radi kada sesija istekne tokom pokretanja (Update-EprijavaClientSession):
+# auto - nova sesija se uspostavlja automatski, prompt - jednom se trazi unos novog ID-a sesije, off - prikupljanje se prekida
+# Uz Robots se robots.txt portala (Set-EprijavaRobotsPolicy) cita prije prvog zahtjeva portalu, i prije uspostavljanja sesije
+function New-EprijavaClient {
+	param(
+		[string]$Session,
+		$RateLimiter = (New-EprijavaRateLimiter),
+		$RetryPolicy = (New-EprijavaRetryPolicy),
+		$Stats = $null,
+		[hashtable]$RequestOptions = (New-EprijavaRequestOptions),
+		$Timeouts = $null,
+		$Cassette = $null,
+		[int]$PageSize = 20,
+		[ValidateSet("auto", "prompt", "off")] [string]$SessionRefresh = "auto",
+		$HttpDump = $null,
+		$Budget = $null,
+		[switch]$Robots
+	)
+
+	$client = [PSCustomObject]@{
+		PSTypeName       = "Eprijava.Client"
+		WebSession       = $null
+		RateLimiter      = $RateLimiter
+		RetryPolicy      = $RetryPolicy
+		Stats            = $Stats
+		RequestOptions   = $RequestOptions
+		Timeouts         = $Timeouts
+		Cassette         = $Cassette
+		PageSize         = [math]::Max($PageSize, 1)
+		SessionRefresh   = $SessionRefresh
+		SessionRefreshes = 0
+		SessionPrompted  = $false
+		HttpDump         = $HttpDump
+		Budget           = $Budget
+	}
+
+	if ($Robots) {
+		Set-EprijavaRobotsPolicy -Client $client | Out-Null
+	}
+
+	if ($Cassette -and $Cassette.Mode -eq "replay") {
+		Write-EprijavaLog -Message "Reprodukovanje odgovora iz kasete $($Cassette.Path)" -Stage session -Outcome replay
+		$client.WebSession = New-Object Microsoft.PowerShell.Commands.WebRequestSession
+	}
+	elseif ($Session) {
+		$client.WebSession = New-EprijavaWebSession -Session $Session
+	}
+	else {
+		$client.WebSession = Connect-EprijavaSession -Client $client
+	}
+
+	$client
+}
+
+# Nova sesija klijenta nakon sto portal tokom pokretanja vrati stranicu za prijavu, $true ukoliko je sesija zamijenjena
+# U rezimu prompt se novi ID sesije trazi samo jednom (prazan unos = automatski), a kasnije se sesija uspostavlja automatski
+# Sesija se ne mijenja u rezimu off i pri reprodukovanju kasete
+function Update-EprijavaClientSession {
+	param(
+		[Parameter(Mandatory)] $Client,
+		[string]$Url
+	)
+
+	if ($Client.SessionRefresh -eq "off" -or ($Client.Cassette -and $Client.Cassette.Mode -eq "replay")) {
+		return $false
+	}
+
+	Write-EprijavaLog -Message "Sesija je istekla ($($Url)), uspostavljanje nove sesije" -Level warning -Stage session -Outcome expired -Data @{ url = $Url }
+	$webSession = $null
+	if ($Client.SessionRefresh -eq "prompt" -and -not $Client.SessionPrompted) {
+		$Client.SessionPrompted = $true
+		# Bez interaktivnog unosa (npr. pokretanje sa -NonInteractive) sesija se uspostavlja automatski
+		$value = try { Read-Host -Prompt "Nova vrijednost taxisSession cookie-ja (prazno = automatsko uspostavljanje sesije)" } catch { $null }
+		if ($value) {
+			$webSession = New-EprijavaWebSession -Session $value.Trim()
+		}
+	}
+	if (-not $webSession) {
+		try {
+			$webSession = Connect-EprijavaSession -Client $Client
+		}
+		catch {
+			# Dostignuto ogranicenje pokretanja prekida prikupljanje kao i kod ostalih zahtjeva
+			if (Test-EprijavaBudgetError -ErrorRecord $_) {
+				throw
+			}
+			Write-EprijavaLog -Message "Nova sesija nije uspostavljena: $($_.Exception.Message)" -Level warning -Stage session -Outcome failed
+			return $false
+		}
+	}
+
+	$Client.WebSession = $webSession
+	$Client.SessionRefreshes++
+	Write-EprijavaLog -Message "Uspostavljena nova sesija, zahtjev se ponavlja" -Stage session -Outcome refreshed -Data @{ refreshes = $Client.SessionRefreshes }
+	$true
+}
@@ -0,0 +1,312 @@
+# Smjestanje rezultata u SQLite bazu (zahtijeva PowerShell modul PSSQLite)
+# Baza ima tabele companies, statements i metrics, a upis nad istim PIB-om, godinom i verzijom izvjestaja azurira postojeci red
+# Istorija pokretanja (tabele runs i result_history) cuva vrijednosti iz svakog pokretanja, pa se vide i naknadne izmjene izvjestaja na portalu
+
+# Naziv kolone u bazi za svojstvo rezultata (npr. TotalIncome -> total_income)
+function ConvertTo-EprijavaColumnName {
+	param(
+		[Parameter(Mandatory)] [string]$Property
+	)
+
+	[regex]::Replace($Property, '(?<=[a-z0-9])([A-Z])', '_$1').ToLowerInvariant()
+}
+
+# Kolone tabele metrics: sve kolone rezultata osim naziva, godine i verzije izvjestaja (koje su u tabeli statements)
+function Get-EprijavaMetricColumns {
+	foreach ($column in (Get-EprijavaResultColumns -StatAnnex)) {
+		if ($column.Property -in @("Name", "Year", "Version", "Latest")) {
+			continue
+		}
+
+		[PSCustomObject]@{
+			Property = $column.Property
+			Header   = $column.Header
+			Name     = ConvertTo-EprijavaColumnName -Property $column.Property
+			Type     = $column.Type
+			SqlType  = switch ($column.Type) {
+				([int]) { "INTEGER" }
+				([decimal]) { "NUMERIC" }
+				default { "TEXT" }
+			}
+		}
+	}
+}
+
+# Kreiranje tabela (ukoliko ne postoje) i dodavanje kolona metrics koje nedostaju u bazi iz ranije verzije
+function Initialize-EprijavaDatabase {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if (-not (Get-Module -Name PSSQLite)) {
+		if (-not (Get-Module -ListAvailable -Name PSSQLite)) {
+			throw "Za upis u SQLite bazu potreban je modul PSSQLite (Install-Module PSSQLite)"
+		}
+		Import-Module PSSQLite
+	}
+
+	$metricColumns = @(Get-EprijavaMetricColumns)
+	$definitions = ($metricColumns | ForEach-Object { "$($_.Name) $($_.SqlType)" }) -join ", "
+
+	Invoke-SqliteQuery -DataSource $Path -Query @"
+CREATE TABLE IF NOT EXISTS companies (
+	pib TEXT PRIMARY KEY,
+	name TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS statements (
+	pib TEXT NOT NULL REFERENCES companies (pib),
+	year INTEGER NOT NULL,
+	version INTEGER NOT NULL DEFAULT 1,
+	number TEXT NOT NULL,
+	latest INTEGER NOT NULL DEFAULT 1,
+	downloaded_at TEXT NOT NULL,
+	PRIMARY KEY (pib, year, version)
+);
+CREATE TABLE IF NOT EXISTS metrics (
+	pib TEXT NOT NULL,
+	year INTEGER NOT NULL,
+	version INTEGER NOT NULL DEFAULT 1,
+	$($definitions),
+	PRIMARY KEY (pib, year, version),
+	FOREIGN KEY (pib, year, version) REFERENCES statements (pib, year, version)
+);
+CREATE TABLE IF NOT EXISTS runs (
+	id INTEGER PRIMARY KEY AUTOINCREMENT,
+	started_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS result_history (
+	run_id INTEGER NOT NULL REFERENCES runs (id),
+	pib TEXT NOT NULL,
+	year INTEGER NOT NULL,
+	version INTEGER NOT NULL DEFAULT 1,
+	number TEXT,
+	$($definitions),
+	PRIMARY KEY (run_id, pib, year, version)
+);
+"@
+
+	# Baza iz ranije verzije (jedan izvjestaj po godini): tabele se prepisuju sa kolonom version u primarnom kljucu
+	$statementColumns = @(Invoke-SqliteQuery -DataSource $Path -Query "PRAGMA table_info(statements)" | ForEach-Object { $_.name })
+	if ($statementColumns -notcontains "version") {
+		$existingMetrics = @(Invoke-SqliteQuery -DataSource $Path -Query "PRAGMA table_info(metrics)" | ForEach-Object { $_.name } | Where-Object { $_ -notin @("pib", "year") })
+		$copied = @("pib", "year") + @($metricColumns.Name | Where-Object { $existingMetrics -contains $_ })
+		Invoke-SqliteQuery -DataSource $Path -Query @"
+ALTER TABLE statements RENAME TO statements_old;
+ALTER TABLE metrics RENAME TO metrics_old;
+CREATE TABLE statements (
+	pib TEXT NOT NULL REFERENCES companies (pib),
+	year INTEGER NOT NULL,
+	version INTEGER NOT NULL DEFAULT 1,
+	number TEXT NOT NULL,
+	latest INTEGER NOT NULL DEFAULT 1,
+	downloaded_at TEXT NOT NULL,
+	PRIMARY KEY (pib, year, version)
+);
+CREATE TABLE metrics (
+	pib TEXT NOT NULL,
+	year INTEGER NOT NULL,
+	version INTEGER NOT NULL DEFAULT 1,
+	$($definitions),
+	PRIMARY KEY (pib, year, version),
+	FOREIGN KEY (pib, year, version) REFERENCES statements (pib, year, version)
+);
+INSERT INTO statements (pib, year, number, downloaded_at) SELECT pib, year, number, downloaded_at FROM statements_old;
+INSERT INTO metrics ($($copied -join ", ")) SELECT $($copied -join ", ") FROM metrics_old;
+DROP TABLE metrics_old;
+DROP TABLE statements_old;
+"@
+	}
+
+	foreach ($table in @("metrics", "result_history")) {
+		$existing = @(Invoke-SqliteQuery -DataSource $Path -Query "PRAGMA table_info($($table))" | ForEach-Object { $_.name })
+		foreach ($column in $metricColumns) {
+			if ($existing -notcontains $column.Name) {
+				Invoke-SqliteQuery -DataSource $Path -Query "ALTER TABLE $($table) ADD COLUMN $($column.Name) $($column.SqlType)"
+			}
+		}
+	}
+}
+
+# Upis jednog rezultata (kompanija, izvjestaj i izdvojene vrijednosti)
+function Write-EprijavaDatabaseResult {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] $Result
+	)
+
+	Invoke-SqliteQuery -DataSource $Path -Query "INSERT INTO companies (pib, name) VALUES (@pib, @name) ON CONFLICT (pib) DO UPDATE SET name = excluded.name" -SqlParameters @{
+		pib  = $Result.Pib
+		name = $Result.Name
+	}
+
+	$version = if ($Result.Version) { $Result.Version } else { 1 }
+	$latest = if ($null -ne $Result.Latest) { [int]$Result.Latest } else { 1 }
+	Invoke-SqliteQuery -DataSource $Path -Query "INSERT INTO statements (pib, year, version, number, latest, downloaded_at) VALUES (@pib, @year, @version, @number, @latest, @downloaded_at) ON CONFLICT (pib, year, version) DO UPDATE SET number = excluded.number, latest = excluded.latest, downloaded_at = excluded.downloaded_at" -SqlParameters @{
+		pib           = $Result.Pib
+		year          = $Result.Year
+		version       = $version
+		number        = $Result.Number
+		latest        = $latest
+		downloaded_at = (Get-Date).ToString("o")
+	}
+
+	# Novija verzija izvjestaja iste godine: ranije verzije vise nisu posljednje
+	if ($latest) {
+		Invoke-SqliteQuery -DataSource $Path -Query "UPDATE statements SET latest = 0 WHERE pib = @pib AND year = @year AND version <> @version" -SqlParameters @{
+			pib     = $Result.Pib
+			year    = $Result.Year
+			version = $version
+		}
+	}
+
+	$metricColumns = @(Get-EprijavaMetricColumns)
+	$parameters = @{ pib = $Result.Pib; year = $Result.Year; version = $version }
+	foreach ($column in $metricColumns) {
+		$parameters[$column.Name] = $Result.($column.Property)
+	}
+	$names = $metricColumns.Name -join ", "
+	$values = ($metricColumns | ForEach-Object { "@$($_.Name)" }) -join ", "
+	$updates = ($metricColumns | ForEach-Object { "$($_.Name) = excluded.$($_.Name)" }) -join ", "
+
+	Invoke-SqliteQuery -DataSource $Path -Query "INSERT INTO metrics (pib, year, version, $($names)) VALUES (@pib, @year, @version, $($values)) ON CONFLICT (pib, year, version) DO UPDATE SET $($updates)" -SqlParameters $parameters
+}
+
+# Ucitavanje rezultata iz baze, u istom obliku kao Import-EprijavaResults
+function Import-EprijavaDatabaseResults {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	Initialize-EprijavaDatabase -Path $Path
+	$metricColumns = @(Get-EprijavaMetricColumns)
+	$fields = @(
+		@{ Property = "Name"; Type = [string] }
+		@{ Property = "Pib"; Type = [string] }
+		@{ Property = "Year"; Type = [int] }
+		@{ Property = "Number"; Type = [string] }
+		@{ Property = "Version"; Type = [int] }
+		@{ Property = "Latest"; Type = [int] }
+	) + $metricColumns
+
+	foreach ($row in (Invoke-SqliteQuery -DataSource $Path -Query "SELECT c.name, s.number, s.latest, m.* FROM metrics m JOIN companies c ON c.pib = m.pib JOIN statements s ON s.pib = m.pib AND s.year = m.year AND s.version = m.version ORDER BY c.name, m.year DESC, m.version DESC")) {
+		$values = @{ Name = $row.name; Pib = $row.pib; Year = $row.year; Number = $row.number; Version = $row.version; Latest = $row.latest }
+		foreach ($column in $metricColumns) {
+			$values[$column.Property] = $row.($column.Name)
+		}
+		$result = New-EprijavaModelObject -Type ParsedStatement -Values $values -Fields $fields
+		Rename-EprijavaCompanyAlias -Result $result
+	}
+}
+
+# Novo pokretanje u istoriji, vraca identifikator pokretanja za Add-EprijavaDatabaseHistory
+function New-EprijavaDatabaseRun {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[datetime]$StartedAt = (Get-Date)
+	)
+
+	Initialize-EprijavaDatabase -Path $Path
+	Invoke-SqliteQuery -DataSource $Path -Query "INSERT INTO runs (started_at) VALUES (@started_at)" -SqlParameters @{ started_at = $StartedAt.ToUniversalTime().ToString("o") }
+	[long](Invoke-SqliteQuery -DataSource $Path -Query "SELECT MAX(id) AS id FROM runs").id
+}
+
+# Upis vrijednosti jednog rezultata u istoriju pokretanja (ranije vrijednosti ostaju sacuvane)
+function Add-EprijavaDatabaseHistory {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [long]$RunId,
+		[Parameter(Mandatory)] $Result
+	)
+
+	Invoke-SqliteQuery -DataSource $Path -Query "INSERT INTO companies (pib, name) VALUES (@pib, @name) ON CONFLICT (pib) DO UPDATE SET name = excluded.name" -SqlParameters @{
+		pib  = $Result.Pib
+		name = $Result.Name
+	}
+
+	$metricColumns = @(Get-EprijavaMetricColumns)
+	$version = if ($Result.Version) { $Result.Version } else { 1 }
+	$parameters = @{ run_id = $RunId; pib = $Result.Pib; year = $Result.Year; version = $version; number = $Result.Number }
+	foreach ($column in $metricColumns) {
+		$parameters[$column.Name] = $Result.($column.Property)
+	}
+	$names = $metricColumns.Name -join ", "
+	$values = ($metricColumns | ForEach-Object { "@$($_.Name)" }) -join ", "
+
+	Invoke-SqliteQuery -DataSource $Path -Query "INSERT OR REPLACE INTO result_history (run_id, pib, year, version, number, $($names)) VALUES (@run_id, @pib, @year, @version, @number, $($values))" -SqlParameters $parameters
+}
+
+# Vrijednosti kompanije iz svih pokretanja (Company je PIB ili dio naziva), sortirane po izvjestaju i vremenu pokretanja
+function Get-EprijavaDatabaseHistory {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [string]$Company
+	)
+
+	Initialize-EprijavaDatabase -Path $Path
+	$metricColumns = @(Get-EprijavaMetricColumns)
+	$filter = if ($Company -match '^\d{8}$') { "h.pib = @company" } else { "c.name LIKE @company" }
+	$value = if ($Company -match '^\d{8}$') { $Company } else { "%$($Company)%" }
+
+	foreach ($row in (Invoke-SqliteQuery -DataSource $Path -Query "SELECT r.started_at, c.name, h.* FROM result_history h JOIN runs r ON r.id = h.run_id JOIN companies c ON c.pib = h.pib WHERE $($filter) ORDER BY c.name, h.year, h.version, r.started_at" -SqlParameters @{ company = $value })) {
+		$snapshot = [PSCustomObject]@{
+			PSTypeName = "Eprijava.HistorySnapshot"
+			RunAt      = [datetime]::Parse($row.started_at, [cultureinfo]::InvariantCulture, [System.Globalization.DateTimeStyles]::RoundtripKind).ToLocalTime()
+			Name       = $row.name
+			Pib        = $row.pib
+			Year       = [int]$row.year
+			Version    = [int]$row.version
+			Number     = if ($row.number -is [System.DBNull]) { $null } else { $row.number }
+		}
+		foreach ($column in $metricColumns) {
+			$columnValue = $row.($column.Name)
+			$columnValue = if ($columnValue -is [System.DBNull]) { $null } else { $columnValue -as $column.Type }
+			$snapshot | Add-Member -NotePropertyName $column.Property -NotePropertyValue $columnValue
+		}
+		$snapshot
+	}
+}
+
+# Izmjene vrijednosti izmedju uzastopnih pokretanja za isti izvjestaj (kompanija, godina i verzija)
+# Prvo pokretanje u kojem se izvjestaj pojavio nije izmjena
+function Compare-EprijavaHistory {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Snapshots
+	)
+
+	$fields = @(@{ Header = "number"; Property = "Number" }) + @(Get-EprijavaMetricColumns | ForEach-Object { @{ Header = $_.Header; Property = $_.Property } })
+	foreach ($statement in ($Snapshots | Group-Object Pib, Year, Version)) {
+		$runs = @($statement.Group | Sort-Object RunAt)
+		for ($i = 1; $i -lt $runs.Count; $i++) {
+			foreach ($field in $fields) {
+				$previous = $runs[$i - 1].($field.Property)
+				$current = $runs[$i].($field.Property)
+				if ("$previous" -ne "$current") {
+					[PSCustomObject]@{
+						PSTypeName    = "Eprijava.HistoryChange"
+						Name          = $runs[$i].Name
+						Pib           = $runs[$i].Pib
+						Year          = $runs[$i].Year
+						Version       = $runs[$i].Version
+						Field         = $field.Header
+						PreviousValue = $previous
+						Value         = $current
+						PreviousRunAt = $runs[$i - 1].RunAt
+						RunAt         = $runs[$i].RunAt
+					}
+				}
+			}
+		}
+	}
+}
+
+# Vrijednosti kakve su bile u zadatom trenutku: za svaki izvjestaj posljednje pokretanje do AsOf
+function Select-EprijavaHistorySnapshot {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Snapshots,
+		[Parameter(Mandatory)] [datetime]$AsOf
+	)
+
+	foreach ($statement in ($Snapshots | Where-Object { $_.RunAt -le $AsOf } | Group-Object Pib, Year, Version)) {
+		$statement.Group | Sort-Object RunAt | Select-Object -Last 1
+	}
+}
@@ -0,0 +1,83 @@
+# Zbirni pregled sektora po godinama (Summary.csv): ukupni prihodi, dobit i broj zaposlenih svih kompanija i medijane
+# Redovi pregleda po oznakama (Get-EprijavaTagSummary) se upisuju ispod pregleda sektora, sa kolonom tag
+
+# Medijana brojeva, $null vrijednosti se zanemaruju, a za praznu listu je rezultat $null
+function Get-EprijavaMedian {
+	param(
+		[AllowEmptyCollection()] [object[]]$Values
+	)
+
+	$sorted = @($Values | Where-Object { $null -ne $_ } | ForEach-Object { [decimal]$_ } | Sort-Object)
+	if ($sorted.Count -eq 0) {
+		return $null
+	}
+
+	$middle = [math]::Floor($sorted.Count / 2)
+	if ($sorted.Count % 2 -eq 1) {
+		return $sorted[$middle]
+	}
+	ConvertTo-EprijavaMoney -Value (($sorted[$middle - 1] + $sorted[$middle]) / 2)
+}
+
+# Zbir brojeva bez $null vrijednosti, $null ukoliko nijedna vrijednost nije poznata
+function Get-EprijavaTotal {
+	param(
+		[AllowEmptyCollection()] [object[]]$Values
+	)
+
+	$known = @($Values | Where-Object { $null -ne $_ })
+	if ($known.Count -eq 0) {
+		return $null
+	}
+
+	# Measure-Object sabira kao [double], pa se zbir racuna u [decimal] bez gubitka centova
+	$total = [decimal]0
+	foreach ($value in $known) {
+		$total += [decimal]$value
+	}
+	$total
+}
+
+# Pregled za svaku godinu skupa podataka (samo posljednje verzije izvjestaja)
+# Ukupne vrijednosti sabiraju poznate podatke kompanija, a medijane se racunaju samo za kompanije koje imaju podatak
+function Get-EprijavaSectorSummary {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	$latest = @($Results | Where-Object { $_.Latest -ne 0 })
+	foreach ($year in ($latest | Group-Object { [int]$_.Year } | Sort-Object { [int]$_.Name })) {
+		$totalIncome = Get-EprijavaTotal -Values @($year.Group.TotalIncome)
+		$profit = Get-EprijavaTotal -Values @($year.Group.Profit)
+		$employees = Get-EprijavaTotal -Values @($year.Group.EmployeeCount)
+
+		[PSCustomObject]@{
+			PSTypeName          = "Eprijava.SectorSummary"
+			Year                = [int]$year.Name
+			Companies           = $year.Count
+			TotalIncome         = if ($null -ne $totalIncome) { ConvertTo-EprijavaMoney -Value $totalIncome } else { $null }
+			Profit              = if ($null -ne $profit) { ConvertTo-EprijavaMoney -Value $profit } else { $null }
+			EmployeeCount       = if ($null -ne $employees) { [int]$employees } else { $null }
+			MedianTotalIncome   = Get-EprijavaMedian -Values @($year.Group.TotalIncome)
+			MedianAveragePay    = Get-EprijavaMedian -Values @($year.Group.AveragePay)
+			MedianEmployeeCount = Get-EprijavaMedian -Values @($year.Group.EmployeeCount)
+		}
+	}
+}
+
+# Upis pregleda u CSV fajl
+function Export-EprijavaSectorSummary {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Summary
+	)
+
+	$columns = $script:SummaryColumns
+	if (@($Summary | Where-Object { $_.PSObject.Properties["Tag"] }).Count -gt 0) {
+		$columns = @(@{ Header = "tag"; Property = "Tag"; Type = [string] }) + $script:SummaryColumns
+	}
+	Set-EprijavaResultFile -Path $Path -Columns $columns
+	if ($Summary.Count -gt 0) {
+		Add-Content -Path $Path -Value @($Summary | ForEach-Object { ConvertTo-EprijavaResultLine -Result $_ -Columns $columns })
+	}
+}
@@ -0,0 +1,73 @@
+# Oznake kompanija iz konfiguracionog fajla (kljuc Tags, npr. outsourcing, product, gaming), za prikupljanje i izvjestaje
+# samo za dio kompanija (-Tag) i zbirni pregled po oznakama
+
+# Oznake po PIB-u ili nazivu kompanije
+$script:CompanyTags = @{}
+
+# Oznake iz konfiguracije: za svaku oznaku lista PIB-ova ili naziva kompanija, $null uklanja zadate oznake
+function Set-EprijavaCompanyTags {
+	param(
+		[AllowNull()] [System.Collections.IDictionary]$Tags
+	)
+
+	$script:CompanyTags = @{}
+	if (-not $Tags) {
+		return
+	}
+	foreach ($tag in $Tags.Keys) {
+		foreach ($company in @($Tags[$tag])) {
+			$key = "$company".Trim()
+			if (-not $script:CompanyTags.ContainsKey($key)) {
+				$script:CompanyTags[$key] = [System.Collections.Generic.List[string]]::new()
+			}
+			$script:CompanyTags[$key].Add("$tag")
+		}
+	}
+}
+
+# Sve zadate oznake, po abecedi
+function Get-EprijavaTags {
+	@($script:CompanyTags.Values | ForEach-Object { $_ } | Sort-Object -Unique)
+}
+
+# Oznake kompanije iz liste ili rezultata, po PIB-u ili nazivu
+function Get-EprijavaCompanyTags {
+	param(
+		[Parameter(Mandatory)] $Result
+	)
+
+	$tags = foreach ($key in @($Result.Pib, $Result.Name)) {
+		if ($key -and $script:CompanyTags.ContainsKey("$key")) {
+			$script:CompanyTags["$key"]
+		}
+	}
+	@($tags | Sort-Object -Unique)
+}
+
+# Propustanje samo kompanija ili rezultata sa bar jednom od zadatih oznaka, bez zadatih oznaka propustaju se svi
+function Select-EprijavaTag {
+	param(
+		[Parameter(ValueFromPipeline)] $InputObject,
+		[string[]]$Tag = @()
+	)
+
+	process {
+		if ($Tag.Count -eq 0 -or @(Get-EprijavaCompanyTags -Result $InputObject | Where-Object { $Tag -contains $_ }).Count -gt 0) {
+			$InputObject
+		}
+	}
+}
+
+# Zbirni pregled po godinama (kao Get-EprijavaSectorSummary) za kompanije svake oznake, uz svojstvo Tag
+function Get-EprijavaTagSummary {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results
+	)
+
+	foreach ($tag in (Get-EprijavaTags)) {
+		$tagged = @($Results | Select-EprijavaTag -Tag $tag)
+		foreach ($row in (Get-EprijavaSectorSummary -Results $tagged)) {
+			$row | Add-Member -NotePropertyName Tag -NotePropertyValue $tag -PassThru
+		}
+	}
+}
@@ -0,0 +1,88 @@
+# Provjera rezultata prije objavljivanja: redovi sa nevjerovatnim vrijednostima (najcesce posljedica pogresno
+# prepoznatih pozicija izvjestaja) se navode u anomalies.csv, a rezultati se ne mijenjaju
+
+# Minimalna neto zarada u Crnoj Gori od zadate godine (za provjeru prosjecne zarade)
+$script:MinimumWages = [ordered]@{
+	2013 = [decimal]193
+	2019 = [decimal]222
+	2022 = [decimal]450
+	2024 = [decimal]600
+}
+
+# Minimalna neto zarada koja je vazila u godini, $null za godine prije prve poznate vrijednosti
+function Get-EprijavaMinimumWage {
+	param(
+		[Parameter(Mandatory)] [int]$Year
+	)
+
+	$wage = $null
+	foreach ($entry in $script:MinimumWages.GetEnumerator()) {
+		if ($entry.Key -le $Year) {
+			$wage = $entry.Value
+		}
+	}
+
+	$wage
+}
+
+# Anomalije za svaki izvjestaj (sve verzije): prosjecna zarada ispod minimalne, prihod po zaposlenom iznad granice,
+# neto rezultat veci od ukupnih prihoda i troskovi zarada bez zaposlenih
+# MinimumWage = 0 znaci minimalna zarada za godinu izvjestaja (Get-EprijavaMinimumWage)
+function Get-EprijavaAnomalies {
+	param(
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Results,
+		[decimal]$MinimumWage = 0,
+		[decimal]$MaxRevenuePerEmployee = 1000000
+	)
+
+	foreach ($result in $Results) {
+		$checks = @()
+
+		$wage = if ($MinimumWage -gt 0) { $MinimumWage } else { Get-EprijavaMinimumWage -Year $result.Year }
+		if ($null -ne $wage -and $null -ne $result.AveragePay -and $result.EmployeeCount -gt 0 -and $result.AveragePay -lt $wage) {
+			$checks += @{ Check = "BelowMinimumWage"; Value = $result.AveragePay; Limit = $wage; Message = "Prosjecna zarada $($result.AveragePay) je manja od minimalne zarade $($wage)" }
+		}
+
+		if ($null -ne $result.TotalIncome -and $result.EmployeeCount -gt 0) {
+			$revenuePerEmployee = ConvertTo-EprijavaMoney -Value ([decimal]$result.TotalIncome / [decimal]$result.EmployeeCount)
+			if ($revenuePerEmployee -gt $MaxRevenuePerEmployee) {
+				$checks += @{ Check = "RevenuePerEmployee"; Value = $revenuePerEmployee; Limit = $MaxRevenuePerEmployee; Message = "Prihod po zaposlenom $($revenuePerEmployee) je veci od $($MaxRevenuePerEmployee)" }
+			}
+		}
+
+		if ($null -ne $result.Profit -and $null -ne $result.TotalIncome -and $result.Profit -gt 0 -and $result.Profit -gt $result.TotalIncome) {
+			$checks += @{ Check = "ProfitAboveRevenue"; Value = $result.Profit; Limit = $result.TotalIncome; Message = "Neto rezultat $($result.Profit) je veci od ukupnih prihoda $($result.TotalIncome)" }
+		}
+
+		if ($result.EmployeeCount -eq 0 -and $null -ne $result.EmployeeCount -and $result.NetPayCosts -gt 0) {
+			$checks += @{ Check = "PayWithoutEmployees"; Value = $result.NetPayCosts; Limit = $null; Message = "Neto troskovi zarada $($result.NetPayCosts) bez zaposlenih" }
+		}
+
+		foreach ($check in $checks) {
+			[PSCustomObject]@{
+				PSTypeName = "Eprijava.Anomaly"
+				Name       = $result.Name
+				Pib        = $result.Pib
+				Year       = [int]$result.Year
+				Version    = $result.Version
+				Check      = $check.Check
+				Value      = $check.Value
+				Limit      = $check.Limit
+				Message    = $check.Message
+			}
+		}
+	}
+}
+
+# Upis anomalija u CSV fajl (samo zaglavlje ukoliko anomalija nema)
+function Export-EprijavaAnomalies {
+	param(
+		[Parameter(Mandatory)] [string]$Path,
+		[Parameter(Mandatory)] [AllowEmptyCollection()] [object[]]$Anomalies
+	)
+
+	Set-EprijavaResultFile -Path $Path -Columns $script:AnomalyColumns
+	if ($Anomalies.Count -gt 0) {
+		Add-Content -Path $Path -Value @($Anomalies | ForEach-Object { ConvertTo-EprijavaResultLine -Result $_ -Columns $script:AnomalyColumns })
+	}
+}
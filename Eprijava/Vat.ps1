@@ -0,0 +1,94 @@
+# Provjera PIB-ova u listi obveznika PDV-a koju objavljuje Poreska uprava
+# Lista se ucitava iz preuzetog CSV fajla, a rezultati dobijaju kolone vatRegistered (PIB je u listi) i vatActive (registracija nije brisana)
+
+# Status iz liste obveznika PDV-a po PIB-u, $null = bez provjere
+$script:VatList = $null
+
+# Ucitavanje liste obveznika PDV-a iz CSV fajla (zarez ili tacka-zarez kao separator)
+# Kolona sa PIB-om je kolona ciji naziv sadrzi "pib", a status se cita iz kolone sa "status" ili "aktiv" u nazivu
+# (npr. aktivan, brisan, 1/0) ili se registracija smatra brisanom kada je popunjena kolona sa "brisanj" u nazivu; bez tih kolona je svaki PIB iz liste aktivan
+function Import-EprijavaVatList {
+	param(
+		[Parameter(Mandatory)] [string]$Path
+	)
+
+	if (-not (Test-Path -Path $Path -PathType Leaf)) {
+		throw "Lista obveznika PDV-a nije pronadjena: $($Path)"
+	}
+
+	$firstLine = Get-Content -Path $Path -Encoding UTF8 -TotalCount 1
+	$delimiter = if (([regex]::Matches($firstLine, ';')).Count -gt ([regex]::Matches($firstLine, ',')).Count) { ';' } else { ',' }
+	$rows = @(Import-Csv -Path $Path -Encoding UTF8 -Delimiter $delimiter)
+	if ($rows.Count -eq 0) {
+		throw "Lista obveznika PDV-a je prazna: $($Path)"
+	}
+
+	$headers = @($rows[0].PSObject.Properties.Name)
+	$pibColumn = $headers | Where-Object { $_ -match '(?i)pib' } | Select-Object -First 1
+	if (-not $pibColumn) {
+		throw "Lista obveznika PDV-a $($Path) nema kolonu sa PIB-om (ocekivano npr. PIB)"
+	}
+	$statusColumn = $headers | Where-Object { $_ -match '(?i)status|aktiv' } | Select-Object -First 1
+	$removedColumn = $headers | Where-Object { $_ -match '(?i)brisanj' } | Select-Object -First 1
+
+	$list = @{}
+	foreach ($row in $rows) {
+		$pib = "$($row.$pibColumn)".Trim()
+		if ($pib -notmatch '^\d{8}$') {
+			continue
+		}
+
+		$active = $true
+		if ($statusColumn) {
+			$active = "$($row.$statusColumn)".Trim() -notmatch '(?i)^(0|ne|false)$|neaktiv|brisan|ugas|odjav|inactive'
+		}
+		if ($removedColumn -and "$($row.$removedColumn)".Trim()) {
+			$active = $false
+		}
+		# PIB koji se u listi ponavlja (ponovna registracija) je aktivan ukoliko je aktivan u bilo kojem redu
+		$list[$pib] = $active -or ($list.ContainsKey($pib) -and $list[$pib])
+	}
+
+	$script:VatList = $list
+	$list.Count
+}
+
+# Da li je lista obveznika PDV-a ucitana
+function Test-EprijavaVatListLoaded {
+	$null -ne $script:VatList
+}
+
+# Status PIB-a u listi obveznika PDV-a: Registered (PIB je u listi) i Active (registracija nije brisana)
+function Get-EprijavaVatStatus {
+	param(
+		[Parameter(Mandatory)] [string]$Pib
+	)
+
+	if (-not (Test-EprijavaVatListLoaded)) {
+		throw "Lista obveznika PDV-a nije ucitana (Import-EprijavaVatList)"
+	}
+
+	$registered = $script:VatList.ContainsKey($Pib)
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.VatStatus"
+		Pib        = $Pib
+		Registered = $registered
+		Active     = $registered -and $script:VatList[$Pib]
+	}
+}
+
+# Dodavanje statusa iz liste obveznika PDV-a rezultatu (1/0), rezultat se vraca neizmijenjen kada lista nije ucitana
+function Add-EprijavaVatStatus {
+	param(
+		[Parameter(Mandatory, ValueFromPipeline)] $Result
+	)
+
+	process {
+		if ((Test-EprijavaVatListLoaded) -and $Result.Pib) {
+			$status = Get-EprijavaVatStatus -Pib $Result.Pib
+			$Result | Add-Member -NotePropertyName VatRegistered -NotePropertyValue ([int]$status.Registered) -Force
+			$Result | Add-Member -NotePropertyName VatActive -NotePropertyValue ([int]$status.Active) -Force
+		}
+		$Result
+	}
+}
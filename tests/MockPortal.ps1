@@ -0,0 +1,123 @@
+# Lokalni testni server koji umjesto portala ePrijava vraca snimljene odgovore iz foldera fixtures
+# Zahtjev se uparuje sa rutom po putanji i upitu (npr. FinancialStatement/Details?rbr=55136/2020), a zatim samo po putanji
+
+# Slobodan lokalni port za testni server
+function Get-MockPortalPort {
+	$tcpListener = [System.Net.Sockets.TcpListener]::new([System.Net.IPAddress]::Loopback, 0)
+	$tcpListener.Start()
+	try {
+		$tcpListener.LocalEndpoint.Port
+	}
+	finally {
+		$tcpListener.Stop()
+	}
+}
+
+# Pokretanje servera sa rutama u obliku @{ "<PUTANJA>" = @{ File; ContentType; Status; Cookie; ETag; Session; LoginFile } }, putanja je relativna u odnosu na /TaxisPortal
+# Nepoznate putanje vracaju 404, ruta sa ETag vraca 304 za zahtjev sa istim If-None-Match,
+# ruta sa Session vraca LoginFile (stranica za prijavu) za zahtjev bez cookie-ja taxisSession=<Session>,
+# a svi primljeni zahtjevi se biljeze u Requests kao "<METOD> <PUTANJA>"
+function Start-MockPortal {
+	param(
+		[Parameter(Mandatory)] [hashtable]$Routes
+	)
+
+	$port = Get-MockPortalPort
+	$listener = [System.Net.HttpListener]::new()
+	$listener.Prefixes.Add("http://127.0.0.1:$($port)/")
+	$listener.Start()
+
+	$requests = [System.Collections.ArrayList]::Synchronized([System.Collections.ArrayList]::new())
+	$powershell = [powershell]::Create()
+	$null = $powershell.AddScript({
+		param($Listener, $Routes, $Requests)
+
+		while ($Listener.IsListening) {
+			try {
+				$context = $Listener.GetContext()
+			}
+			catch {
+				break
+			}
+
+			$path = [uri]::UnescapeDataString($context.Request.Url.PathAndQuery) -replace '^/TaxisPortal/?', ''
+			$null = $Requests.Add("$($context.Request.HttpMethod) $($path)")
+
+			$route = $Routes[$path]
+			if (-not $route) {
+				$route = $Routes[($path -split '\?')[0]]
+			}
+
+			$response = $context.Response
+			if (-not $route) {
+				$response.StatusCode = 404
+				$response.Close()
+				continue
+			}
+
+			if ($route.Session -and $context.Request.Headers["Cookie"] -notmatch "taxisSession=$([regex]::Escape($route.Session))(;|$)") {
+				$route = @{ File = $route.LoginFile }
+			}
+
+			if ($route.ETag -and $context.Request.Headers["If-None-Match"] -eq $route.ETag) {
+				$response.StatusCode = 304
+				$response.Close()
+				continue
+			}
+			if ($route.ETag) {
+				$response.Headers.Add("ETag", $route.ETag)
+			}
+
+			$body = if ($route.File) { [IO.File]::ReadAllBytes($route.File) } else { [byte[]]@() }
+			$response.StatusCode = if ($route.Status) { $route.Status } else { 200 }
+			$response.ContentType = if ($route.ContentType) { $route.ContentType } else { "text/html; charset=utf-8" }
+			if ($route.Cookie) {
+				$response.Headers.Add("Set-Cookie", $route.Cookie)
+			}
+			$response.ContentLength64 = $body.Length
+			$response.OutputStream.Write($body, 0, $body.Length)
+			$response.Close()
+		}
+	}).AddArgument($listener).AddArgument($Routes).AddArgument($requests)
+
+	[PSCustomObject]@{
+		PSTypeName = "Eprijava.MockPortal"
+		Url        = "http://127.0.0.1:$($port)/TaxisPortal"
+		Requests   = $requests
+		Listener   = $listener
+		PowerShell = $powershell
+		Handle     = $powershell.BeginInvoke()
+	}
+}
+
+# Zaustavljanje testnog servera
+function Stop-MockPortal {
+	param(
+		[Parameter(Mandatory)] $Server
+	)
+
+	$Server.Listener.Stop()
+	$Server.Listener.Close()
+	$null = $Server.PowerShell.EndInvoke($Server.Handle)
+	$Server.PowerShell.Dispose()
+}
+
+# Rute snimljenih odgovora portala za kompaniju Coinis (03014215): tri izvjestaja u sva tri formata HTML-a,
+# od kojih je izvjestaj za 2020. godinu izmijenjen (druga verzija je u formatu v2), lista izvjestaja u dvije stranice po dva izvjestaja
+# i PDF verzija izvjestaja (isti PDF za sve izvjestaje)
+function Get-MockPortalRoutes {
+	$fixtures = "$PSScriptRoot/fixtures"
+
+	@{
+		"FinancialStatement"                                                                     = @{ File = "$fixtures/details.html"; Cookie = "taxisSession=mock-session; path=/" }
+		"FinancialStatement/Grid"                                                                = @{ File = "$fixtures/grid.json"; ContentType = "application/json; charset=utf-8" }
+		"FinancialStatement/TaxPayerStatementsList"                                              = @{ File = "$fixtures/statements-list.json"; ContentType = "application/json; charset=utf-8" }
+		"TaxPayerCompanies/Details"                                                              = @{ File = "$fixtures/details.html" }
+		"FinancialStatement/TaxPayerStatementsList?PIB=03014215&take=2&skip=0&page=1&pageSize=2" = @{ File = "$fixtures/statements-page1.json"; ContentType = "application/json; charset=utf-8" }
+		"FinancialStatement/TaxPayerStatementsList?PIB=03014215&take=2&skip=2&page=2&pageSize=2" = @{ File = "$fixtures/statements-page2.json"; ContentType = "application/json; charset=utf-8" }
+		"FinancialStatement/Details?rbr=55136/2020"                                              = @{ File = "$fixtures/report-v1.html" }
+		"FinancialStatement/Details?rbr=48213/2021"                                              = @{ File = "$fixtures/report-legacy.html" }
+		"FinancialStatement/Details?rbr=61002/2021"                                              = @{ File = "$fixtures/report-v2.html" }
+		"FinancialStatement/DetailsPdf"                                                          = @{ File = "$fixtures/report.pdf"; ContentType = "application/pdf" }
+	}
+}